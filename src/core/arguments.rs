@@ -0,0 +1,177 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::constants::CONFIG_FILE;
+use core::settings::Overrides;
+
+use log::Level;
+
+use std::error::Error;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+type Result<T> = std::result::Result<T, Box<Error>>;
+
+/// Port used when `--server` has none
+pub const DEFAULT_PORT: u16 = 25565;
+
+/// Name used when `--username` is missing
+pub const DEFAULT_USERNAME: &str = "Player";
+
+/// Shown with `--help` or after an invalid argument
+pub const USAGE: &str = "Usage: litecraft [OPTIONS]
+
+Options:
+    --config <FILE>           Configuration file [default: litecraft.yml]
+    --game-dir <DIR>          Folder with resources, resourcepacks and configuration
+    --width <PIXELS>          Window width for this run
+    --height <PIXELS>         Window height for this run
+    --fullscreen              Start in fullscreen for this run
+    --windowed                Start windowed for this run
+    --username <NAME>         Player name [default: Player]
+    --server <HOST:PORT>      Connect to a server after loading
+    --log-level <LEVEL>       One of error, warn, info, debug or trace [default: info]
+    --import-options <FILE>   Import a vanilla options.txt into configuration
+    --help                    Show this message";
+
+/// Server address given with `--server`
+#[derive(Clone, Debug, PartialEq)]
+pub struct ServerAddress {
+    pub host: String,
+    pub port: u16,
+}
+
+impl FromStr for ServerAddress {
+    type Err = Box<Error>;
+
+    fn from_str(value: &str) -> Result<ServerAddress> {
+        let invalid = || format!("Invalid server address '{}'", value);
+
+        // Bracketed IPv6 addresses take a port after the bracket, bare ones never have a port
+        let (host, port) = if value.starts_with('[') {
+            let close = value.find(']').ok_or_else(invalid)?;
+
+            let port = match &value[close + 1..] {
+                "" => DEFAULT_PORT,
+                rest if rest.starts_with(':') => rest[1..].parse::<u16>()?,
+                _ => return Err(invalid().into()),
+            };
+
+            (&value[1..close], port)
+        } else {
+            match value.rfind(':') {
+                Some(colon) if !value[..colon].contains(':') => {
+                    (&value[..colon], value[colon + 1..].parse::<u16>()?)
+                },
+                _ => (value, DEFAULT_PORT),
+            }
+        };
+
+        if host.is_empty() {
+            return Err(invalid().into());
+        }
+
+        Ok(ServerAddress {
+            host: host.to_owned(),
+            port,
+        })
+    }
+}
+
+/// Command line arguments, they apply to this run only and are never saved
+#[derive(Clone, Debug, PartialEq)]
+pub struct Arguments {
+    pub config: PathBuf,
+    pub game_dir: Option<PathBuf>,
+    pub overrides: Overrides,
+    pub username: String,
+    pub server: Option<ServerAddress>,
+    pub log_level: Level,
+    pub import_options: Option<PathBuf>,
+    pub help: bool,
+}
+
+impl Default for Arguments {
+    fn default() -> Arguments {
+        Arguments {
+            config: PathBuf::from(CONFIG_FILE),
+            game_dir: None,
+            overrides: Overrides::default(),
+            username: DEFAULT_USERNAME.to_owned(),
+            server: None,
+            log_level: Level::Info,
+            import_options: None,
+            help: false,
+        }
+    }
+}
+
+impl Arguments {
+    /// Parse arguments without program name, flags take values as `--flag value` or `--flag=value`
+    pub fn parse<I>(args: I) -> Result<Arguments>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut arguments = Arguments::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let (flag, inline) = match arg.find('=') {
+                Some(equals) if arg.starts_with("--") => {
+                    (arg[..equals].to_owned(), Some(arg[equals + 1..].to_owned()))
+                },
+                _ => (arg.clone(), None),
+            };
+
+            let mut value = || -> Result<String> {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| format!("Missing value for {}", flag).into())
+            };
+
+            match flag.as_str() {
+                "--config" => arguments.config = PathBuf::from(value()?),
+                "--game-dir" | "--gameDir" => arguments.game_dir = Some(PathBuf::from(value()?)),
+                "--width" => arguments.overrides.width = Some(parse_number(&flag, &value()?)?),
+                "--height" => arguments.overrides.height = Some(parse_number(&flag, &value()?)?),
+                "--fullscreen" => arguments.overrides.fullscreen = Some(true),
+                "--windowed" => arguments.overrides.fullscreen = Some(false),
+                "--username" => arguments.username = value()?,
+                "--server" => arguments.server = Some(value()?.parse()?),
+                "--log-level" => {
+                    let level = value()?;
+
+                    arguments.log_level = level
+                        .parse()
+                        .map_err(|_| format!("Invalid log level '{}'", level))?;
+                },
+                "--import-options" => arguments.import_options = Some(PathBuf::from(value()?)),
+                "--help" | "-h" => arguments.help = true,
+                _ => return Err(format!("Unknown argument '{}'", arg).into()),
+            }
+        }
+
+        Ok(arguments)
+    }
+}
+
+/// Parse a positive number given to a flag
+fn parse_number(flag: &str, value: &str) -> Result<u32> {
+    match value.parse::<u32>() {
+        Ok(number) if number > 0 => Ok(number),
+        _ => Err(format!("Invalid value '{}' for {}", value, flag).into()),
+    }
+}
@@ -0,0 +1,224 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::camera::perspective::Perspective;
+use core::camera::{Camera, Movement};
+use core::controls::Action;
+
+use gfx::canvas::Canvas;
+
+use cgmath::prelude::*;
+use cgmath::Vector3;
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+/// Walking speed in blocks per second
+const WALK_SPEED: f32 = 4.317;
+
+/// Sprinting speed in blocks per second
+const SPRINT_SPEED: f32 = 5.612;
+
+/// Degrees turned per pixel at vanilla's highest sensitivity
+const DEGREES_PER_PIXEL: f32 = 1.2;
+
+/// How fast cinematic camera catches up with the mouse, fraction of distance left per second
+const SMOOTHING: f32 = 4.0;
+
+/// Longest frame taken into account, so a hiccup doesn't throw the camera away
+const MAX_ELAPSED: f32 = 0.25;
+
+/// Turns mouse movement and movement actions into first-person camera motion
+pub struct CameraController {
+    sensitivity: f32,
+    invert: bool,
+    smooth: bool,
+    sprinting: bool,
+    moving: HashSet<Movement>,
+    pending: (f32, f32),
+    perspective: Perspective,
+}
+
+impl CameraController {
+    /// Create a controller with vanilla mouse sensitivity, from 0.0 to 1.0
+    pub fn new(sensitivity: f32, invert: bool) -> CameraController {
+        CameraController {
+            sensitivity,
+            invert,
+            smooth: false,
+            sprinting: false,
+            moving: HashSet::new(),
+            pending: (0.0, 0.0),
+            perspective: Perspective::FirstPerson,
+        }
+    }
+
+    /// Set vanilla mouse sensitivity, from 0.0 to 1.0
+    pub fn set_sensitivity(&mut self, sensitivity: f32) { self.sensitivity = sensitivity }
+
+    /// Check if cinematic camera is on
+    pub fn smooth(&self) -> bool { self.smooth }
+
+    /// Turn cinematic camera on or off
+    pub fn set_smooth(&mut self, smooth: bool) {
+        self.smooth = smooth;
+        self.pending = (0.0, 0.0);
+    }
+
+    /// Get where the camera is placed relative to the player
+    pub fn perspective(&self) -> Perspective { self.perspective }
+
+    /// Set where the camera is placed relative to the player
+    pub fn set_perspective(&mut self, perspective: Perspective) { self.perspective = perspective }
+
+    /// Check if the local player model should be drawn
+    pub fn render_player(&self) -> bool { self.perspective.shows_player() }
+
+    /// Grab and hide cursor so mouse turns the camera, or give it back
+    pub fn grab(&mut self, canvas: &mut Canvas, grab: bool) {
+        canvas.set_cursor_grab(grab);
+
+        if !grab {
+            self.release();
+        }
+    }
+
+    /// Stop moving, keys released while the cursor was free or the window unfocused never reach us
+    pub fn release(&mut self) {
+        self.moving.clear();
+        self.sprinting = false;
+    }
+
+    /// Degrees turned per pixel, same curve as vanilla
+    fn degrees_per_pixel(&self) -> f32 {
+        let sensitivity = self.sensitivity * 0.6 + 0.2;
+
+        sensitivity * sensitivity * sensitivity * DEGREES_PER_PIXEL
+    }
+
+    /// Turn camera by a raw mouse movement in pixels, ignored while cursor is free
+    pub fn look(&mut self, canvas: &Canvas, camera: &mut Camera, dx: f64, dy: f64) {
+        if !canvas.cursor_grabbed() {
+            return;
+        }
+
+        let scale = self.degrees_per_pixel();
+        let invert = if self.invert { 1.0 } else { -1.0 };
+
+        let yaw = dx as f32 * scale;
+        let pitch = dy as f32 * scale * invert;
+
+        if self.smooth {
+            self.pending.0 += yaw;
+            self.pending.1 += pitch;
+        } else {
+            camera.rotate(yaw, pitch);
+        }
+    }
+
+    /// Start or stop moving in a direction
+    pub fn set_moving(&mut self, movement: Movement, moving: bool) {
+        if moving {
+            self.moving.insert(movement);
+        } else {
+            self.moving.remove(&movement);
+        }
+    }
+
+    /// Handle a bound action being pressed or released, returns false if it doesn't move the camera
+    pub fn action(&mut self, action: Action, pressed: bool) -> bool {
+        let movement = match action {
+            Action::Forward => Movement::FORWARD,
+            Action::Back => Movement::BACKWARD,
+            Action::Left => Movement::LEFT,
+            Action::Right => Movement::RIGHT,
+            Action::Jump => Movement::UP,
+            Action::Sneak => Movement::DOWN,
+            Action::Sprint => {
+                self.sprinting = pressed;
+                return true;
+            },
+            Action::TogglePerspective => {
+                if pressed {
+                    self.perspective = self.perspective.next();
+                }
+
+                return true;
+            },
+            Action::SmoothCamera => {
+                if pressed {
+                    let smooth = !self.smooth;
+                    self.set_smooth(smooth);
+                }
+
+                return true;
+            },
+            _ => return false,
+        };
+
+        self.set_moving(movement, pressed);
+        true
+    }
+
+    /// Move camera by time passed since last update, so speed doesn't depend on frame rate
+    pub fn update(&mut self, camera: &mut Camera, elapsed: Duration) {
+        let elapsed = (elapsed.as_secs() as f32 + elapsed.subsec_nanos() as f32 / 1_000_000_000.0)
+            .min(MAX_ELAPSED);
+
+        if self.smooth {
+            let fraction = 1.0 - (-SMOOTHING * elapsed).exp();
+            let (yaw, pitch) = (self.pending.0 * fraction, self.pending.1 * fraction);
+
+            camera.rotate(yaw, pitch);
+
+            self.pending.0 -= yaw;
+            self.pending.1 -= pitch;
+        }
+
+        // Walking ignores pitch, like vanilla
+        let front = camera.front();
+        let forward = Vector3::new(front.x, 0.0, front.z);
+        let forward = if forward.magnitude2() > 0.0 {
+            forward.normalize()
+        } else {
+            forward
+        };
+
+        let right = camera.right();
+        let up = Vector3::unit_y();
+
+        let direction = self.moving.iter().fold(Vector3::zero(), |direction, movement| {
+            direction
+                + match movement {
+                    Movement::FORWARD => forward,
+                    Movement::BACKWARD => -forward,
+                    Movement::LEFT => -right,
+                    Movement::RIGHT => right,
+                    Movement::UP => up,
+                    Movement::DOWN => -up,
+                }
+        });
+
+        // Opposite keys cancel out
+        if direction.magnitude2() < 1e-6 {
+            return;
+        }
+
+        let speed = if self.sprinting { SPRINT_SPEED } else { WALK_SPEED };
+        let position = camera.position() + direction.normalize() * speed * elapsed;
+
+        camera.set_position(position);
+    }
+}
@@ -0,0 +1,84 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use cgmath::prelude::*;
+use cgmath::{Matrix4, Point3, Vector3, Vector4};
+
+/// Volume seen by a camera, six planes facing inwards
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Frustum {
+    /// Left, right, bottom, top, near and far planes as `(normal, distance)`
+    planes: [Vector4<f32>; 6],
+}
+
+impl Frustum {
+    /// Extract planes from a combined projection and view matrix
+    pub fn from_matrix(matrix: Matrix4<f32>) -> Frustum {
+        let (x, y, z, w) = (matrix.row(0), matrix.row(1), matrix.row(2), matrix.row(3));
+
+        let mut planes = [w + x, w - x, w + y, w - y, w + z, w - z];
+
+        for plane in &mut planes {
+            let length = plane.truncate().magnitude();
+
+            // Infinite projections have no far plane, make it accept everything
+            *plane = if length > f32::EPSILON {
+                *plane / length
+            } else {
+                Vector4::new(0.0, 0.0, 0.0, 1.0)
+            };
+        }
+
+        Frustum { planes }
+    }
+
+    /// Signed distance from a plane, positive inside
+    fn distance(plane: Vector4<f32>, point: Point3<f32>) -> f32 {
+        plane.truncate().dot(point.to_vec()) + plane.w
+    }
+
+    /// Check if a point is inside
+    pub fn contains_point(&self, point: Point3<f32>) -> bool {
+        self.planes
+            .iter()
+            .all(|&plane| Frustum::distance(plane, point) >= 0.0)
+    }
+
+    /// Check if an axis aligned box is at least partially inside, may give false positives near corners
+    pub fn intersects_aabb(&self, min: Point3<f32>, max: Point3<f32>) -> bool {
+        self.planes.iter().all(|&plane| {
+            // Corner furthest along plane normal
+            let corner = Point3::new(
+                if plane.x >= 0.0 { max.x } else { min.x },
+                if plane.y >= 0.0 { max.y } else { min.y },
+                if plane.z >= 0.0 { max.z } else { min.z },
+            );
+
+            Frustum::distance(plane, corner) >= 0.0
+        })
+    }
+
+    /// Check if a sphere is at least partially inside
+    pub fn intersects_sphere(&self, center: Point3<f32>, radius: f32) -> bool {
+        self.planes
+            .iter()
+            .all(|&plane| Frustum::distance(plane, center) >= -radius)
+    }
+
+    /// Check if a block, given by its lowest corner, is at least partially inside
+    pub fn intersects_block(&self, position: Point3<f32>) -> bool {
+        self.intersects_aabb(position, position + Vector3::new(1.0, 1.0, 1.0))
+    }
+}
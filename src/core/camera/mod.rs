@@ -14,21 +14,45 @@
 // IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 use cgmath::prelude::*;
-use cgmath::{ortho, perspective, Deg, Euler, Matrix4, Point3, Quaternion, Vector3};
+use cgmath::{ortho, perspective, Deg, Matrix4, Point3, Vector3, Vector4};
+use core::settings::Settings;
+
 use std::f32;
 
+pub mod controller;
+pub mod frustum;
+pub mod perspective;
+
+use self::frustum::Frustum;
+
 const YAW: f32 = -90.0;
 const PITCH: f32 = 0.0;
 
+/// Looking straight up or down flips the view, so stop just before
+const MAX_PITCH: f32 = 89.9;
+
+/// Closest distance drawn, small enough to not clip blocks touching the player
+const NEAR_PLANE: f32 = 0.05;
+
+/// Render distance in chunks until told otherwise
+const RENDER_DISTANCE: u8 = 12;
+
+/// Blocks on each side of a chunk
+const CHUNK_SIZE: f32 = 16.0;
+
 /// Camera movement direction
-enum Movement {
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Movement {
     FORWARD,
     BACKWARD,
     LEFT,
     RIGHT,
+    UP,
+    DOWN,
 }
 
 /// Basic camera
+#[derive(Clone, Debug)]
 pub struct Camera {
     // Camera Attributes
     position: Point3<f32>,
@@ -37,6 +61,7 @@ pub struct Camera {
     pitch: f32,
 
     fov: f32,
+    render_distance: u8,
 
     // Window size
     width: u32,
@@ -59,6 +84,7 @@ impl Camera {
             pitch: PITCH,
 
             fov: 60.0,
+            render_distance: RENDER_DISTANCE,
 
             width: 800,
             height: 600,
@@ -71,6 +97,12 @@ impl Camera {
         self.height = height;
     }
 
+    /// Match window size and render distance in settings, called every frame before drawing
+    pub fn apply_settings(&mut self, settings: &Settings) {
+        self.aspect_ratio(settings.width(), settings.height());
+        self.set_render_distance(settings.render_distance());
+    }
+
     /// Get current camera position
     pub fn position(&self) -> Point3<f32> { self.position }
 
@@ -80,6 +112,20 @@ impl Camera {
     /// Get current camera pitch
     pub fn pitch(&self) -> f32 { self.pitch }
 
+    /// Get direction the camera is looking at
+    pub fn front(&self) -> Vector3<f32> {
+        let (yaw, pitch) = (self.yaw.to_radians(), self.pitch.to_radians());
+
+        Vector3::new(yaw.cos() * pitch.cos(), pitch.sin(), yaw.sin() * pitch.cos()).normalize()
+    }
+
+    /// Get horizontal direction to the right of the camera
+    pub fn right(&self) -> Vector3<f32> {
+        let yaw = self.yaw.to_radians();
+
+        Vector3::new(-yaw.sin(), 0.0, yaw.cos())
+    }
+
     /// Set camera position
     pub fn set_position(&mut self, pos: Point3<f32>) -> &Camera {
         self.position = pos;
@@ -98,25 +144,62 @@ impl Camera {
         self
     }
 
+    /// Turn camera by some degrees, pitch is clamped so it never looks behind itself
+    pub fn rotate(&mut self, yaw: f32, pitch: f32) -> &Camera {
+        self.yaw = (self.yaw + yaw) % 360.0;
+        self.pitch = (self.pitch + pitch).max(-MAX_PITCH).min(MAX_PITCH);
+        self
+    }
+
     /// Set camera fov
     pub fn set_fov(&mut self, value: f32) -> &Camera {
         self.fov = value;
         self
     }
 
-    /// Get perspective matrix
-    pub fn perspective(&self) -> Matrix4<f32> {
-        use std::f32;
+    /// Get render distance in chunks
+    pub fn render_distance(&self) -> u8 { self.render_distance }
+
+    /// Set render distance in chunks, moving the far plane
+    pub fn set_render_distance(&mut self, chunks: u8) -> &Camera {
+        self.render_distance = chunks.max(1);
+        self
+    }
 
-        let zfar = 1024.0;
-        let znear = 1.0;
+    /// Get distance to near and far clipping planes, far plane reaches the corners of the last chunk
+    pub fn planes(&self) -> (f32, f32) {
+        let far = f32::from(self.render_distance) * CHUNK_SIZE * f32::consts::SQRT_2;
+
+        (NEAR_PLANE, far)
+    }
 
+    fn aspect(&self) -> f32 {
         let width: f32 = self.width as f32;
         let height: f32 = self.height as f32;
 
-        let aspect_ratio = (width / height).max(0.5);
+        (width / height).max(0.5)
+    }
+
+    /// Get perspective matrix
+    pub fn perspective(&self) -> Matrix4<f32> {
+        let (znear, zfar) = self.planes();
 
-        perspective(Deg(self.fov), aspect_ratio, znear, zfar)
+        perspective(Deg(self.fov), self.aspect(), znear, zfar)
+    }
+
+    /// Get perspective matrix with reversed depth and no far plane, for a 0 to 1 clip space depth range.
+    /// Near plane ends at depth 1 and infinity at 0, so depth test must be greater and clear to 0.
+    /// Float depth is most precise near 0, which evens out the precision lost with distance
+    pub fn reversed_perspective(&self) -> Matrix4<f32> {
+        let (znear, _) = self.planes();
+        let f = Deg(self.fov / 2.0).cot();
+
+        Matrix4::from_cols(
+            Vector4::new(f / self.aspect(), 0.0, 0.0, 0.0),
+            Vector4::new(0.0, f, 0.0, 0.0),
+            Vector4::new(0.0, 0.0, 0.0, -1.0),
+            Vector4::new(0.0, 0.0, znear, 0.0),
+        )
     }
 
     /// Get orthographic matrix
@@ -124,15 +207,12 @@ impl Camera {
         ortho(0.0, self.width as f32, self.height as f32, 0.0, 0.0, 1024.0)
     }
 
+    /// Get volume seen by this camera, to skip drawing what is outside
+    pub fn frustum(&self) -> Frustum { Frustum::from_matrix(self.perspective() * self.view()) }
+
     /// Get view matrix
     pub fn view(&self) -> Matrix4<f32> {
-        let look = Quaternion::from(Euler {
-            x: Deg(0.0),
-            y: Deg(self.pitch),
-            z: Deg(self.yaw),
-        });
-
-        let look = look.v.normalize();
+        let look = self.front();
         let up = Vector3::new(0.0, 1.0, 0.0);
 
         // Calculate 4x4 view matrix
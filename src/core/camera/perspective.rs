@@ -0,0 +1,90 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::camera::Camera;
+use core::raycast::{raycast, BlockView, ShapeKind};
+
+use cgmath::Vector3;
+
+/// Distance from the player's eyes in third person, in blocks
+pub const THIRD_PERSON_DISTANCE: f32 = 4.0;
+
+/// Half size of the box around the camera that must not enter blocks
+const CAMERA_SIZE: f32 = 0.1;
+
+/// Where the camera is placed relative to the player, switched with F5
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Perspective {
+    FirstPerson,
+    ThirdPersonBack,
+    ThirdPersonFront,
+}
+
+impl Default for Perspective {
+    fn default() -> Perspective { Perspective::FirstPerson }
+}
+
+impl Perspective {
+    /// Get next perspective, in vanilla order
+    pub fn next(self) -> Perspective {
+        match self {
+            Perspective::FirstPerson => Perspective::ThirdPersonBack,
+            Perspective::ThirdPersonBack => Perspective::ThirdPersonFront,
+            Perspective::ThirdPersonFront => Perspective::FirstPerson,
+        }
+    }
+
+    /// Check if the local player model should be drawn
+    pub fn shows_player(self) -> bool { self != Perspective::FirstPerson }
+
+    /// Place a camera for this perspective from the player's eyes, pulled in so it never ends inside blocks
+    pub fn place<W>(self, eyes: &Camera, world: &W) -> Camera
+    where
+        W: BlockView,
+    {
+        let mut camera = eyes.clone();
+
+        let direction = match self {
+            Perspective::FirstPerson => return camera,
+            Perspective::ThirdPersonBack => -eyes.front(),
+            Perspective::ThirdPersonFront => {
+                // Look back at the player's face
+                camera.set_yaw(eyes.yaw() + 180.0);
+                camera.set_pitch(-eyes.pitch());
+
+                eyes.front()
+            },
+        };
+
+        // Cast from every corner of the camera box, like vanilla, so near walls don't clip into view
+        let mut distance = THIRD_PERSON_DISTANCE;
+
+        for &x in &[-1.0, 1.0] {
+            for &y in &[-1.0, 1.0] {
+                for &z in &[-1.0, 1.0] {
+                    let corner = Vector3::new(x, y, z) * CAMERA_SIZE;
+                    let start = eyes.position() + corner;
+
+                    if let Some(hit) = raycast(world, start, direction, distance, ShapeKind::Collision) {
+                        distance = hit.distance;
+                    }
+                }
+            }
+        }
+
+        camera.set_position(eyes.position() + direction * distance);
+        camera
+    }
+}
@@ -24,6 +24,9 @@ pub const ASCII_ART: &str = r"
 /// Config file to lookup
 pub const CONFIG_FILE: &str = "litecraft.yml";
 
+/// WAV file written by the recording sound output
+pub const SOUND_CAPTURE_FILE: &str = "litecraft.wav";
+
 /// Client version
 pub const LITECRAFT_VERSION: &str = "A1";
 
@@ -0,0 +1,531 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use glium::glutin::{ModifiersState, MouseButton, VirtualKeyCode};
+
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Declare bindable keys, named like glutin's `VirtualKeyCode`
+macro_rules! keys {
+    ($($key:ident),* $(,)*) => {
+        /// Keyboard key that can be bound to an action
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub enum Key {
+            $($key),*
+        }
+
+        impl Key {
+            /// Get key name used in configuration files
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Key::$key => stringify!($key)),*
+                }
+            }
+
+            /// Get key by its name
+            pub fn from_name(name: &str) -> Option<Key> {
+                match name {
+                    $(stringify!($key) => Some(Key::$key),)*
+                    _ => None,
+                }
+            }
+
+            /// Get key from a window event key code
+            pub fn from_glutin(code: VirtualKeyCode) -> Option<Key> {
+                match code {
+                    $(VirtualKeyCode::$key => Some(Key::$key),)*
+                    _ => None,
+                }
+            }
+
+            /// Get window event key code
+            pub fn to_glutin(self) -> VirtualKeyCode {
+                match self {
+                    $(Key::$key => VirtualKeyCode::$key),*
+                }
+            }
+        }
+    };
+}
+
+keys! {
+    Key1, Key2, Key3, Key4, Key5, Key6, Key7, Key8, Key9, Key0,
+    A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+    Escape, F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12,
+    Snapshot, Scroll, Pause, Insert, Home, Delete, End, PageDown, PageUp,
+    Left, Up, Right, Down, Back, Return, Space, Tab, Capital, Numlock,
+    Numpad0, Numpad1, Numpad2, Numpad3, Numpad4, Numpad5, Numpad6, Numpad7, Numpad8, Numpad9,
+    Add, Subtract, Multiply, Divide, Decimal, NumpadEnter,
+    Apostrophe, Backslash, Comma, Equals, Grave, LBracket, Minus, Period, RBracket, Semicolon, Slash,
+    LAlt, LControl, LShift, LWin, RAlt, RControl, RShift, RWin,
+}
+
+/// Keyboard key, mouse button or nothing
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Input {
+    Unbound,
+    Key(Key),
+    Mouse(MouseButton),
+}
+
+/// Modifier keys that must be held
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+impl Modifiers {
+    /// Get modifiers from a window event
+    pub fn from_glutin(state: ModifiersState) -> Modifiers {
+        Modifiers {
+            ctrl: state.ctrl,
+            shift: state.shift,
+            alt: state.alt,
+        }
+    }
+
+    /// Check if no modifier is required
+    pub fn is_empty(self) -> bool { !self.ctrl && !self.shift && !self.alt }
+
+    /// Check if every modifier in `other` is also in these
+    pub fn contains(self, other: Modifiers) -> bool {
+        (self.ctrl || !other.ctrl) && (self.shift || !other.shift) && (self.alt || !other.alt)
+    }
+
+    /// Get how many modifiers are required
+    pub fn count(self) -> usize { self.ctrl as usize + self.shift as usize + self.alt as usize }
+
+    /// Drop the modifier a key sets by itself, so pressing shift alone reads as `LShift` not `Shift+LShift`
+    pub fn without(mut self, key: Key) -> Modifiers {
+        match key {
+            Key::LControl | Key::RControl => self.ctrl = false,
+            Key::LShift | Key::RShift => self.shift = false,
+            Key::LAlt | Key::RAlt => self.alt = false,
+            _ => (),
+        }
+
+        self
+    }
+}
+
+/// Input bound to an action, like `W` or `Ctrl+F3`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Binding {
+    pub input: Input,
+    pub modifiers: Modifiers,
+}
+
+impl Binding {
+    /// Bind to nothing
+    pub fn unbound() -> Binding {
+        Binding {
+            input: Input::Unbound,
+            modifiers: Modifiers::default(),
+        }
+    }
+
+    /// Bind to a key
+    pub fn key(key: Key) -> Binding {
+        Binding {
+            input: Input::Key(key),
+            modifiers: Modifiers::default(),
+        }
+    }
+
+    /// Bind to a mouse button
+    pub fn mouse(button: MouseButton) -> Binding {
+        Binding {
+            input: Input::Mouse(button),
+            modifiers: Modifiers::default(),
+        }
+    }
+
+    /// Require modifier keys
+    pub fn with_modifiers(mut self, modifiers: Modifiers) -> Binding {
+        self.modifiers = modifiers;
+        self
+    }
+
+    /// Check if bound to anything
+    pub fn is_bound(&self) -> bool { self.input != Input::Unbound }
+
+    /// Check if a key press with some modifiers held triggers this binding.
+    /// Modifiers the binding doesn't ask for may be held too, so walking keeps working while sprinting
+    pub fn matches_key(&self, key: Key, modifiers: Modifiers) -> bool {
+        self.input == Input::Key(key) && modifiers.contains(self.modifiers)
+    }
+
+    /// Check if a mouse click with some modifiers held triggers this binding
+    pub fn matches_mouse(&self, button: MouseButton, modifiers: Modifiers) -> bool {
+        self.input == Input::Mouse(button) && modifiers.contains(self.modifiers)
+    }
+
+    /// Get binding from a vanilla 1.13 key name, like `key.keyboard.w` or `key.mouse.left`
+    pub fn from_vanilla_name(name: &str) -> Option<Binding> {
+        if name == "key.keyboard.unknown" {
+            return Some(Binding::unbound());
+        }
+
+        if name.starts_with("key.mouse.") {
+            let button = match &name["key.mouse.".len()..] {
+                "left" => MouseButton::Left,
+                "right" => MouseButton::Right,
+                "middle" => MouseButton::Middle,
+                other => MouseButton::Other(other.parse().ok()?),
+            };
+
+            return Some(Binding::mouse(button));
+        }
+
+        if !name.starts_with("key.keyboard.") {
+            return None;
+        }
+
+        let name = &name["key.keyboard.".len()..];
+
+        let key = match name {
+            "left.shift" => Key::LShift,
+            "right.shift" => Key::RShift,
+            "left.control" => Key::LControl,
+            "right.control" => Key::RControl,
+            "left.alt" => Key::LAlt,
+            "right.alt" => Key::RAlt,
+            "left.win" => Key::LWin,
+            "right.win" => Key::RWin,
+            "caps.lock" => Key::Capital,
+            "num.lock" => Key::Numlock,
+            "scroll.lock" => Key::Scroll,
+            "print.screen" => Key::Snapshot,
+            "page.up" => Key::PageUp,
+            "page.down" => Key::PageDown,
+            "enter" => Key::Return,
+            "backspace" => Key::Back,
+            "grave.accent" => Key::Grave,
+            "equal" => Key::Equals,
+            "left.bracket" => Key::LBracket,
+            "right.bracket" => Key::RBracket,
+            "keypad.add" => Key::Add,
+            "keypad.subtract" => Key::Subtract,
+            "keypad.multiply" => Key::Multiply,
+            "keypad.divide" => Key::Divide,
+            "keypad.decimal" => Key::Decimal,
+            "keypad.enter" => Key::NumpadEnter,
+            _ if name.starts_with("keypad.") => {
+                Key::from_name(&format!("Numpad{}", &name["keypad.".len()..]))?
+            },
+            _ if name.len() == 1 && name.chars().all(|c| c.is_ascii_digit()) => {
+                Key::from_name(&format!("Key{}", name))?
+            },
+            _ => {
+                // Single letters, function keys and words like `space` or `escape`
+                let mut chars = name.chars();
+                let first = chars.next()?;
+
+                Key::from_name(&format!("{}{}", first.to_uppercase(), chars.as_str()))?
+            },
+        };
+
+        Some(Binding::key(key))
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.modifiers.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+
+        if self.modifiers.shift {
+            write!(f, "Shift+")?;
+        }
+
+        if self.modifiers.alt {
+            write!(f, "Alt+")?;
+        }
+
+        match self.input {
+            Input::Unbound => write!(f, "none"),
+            Input::Key(key) => write!(f, "{}", key.name()),
+            Input::Mouse(MouseButton::Left) => write!(f, "mouse.left"),
+            Input::Mouse(MouseButton::Right) => write!(f, "mouse.right"),
+            Input::Mouse(MouseButton::Middle) => write!(f, "mouse.middle"),
+            Input::Mouse(MouseButton::Other(button)) => write!(f, "mouse.{}", button),
+        }
+    }
+}
+
+impl FromStr for Binding {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Binding, String> {
+        let mut parts: Vec<&str> = value.split('+').map(str::trim).collect();
+
+        let input = parts.pop().unwrap_or("");
+        let mut modifiers = Modifiers::default();
+
+        for modifier in parts {
+            match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => modifiers.ctrl = true,
+                "shift" => modifiers.shift = true,
+                "alt" => modifiers.alt = true,
+                _ => return Err(format!("Unknown modifier '{}'", modifier)),
+            }
+        }
+
+        let input = match input {
+            "none" | "" => Input::Unbound,
+            "mouse.left" => Input::Mouse(MouseButton::Left),
+            "mouse.right" => Input::Mouse(MouseButton::Right),
+            "mouse.middle" => Input::Mouse(MouseButton::Middle),
+            _ if input.starts_with("mouse.") => match input["mouse.".len()..].parse() {
+                Ok(button) => Input::Mouse(MouseButton::Other(button)),
+                Err(_) => return Err(format!("Unknown mouse button '{}'", input)),
+            },
+            _ => match Key::from_name(input) {
+                Some(key) => Input::Key(key),
+                None => return Err(format!("Unknown key '{}'", input)),
+            },
+        };
+
+        Ok(Binding { input, modifiers })
+    }
+}
+
+impl Serialize for Binding {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Binding {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Binding, D::Error> {
+        struct BindingVisitor;
+
+        impl<'de> Visitor<'de> for BindingVisitor {
+            type Value = Binding;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a key binding like W or Ctrl+F3")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Binding, E> {
+                // A typo shouldn't throw away the whole configuration file
+                Ok(value.parse().unwrap_or_else(|why| {
+                    warn!("Invalid key binding '{}': {}. Leaving it unbound", value, why);
+                    Binding::unbound()
+                }))
+            }
+        }
+
+        deserializer.deserialize_str(BindingVisitor)
+    }
+}
+
+/// Declare actions with their configuration name, vanilla `options.txt` name and default binding
+macro_rules! actions {
+    ($($action:ident => $name:tt, $vanilla:tt, $default:expr;)*) => {
+        /// Something the player can do with a key or mouse button
+        #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub enum Action {
+            $(#[serde(rename = $name)] $action),*
+        }
+
+        impl Action {
+            /// Get every action
+            pub fn all() -> &'static [Action] {
+                const ACTIONS: &[Action] = &[$(Action::$action),*];
+                ACTIONS
+            }
+
+            /// Get name used in configuration files
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Action::$action => $name),*
+                }
+            }
+
+            /// Get name used by vanilla, which is also its translation key
+            pub fn vanilla_name(self) -> &'static str {
+                match self {
+                    $(Action::$action => $vanilla),*
+                }
+            }
+
+            /// Get action by its vanilla name
+            pub fn from_vanilla_name(name: &str) -> Option<Action> {
+                match name {
+                    $($vanilla => Some(Action::$action),)*
+                    _ => None,
+                }
+            }
+
+            /// Get vanilla default binding
+            pub fn default_binding(self) -> Binding {
+                match self {
+                    $(Action::$action => $default),*
+                }
+            }
+        }
+    };
+}
+
+actions! {
+    Attack => "attack", "key.attack", Binding::mouse(MouseButton::Left);
+    Use => "use", "key.use", Binding::mouse(MouseButton::Right);
+    PickItem => "pick_item", "key.pickItem", Binding::mouse(MouseButton::Middle);
+    Forward => "forward", "key.forward", Binding::key(Key::W);
+    Left => "left", "key.left", Binding::key(Key::A);
+    Back => "back", "key.back", Binding::key(Key::S);
+    Right => "right", "key.right", Binding::key(Key::D);
+    Jump => "jump", "key.jump", Binding::key(Key::Space);
+    Sneak => "sneak", "key.sneak", Binding::key(Key::LShift);
+    Sprint => "sprint", "key.sprint", Binding::key(Key::LControl);
+    DropItem => "drop", "key.drop", Binding::key(Key::Q);
+    Inventory => "inventory", "key.inventory", Binding::key(Key::E);
+    SwapHands => "swap_hands", "key.swapHands", Binding::key(Key::F);
+    Chat => "chat", "key.chat", Binding::key(Key::T);
+    PlayerList => "player_list", "key.playerlist", Binding::key(Key::Tab);
+    Command => "command", "key.command", Binding::key(Key::Slash);
+    Advancements => "advancements", "key.advancements", Binding::key(Key::L);
+    Screenshot => "screenshot", "key.screenshot", Binding::key(Key::F2);
+    DebugOverlay => "debug", "key.debug", Binding::key(Key::F3);
+    TogglePerspective => "toggle_perspective", "key.togglePerspective", Binding::key(Key::F5);
+    SmoothCamera => "smooth_camera", "key.smoothCamera", Binding::unbound();
+    Fullscreen => "fullscreen", "key.fullscreen", Binding::key(Key::F11);
+    SpectatorOutlines => "spectator_outlines", "key.spectatorOutlines", Binding::unbound();
+    SaveToolbar => "save_toolbar", "key.saveToolbarActivator", Binding::key(Key::C);
+    LoadToolbar => "load_toolbar", "key.loadToolbarActivator", Binding::key(Key::X);
+    Hotbar1 => "hotbar_1", "key.hotbar.1", Binding::key(Key::Key1);
+    Hotbar2 => "hotbar_2", "key.hotbar.2", Binding::key(Key::Key2);
+    Hotbar3 => "hotbar_3", "key.hotbar.3", Binding::key(Key::Key3);
+    Hotbar4 => "hotbar_4", "key.hotbar.4", Binding::key(Key::Key4);
+    Hotbar5 => "hotbar_5", "key.hotbar.5", Binding::key(Key::Key5);
+    Hotbar6 => "hotbar_6", "key.hotbar.6", Binding::key(Key::Key6);
+    Hotbar7 => "hotbar_7", "key.hotbar.7", Binding::key(Key::Key7);
+    Hotbar8 => "hotbar_8", "key.hotbar.8", Binding::key(Key::Key8);
+    Hotbar9 => "hotbar_9", "key.hotbar.9", Binding::key(Key::Key9);
+}
+
+impl Action {
+    /// Get hotbar slot action, from 0 to 8
+    pub fn hotbar(slot: usize) -> Option<Action> {
+        const HOTBAR: [Action; 9] = [
+            Action::Hotbar1,
+            Action::Hotbar2,
+            Action::Hotbar3,
+            Action::Hotbar4,
+            Action::Hotbar5,
+            Action::Hotbar6,
+            Action::Hotbar7,
+            Action::Hotbar8,
+            Action::Hotbar9,
+        ];
+
+        HOTBAR.get(slot).cloned()
+    }
+}
+
+/// Every action binding
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Controls(BTreeMap<Action, Binding>);
+
+impl Default for Controls {
+    fn default() -> Controls {
+        Controls(
+            Action::all()
+                .iter()
+                .map(|&action| (action, action.default_binding()))
+                .collect(),
+        )
+    }
+}
+
+impl Controls {
+    /// Get action binding
+    pub fn get(&self, action: Action) -> Binding {
+        self.0
+            .get(&action)
+            .cloned()
+            .unwrap_or_else(|| action.default_binding())
+    }
+
+    /// Bind an action
+    pub fn set(&mut self, action: Action, binding: Binding) { self.0.insert(action, binding); }
+
+    /// Restore every vanilla binding
+    pub fn reset(&mut self) { *self = Controls::default(); }
+
+    /// Get actions triggered by a key press
+    pub fn key_actions(&self, key: Key, modifiers: Modifiers) -> Vec<Action> {
+        self.most_specific(|binding| binding.matches_key(key, modifiers))
+    }
+
+    /// Get actions triggered by a mouse click
+    pub fn mouse_actions(&self, button: MouseButton, modifiers: Modifiers) -> Vec<Action> {
+        self.most_specific(|binding| binding.matches_mouse(button, modifiers))
+    }
+
+    /// Get actions of matching bindings asking for the most modifiers, so `Ctrl+F3` wins over `F3`
+    fn most_specific<F>(&self, matches: F) -> Vec<Action>
+    where
+        F: Fn(&Binding) -> bool,
+    {
+        let matching: Vec<(Action, usize)> = self
+            .0
+            .iter()
+            .filter(|(_, binding)| matches(binding))
+            .map(|(&action, binding)| (action, binding.modifiers.count()))
+            .collect();
+
+        let most = matching.iter().map(|&(_, count)| count).max().unwrap_or(0);
+
+        matching
+            .into_iter()
+            .filter(|&(_, count)| count == most)
+            .map(|(action, _)| action)
+            .collect()
+    }
+
+    /// Get pairs of actions sharing the same binding
+    pub fn conflicts(&self) -> Vec<(Action, Action)> {
+        let mut conflicts = Vec::new();
+
+        for (index, (&action, binding)) in self.0.iter().enumerate() {
+            if !binding.is_bound() {
+                continue;
+            }
+
+            for (&other, other_binding) in self.0.iter().skip(index + 1) {
+                if binding == other_binding {
+                    conflicts.push((action, other));
+                }
+            }
+        }
+
+        conflicts
+    }
+
+    /// Check if an action shares its binding with another one
+    pub fn conflicting(&self, action: Action) -> bool {
+        self.conflicts().iter().any(|&(a, b)| a == action || b == action)
+    }
+}
@@ -0,0 +1,161 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::controls::{Key, Modifiers};
+
+use glium::glutin::{
+    DeviceEvent, ElementState, Event, KeyboardInput, MouseButton, MouseScrollDelta, WindowEvent,
+};
+
+use std::collections::HashSet;
+
+/// Pixels scrolled by touchpads counted as one line of a mouse wheel
+const PIXELS_PER_LINE: f64 = 16.0;
+
+/// Input event given to scenes
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InputEvent {
+    /// Key pressed, repeat is set when the key was already held down
+    KeyDown {
+        key: Key,
+        modifiers: Modifiers,
+        repeat: bool,
+    },
+    KeyUp {
+        key: Key,
+        modifiers: Modifiers,
+    },
+    /// Cursor moved to a window position in pixels
+    MouseMove {
+        x: f64,
+        y: f64,
+    },
+    /// Raw mouse movement in pixels, not stopped by window edges
+    MouseMotion {
+        dx: f64,
+        dy: f64,
+    },
+    ButtonDown {
+        button: MouseButton,
+        modifiers: Modifiers,
+    },
+    ButtonUp {
+        button: MouseButton,
+        modifiers: Modifiers,
+    },
+    /// Wheel scrolled in lines, positive is up and right
+    Scroll {
+        x: f32,
+        y: f32,
+    },
+    /// Character typed, control characters are sent as keys only
+    Text(char),
+    /// Window gained or lost focus
+    Focus(bool),
+}
+
+/// Turns window events into input events, remembering held keys to tell repeats apart
+pub struct InputState {
+    held: HashSet<Key>,
+}
+
+impl InputState {
+    pub fn new() -> InputState { InputState { held: HashSet::new() } }
+
+    /// Check if a key is held down
+    pub fn is_held(&self, key: Key) -> bool { self.held.contains(&key) }
+
+    /// Translate a window or device event, ignoring anything that is not input
+    pub fn translate(&mut self, event: &Event) -> Option<InputEvent> {
+        match event {
+            Event::WindowEvent { event, .. } => self.translate_window(event),
+            Event::DeviceEvent {
+                event: DeviceEvent::MouseMotion { delta: (dx, dy) },
+                ..
+            } => Some(InputEvent::MouseMotion { dx: *dx, dy: *dy }),
+            _ => None,
+        }
+    }
+
+    fn translate_window(&mut self, event: &WindowEvent) -> Option<InputEvent> {
+        match event {
+            WindowEvent::KeyboardInput {
+                input:
+                    KeyboardInput {
+                        state,
+                        virtual_keycode: Some(code),
+                        modifiers,
+                        ..
+                    },
+                ..
+            } => {
+                let key = Key::from_glutin(*code)?;
+                let modifiers = Modifiers::from_glutin(*modifiers).without(key);
+
+                Some(match state {
+                    ElementState::Pressed => InputEvent::KeyDown {
+                        key,
+                        modifiers,
+                        repeat: !self.held.insert(key),
+                    },
+                    ElementState::Released => {
+                        self.held.remove(&key);
+                        InputEvent::KeyUp { key, modifiers }
+                    },
+                })
+            },
+
+            WindowEvent::CursorMoved { position, .. } => Some(InputEvent::MouseMove {
+                x: position.x,
+                y: position.y,
+            }),
+
+            WindowEvent::MouseInput {
+                state,
+                button,
+                modifiers,
+                ..
+            } => {
+                let (button, modifiers) = (*button, Modifiers::from_glutin(*modifiers));
+
+                Some(match state {
+                    ElementState::Pressed => InputEvent::ButtonDown { button, modifiers },
+                    ElementState::Released => InputEvent::ButtonUp { button, modifiers },
+                })
+            },
+
+            WindowEvent::MouseWheel { delta, .. } => Some(match delta {
+                MouseScrollDelta::LineDelta(x, y) => InputEvent::Scroll { x: *x, y: *y },
+                MouseScrollDelta::PixelDelta(position) => InputEvent::Scroll {
+                    x: (position.x / PIXELS_PER_LINE) as f32,
+                    y: (position.y / PIXELS_PER_LINE) as f32,
+                },
+            }),
+
+            WindowEvent::ReceivedCharacter(c) if !c.is_control() => Some(InputEvent::Text(*c)),
+
+            WindowEvent::Focused(focused) => {
+                // Keys released while unfocused are never reported
+                if !focused {
+                    self.held.clear();
+                }
+
+                Some(InputEvent::Focus(*focused))
+            },
+
+            _ => None,
+        }
+    }
+}
@@ -13,8 +13,16 @@
 // LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
 // IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
+pub mod arguments;
 pub mod camera;
 pub mod constants;
+pub mod controls;
+pub mod input;
+pub mod raycast;
 pub mod resource_manager;
 pub mod settings;
 pub mod settings_manager;
+pub mod shape;
+pub mod text;
+pub mod tick;
+pub mod vanilla_options;
@@ -0,0 +1,165 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::camera::Camera;
+use core::shape::{Face, VoxelShape};
+
+use cgmath::prelude::*;
+use cgmath::{Point3, Vector3};
+use std::f32;
+
+/// How far a survival player reaches, in blocks
+pub const SURVIVAL_REACH: f32 = 4.5;
+
+/// How far a creative player reaches, in blocks
+pub const CREATIVE_REACH: f32 = 5.0;
+
+/// Which shape of a block rays should hit
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShapeKind {
+    /// What entities bump into, like a fence being 1.5 blocks tall
+    Collision,
+
+    /// What the player targets, like a flower being selectable
+    Outline,
+}
+
+/// Anything that knows block shapes, usually the world
+pub trait BlockView {
+    /// Get shape of the block state at a position, empty for air or unloaded blocks
+    fn shape(&self, position: Point3<i32>, kind: ShapeKind) -> &VoxelShape;
+}
+
+/// Block hit by a ray
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RayHit {
+    /// Block position
+    pub block: Point3<i32>,
+
+    /// Side of the block hit
+    pub face: Face,
+
+    /// Exact point hit
+    pub point: Point3<f32>,
+
+    /// Distance from the ray origin
+    pub distance: f32,
+}
+
+impl RayHit {
+    /// Get position where a block placed against the hit face would go
+    pub fn adjacent(&self) -> Point3<i32> { self.block + self.face.normal() }
+}
+
+/// Find first block hit walking voxels along a ray up to some distance.
+/// Shapes may be at most 1.5 blocks tall, like vanilla fences and walls.
+pub fn raycast<W>(
+    world: &W,
+    origin: Point3<f32>,
+    direction: Vector3<f32>,
+    reach: f32,
+    kind: ShapeKind,
+) -> Option<RayHit>
+where
+    W: BlockView,
+{
+    if direction.magnitude2() < f32::EPSILON {
+        return None;
+    }
+
+    let direction = direction.normalize();
+
+    let mut block = Point3::new(
+        origin.x.floor() as i32,
+        origin.y.floor() as i32,
+        origin.z.floor() as i32,
+    );
+
+    // Distance along the ray to the next voxel boundary and between boundaries, per axis
+    let mut step = [0i32; 3];
+    let mut next = [f32::INFINITY; 3];
+    let mut delta = [f32::INFINITY; 3];
+
+    for axis in 0..3 {
+        if direction[axis] > 0.0 {
+            step[axis] = 1;
+            delta[axis] = 1.0 / direction[axis];
+            next[axis] = (block[axis] as f32 + 1.0 - origin[axis]) * delta[axis];
+        } else if direction[axis] < 0.0 {
+            step[axis] = -1;
+            delta[axis] = -1.0 / direction[axis];
+            next[axis] = (origin[axis] - block[axis] as f32) * delta[axis];
+        }
+    }
+
+    let mut travelled = 0.0;
+    let mut closest: Option<RayHit> = None;
+
+    while travelled <= reach {
+        // Shapes like fences stick into the voxel above, so the block below is tested too
+        for &position in &[block, block - Vector3::unit_y()] {
+            if let Some((distance, face)) = world.shape(position, kind).ray(position, origin, direction) {
+                // Shapes may stick out of their block, so only accept hits inside reach
+                if distance <= reach && closest.map_or(true, |hit| distance < hit.distance) {
+                    closest = Some(RayHit {
+                        block: position,
+                        face,
+                        point: origin + direction * distance,
+                        distance,
+                    });
+                }
+            }
+        }
+
+        let axis = if next[0] < next[1] {
+            if next[0] < next[2] {
+                0
+            } else {
+                2
+            }
+        } else if next[1] < next[2] {
+            1
+        } else {
+            2
+        };
+
+        // Voxels further along the ray can't be hit before leaving this one
+        if let Some(hit) = closest {
+            if hit.distance <= next[axis] {
+                return Some(hit);
+            }
+        }
+
+        travelled = next[axis];
+        next[axis] += delta[axis];
+        block[axis] += step[axis];
+    }
+
+    closest
+}
+
+/// Find block the camera is looking at
+pub fn target<W>(world: &W, camera: &Camera, reach: f32) -> Option<RayHit>
+where
+    W: BlockView,
+{
+    raycast(
+        world,
+        camera.position(),
+        camera.front(),
+        reach,
+        ShapeKind::Outline,
+    )
+}
@@ -0,0 +1,702 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::resource_manager::resource::Resource;
+use core::resource_manager::resource_type::ResourceType;
+use core::resource_manager::texture_manager::TextureManager;
+use core::resource_manager::ResourceManager;
+
+use conrod::text::rt::{point, Scale};
+use conrod::text::Font;
+
+use glium::Display;
+
+use image::{self, Rgba, RgbaImage};
+
+use serde_json;
+
+use std::collections::HashMap;
+use std::error::Error;
+
+type Result<T> = std::result::Result<T, Box<Error>>;
+
+/// Height of a text line in font units
+pub const LINE_HEIGHT: f32 = 9.0;
+
+/// Distance from the top of a line to the text baseline in font units
+const BASELINE: f32 = 7.0;
+
+/// Advance of the space character, it never has a glyph
+const SPACE_ADVANCE: f32 = 4.0;
+
+/// Glyph atlas page width and height
+pub const PAGE_SIZE: u32 = 256;
+
+/// Empty pixels between glyphs to avoid texture bleeding
+const PADDING: u32 = 1;
+
+/// Font provider declared on `font/default.json`
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ProviderDefinition {
+    Bitmap {
+        file: String,
+        #[serde(default = "default_height")]
+        height: i32,
+        ascent: i32,
+        chars: Vec<String>,
+    },
+    LegacyUnicode {
+        sizes: String,
+        template: String,
+    },
+    Ttf {
+        file: String,
+        #[serde(default)]
+        shift: [f32; 2],
+        #[serde(default = "default_size")]
+        size: f32,
+        #[serde(default = "default_oversample")]
+        oversample: f32,
+        #[serde(default)]
+        skip: String,
+    },
+}
+
+#[derive(Deserialize, Debug)]
+struct FontDefinition {
+    providers: Vec<ProviderDefinition>,
+}
+
+fn default_height() -> i32 { 8 }
+
+fn default_size() -> f32 { 11.0 }
+
+fn default_oversample() -> f32 { 2.0 }
+
+/// Split a `namespace:path` location, Minecraft's namespace is used when missing
+fn location(value: &str) -> (String, String) {
+    match value.find(':') {
+        Some(index) => (value[..index].to_owned(), value[index + 1..].to_owned()),
+        None => ("minecraft".to_owned(), value.to_owned()),
+    }
+}
+
+/// Get a texture resource from a provider file location
+fn texture_resource(value: &str) -> Resource {
+    let (namespace, path) = location(value);
+
+    Resource::new(
+        namespace,
+        path.trim_end_matches(".png").to_owned(),
+        ResourceType::Texture,
+    )
+}
+
+/// Load a texture resource as RGBA pixels
+fn load_image(resource: &Resource) -> Result<RgbaImage> {
+    let data = resource.load_binary()?;
+    Ok(image::load_from_memory(&data)?.to_rgba())
+}
+
+/// Copy a region of an image
+fn crop(source: &RgbaImage, x: u32, y: u32, width: u32, height: u32) -> RgbaImage {
+    RgbaImage::from_fn(width, height, |i, j| *source.get_pixel(x + i, y + j))
+}
+
+/// Glyph pixels produced by a provider, before being packed on the atlas
+struct GlyphBitmap {
+    image: RgbaImage,
+    /// Font units per bitmap pixel
+    scale: f32,
+    advance: f32,
+    bearing_x: f32,
+    bearing_y: f32,
+}
+
+/// Source of glyphs for a font
+trait GlyphProvider {
+    /// Render a character, none if this provider doesn't know it
+    fn glyph(&mut self, character: char) -> Option<GlyphBitmap>;
+}
+
+/// Glyphs stored on a grid texture, like `ascii.png`
+struct BitmapProvider {
+    sheet: RgbaImage,
+    chars: HashMap<char, (u32, u32)>,
+    cell: (u32, u32),
+    scale: f32,
+    ascent: i32,
+}
+
+impl BitmapProvider {
+    fn new(file: &str, height: i32, ascent: i32, rows: &[String]) -> Result<BitmapProvider> {
+        let sheet = load_image(&texture_resource(file))?;
+
+        let columns = rows.iter().map(|row| row.chars().count()).max().unwrap_or(0) as u32;
+
+        if rows.is_empty() || columns == 0 {
+            return Err(From::from(format!(
+                "Bitmap font '{}' doesn't define any character",
+                file
+            )));
+        }
+
+        let cell = (sheet.width() / columns, sheet.height() / rows.len() as u32);
+        let mut chars = HashMap::new();
+
+        for (y, row) in rows.iter().enumerate() {
+            for (x, character) in row.chars().enumerate() {
+                // Null characters are used as grid placeholders
+                if character != '\u{0}' && character != ' ' {
+                    chars.insert(character, (x as u32 * cell.0, y as u32 * cell.1));
+                }
+            }
+        }
+
+        Ok(BitmapProvider {
+            sheet,
+            chars,
+            cell,
+            scale: height as f32 / cell.1 as f32,
+            ascent,
+        })
+    }
+
+    /// Detect glyph width looking for the last column with a visible pixel
+    fn width(&self, x: u32, y: u32) -> u32 {
+        (0..self.cell.0)
+            .rev()
+            .find(|&i| (0..self.cell.1).any(|j| self.sheet.get_pixel(x + i, y + j).data[3] != 0))
+            .map_or(0, |i| i + 1)
+    }
+}
+
+impl GlyphProvider for BitmapProvider {
+    fn glyph(&mut self, character: char) -> Option<GlyphBitmap> {
+        let (x, y) = *self.chars.get(&character)?;
+        let width = self.width(x, y);
+
+        Some(GlyphBitmap {
+            image: crop(&self.sheet, x, y, width, self.cell.1),
+            scale: self.scale,
+            advance: (0.5 + width as f32 * self.scale).floor() + 1.0,
+            bearing_x: 0.0,
+            bearing_y: BASELINE - self.ascent as f32,
+        })
+    }
+}
+
+/// Pre 1.13 unicode pages with glyph widths from `glyph_sizes.bin`
+struct LegacyUnicodeProvider {
+    sizes: Vec<u8>,
+    template: String,
+    pages: HashMap<u32, Option<RgbaImage>>,
+}
+
+impl LegacyUnicodeProvider {
+    fn new(sizes: &str, template: &str) -> Result<LegacyUnicodeProvider> {
+        let (namespace, path) = location(sizes);
+        let name = path
+            .trim_start_matches("font/")
+            .trim_end_matches(".bin")
+            .to_owned();
+
+        Ok(LegacyUnicodeProvider {
+            sizes: Resource::new(namespace, name, ResourceType::GlyphSizes).load_binary()?,
+            template: template.to_owned(),
+            pages: HashMap::new(),
+        })
+    }
+
+    /// Get a unicode page texture, loading it the first time
+    fn page(&mut self, page: u32) -> Option<&RgbaImage> {
+        let template = &self.template;
+
+        self.pages
+            .entry(page)
+            .or_insert_with(|| {
+                let resource = texture_resource(&template.replace("%s", &format!("{:02x}", page)));
+
+                load_image(&resource)
+                    .map_err(|error| warn!("Failed to load unicode page {}: {}", resource, error))
+                    .ok()
+            })
+            .as_ref()
+    }
+}
+
+impl GlyphProvider for LegacyUnicodeProvider {
+    fn glyph(&mut self, character: char) -> Option<GlyphBitmap> {
+        let codepoint = character as u32;
+        let size = *self.sizes.get(codepoint as usize)?;
+
+        if size == 0 {
+            return None;
+        }
+
+        let page = self.page(codepoint >> 8)?;
+
+        // Pages are a 16x16 grid, widths are stored for 16 pixel cells
+        let cell = page.width() / 16;
+        let factor = cell as f32 / 16.0;
+
+        let start = ((size >> 4) as f32 * factor) as u32;
+        let end = ((u32::from(size & 0xF) + 1) as f32 * factor) as u32;
+
+        let x = (codepoint & 0xF) * cell;
+        let y = (codepoint >> 4 & 0xF) * cell;
+
+        let scale = 8.0 / cell as f32;
+        let width = end.saturating_sub(start).min(cell);
+
+        Some(GlyphBitmap {
+            image: crop(page, x + start, y, width, cell),
+            scale,
+            advance: (width as f32 * scale).floor() + 1.0,
+            bearing_x: 0.0,
+            bearing_y: 0.0,
+        })
+    }
+}
+
+/// Glyphs rasterized from a TrueType font
+struct TrueTypeProvider {
+    font: Font<'static>,
+    shift: [f32; 2],
+    size: f32,
+    oversample: f32,
+    skip: String,
+}
+
+impl TrueTypeProvider {
+    fn new(
+        file: &str,
+        shift: [f32; 2],
+        size: f32,
+        oversample: f32,
+        skip: &str,
+    ) -> Result<TrueTypeProvider> {
+        let (namespace, path) = location(file);
+        let name = path.trim_end_matches(".ttf").to_owned();
+
+        Ok(TrueTypeProvider {
+            font: ResourceManager::font(&Resource::new(namespace, name, ResourceType::TrueTypeFont))?,
+            shift,
+            size,
+            oversample: oversample.max(1.0),
+            skip: skip.to_owned(),
+        })
+    }
+}
+
+impl GlyphProvider for TrueTypeProvider {
+    fn glyph(&mut self, character: char) -> Option<GlyphBitmap> {
+        if self.skip.contains(character) {
+            return None;
+        }
+
+        let glyph = self.font.glyph(character);
+
+        // Glyph zero is the missing glyph box
+        if glyph.id().0 == 0 {
+            return None;
+        }
+
+        let glyph = glyph.scaled(Scale::uniform(self.size * self.oversample));
+        let advance = glyph.h_metrics().advance_width / self.oversample;
+        let glyph = glyph.positioned(point(0.0, 0.0));
+
+        let bounds = match glyph.pixel_bounding_box() {
+            Some(bounds) => bounds,
+            None => {
+                return Some(GlyphBitmap {
+                    image: RgbaImage::new(0, 0),
+                    scale: 1.0 / self.oversample,
+                    advance,
+                    bearing_x: 0.0,
+                    bearing_y: 0.0,
+                })
+            },
+        };
+
+        let mut image = RgbaImage::new(bounds.width() as u32, bounds.height() as u32);
+
+        glyph.draw(|x, y, coverage| {
+            image.put_pixel(x, y, Rgba([255, 255, 255, (coverage * 255.0) as u8]));
+        });
+
+        Some(GlyphBitmap {
+            image,
+            scale: 1.0 / self.oversample,
+            advance,
+            bearing_x: bounds.min.x as f32 / self.oversample + self.shift[0],
+            bearing_y: BASELINE + bounds.min.y as f32 / self.oversample + self.shift[1],
+        })
+    }
+}
+
+/// Glyph packed on the atlas
+#[derive(Clone, Copy, Debug)]
+pub struct Glyph {
+    page: usize,
+    rect: [u32; 4],
+    scale: f32,
+    advance: f32,
+    bearing_x: f32,
+    bearing_y: f32,
+}
+
+impl Glyph {
+    /// Get atlas page index
+    pub fn page(&self) -> usize { self.page }
+
+    /// Get atlas rectangle in pixels as x, y, width and height, origin is top left
+    pub fn rect(&self) -> [u32; 4] { self.rect }
+
+    /// Get horizontal advance in font units
+    pub fn advance(&self) -> f32 { self.advance }
+
+    /// Get offset from the pen position to the glyph top left corner in font units
+    pub fn bearing(&self) -> [f32; 2] { [self.bearing_x, self.bearing_y] }
+
+    /// Get glyph size in font units
+    pub fn size(&self) -> [f32; 2] {
+        [self.rect[2] as f32 * self.scale, self.rect[3] as f32 * self.scale]
+    }
+
+    /// Check if the glyph has something to draw
+    pub fn visible(&self) -> bool { self.rect[2] > 0 && self.rect[3] > 0 }
+}
+
+/// Glyph positioned on a text line
+#[derive(Clone, Copy, Debug)]
+pub struct PlacedGlyph {
+    pub glyph: Glyph,
+    /// Top left corner in font units, relative to the text origin
+    pub position: [f32; 2],
+    pub character: char,
+}
+
+/// Atlas texture with shelf packing
+struct AtlasPage {
+    image: RgbaImage,
+    cursor: (u32, u32),
+    row_height: u32,
+    dirty: bool,
+}
+
+impl AtlasPage {
+    fn new() -> AtlasPage {
+        AtlasPage {
+            image: RgbaImage::new(PAGE_SIZE, PAGE_SIZE),
+            cursor: (0, 0),
+            row_height: 0,
+            dirty: true,
+        }
+    }
+
+    /// Find room for a glyph, none if the page is full
+    fn allocate(&mut self, width: u32, height: u32) -> Option<(u32, u32)> {
+        if self.cursor.0 + width > PAGE_SIZE {
+            self.cursor = (0, self.cursor.1 + self.row_height + PADDING);
+            self.row_height = 0;
+        }
+
+        if self.cursor.1 + height > PAGE_SIZE {
+            return None;
+        }
+
+        let position = self.cursor;
+
+        self.cursor.0 += width + PADDING;
+        self.row_height = self.row_height.max(height);
+
+        Some(position)
+    }
+}
+
+/// Minecraft font loader and glyph atlas
+pub struct FontManager {
+    providers: Vec<Box<GlyphProvider>>,
+    glyphs: HashMap<char, Option<Glyph>>,
+    by_width: HashMap<u32, Vec<char>>,
+    white: Option<Glyph>,
+    pages: Vec<AtlasPage>,
+}
+
+impl FontManager {
+    /// Start font manager
+    pub fn new() -> FontManager {
+        info!("Starting font manager...");
+
+        FontManager {
+            providers: Vec::new(),
+            glyphs: HashMap::new(),
+            by_width: HashMap::new(),
+            white: None,
+            pages: Vec::new(),
+        }
+    }
+
+    /// Get the resource used to upload an atlas page
+    pub fn page(index: usize) -> Resource {
+        Resource::litecraft(format!("font_page_{}", index), ResourceType::Texture)
+    }
+
+    /// Check if any font provider is loaded
+    pub fn loaded(&self) -> bool { !self.providers.is_empty() }
+
+    /// Load providers from `font/default.json`
+    pub fn load(&mut self) -> Result<()> {
+        let definition = Resource::minecraft("default", ResourceType::FontProvider).load()?;
+        let definition: FontDefinition = serde_json::from_str(&definition)?;
+
+        self.providers.clear();
+        self.glyphs.clear();
+        self.by_width.clear();
+        self.white = None;
+
+        // Page textures are kept, they will be overwritten by the next upload
+        self.pages.clear();
+
+        for provider in definition.providers {
+            let loaded: Result<Box<GlyphProvider>> = match provider {
+                ProviderDefinition::Bitmap {
+                    file,
+                    height,
+                    ascent,
+                    chars,
+                } => BitmapProvider::new(&file, height, ascent, &chars)
+                    .map(|p| Box::new(p) as Box<GlyphProvider>),
+                ProviderDefinition::LegacyUnicode { sizes, template } => {
+                    LegacyUnicodeProvider::new(&sizes, &template)
+                        .map(|p| Box::new(p) as Box<GlyphProvider>)
+                },
+                ProviderDefinition::Ttf {
+                    file,
+                    shift,
+                    size,
+                    oversample,
+                    skip,
+                } => TrueTypeProvider::new(&file, shift, size, oversample, &skip)
+                    .map(|p| Box::new(p) as Box<GlyphProvider>),
+            };
+
+            match loaded {
+                Ok(provider) => self.providers.push(provider),
+                Err(error) => warn!("Failed to load font provider: {}", error),
+            }
+        }
+
+        info!("Loaded {} font providers", self.providers.len());
+
+        Ok(())
+    }
+
+    /// Get a glyph, rasterizing it on the atlas the first time it is requested
+    pub fn glyph(&mut self, character: char) -> Option<Glyph> {
+        if let Some(glyph) = self.glyphs.get(&character) {
+            return *glyph;
+        }
+
+        // First provider that knows the character wins
+        let bitmap = self
+            .providers
+            .iter_mut()
+            .filter_map(|provider| provider.glyph(character))
+            .next();
+        let glyph = bitmap.map(|bitmap| self.pack(bitmap));
+
+        // Remember visible glyphs by advance to obfuscate text
+        if let Some(glyph) = glyph.filter(|glyph| glyph.visible()) {
+            self.by_width
+                .entry(glyph.advance.to_bits())
+                .or_insert_with(Vec::new)
+                .push(character);
+        }
+
+        self.glyphs.insert(character, glyph);
+        glyph
+    }
+
+    /// Get a random character with the same advance, used by obfuscated text
+    pub fn obfuscate(&mut self, character: char) -> char {
+        use rand::seq::SliceRandom;
+        use rand::thread_rng;
+
+        // Make sure there is something to choose from
+        if self.by_width.is_empty() {
+            for character in (33u8..127).map(char::from) {
+                self.glyph(character);
+            }
+        }
+
+        let advance = match self.glyph(character) {
+            Some(glyph) => glyph.advance,
+            None => return character,
+        };
+
+        self.by_width
+            .get(&advance.to_bits())
+            .and_then(|characters| characters.choose(&mut thread_rng()))
+            .cloned()
+            .unwrap_or(character)
+    }
+
+    /// Get a solid white glyph, used to draw lines and boxes with the font atlas
+    pub fn white(&mut self) -> Glyph {
+        if let Some(glyph) = self.white {
+            return glyph;
+        }
+
+        let mut glyph = self.pack(GlyphBitmap {
+            image: RgbaImage::from_pixel(3, 3, Rgba([255, 255, 255, 255])),
+            scale: 1.0,
+            advance: 0.0,
+            bearing_x: 0.0,
+            bearing_y: 0.0,
+        });
+
+        // Sample only the center pixel so filtering never reaches empty space
+        glyph.rect = [glyph.rect[0] + 1, glyph.rect[1] + 1, 1, 1];
+
+        self.white = Some(glyph);
+        glyph
+    }
+
+    /// Copy glyph pixels to an atlas page
+    fn pack(&mut self, bitmap: GlyphBitmap) -> Glyph {
+        let (width, height) = bitmap.image.dimensions();
+
+        let mut glyph = Glyph {
+            page: 0,
+            rect: [0, 0, 0, 0],
+            scale: bitmap.scale,
+            advance: bitmap.advance,
+            bearing_x: bitmap.bearing_x,
+            bearing_y: bitmap.bearing_y,
+        };
+
+        if width == 0 || height == 0 {
+            return glyph;
+        }
+
+        if width > PAGE_SIZE || height > PAGE_SIZE {
+            warn!("Glyph of {}x{} pixels doesn't fit on font atlas", width, height);
+            return glyph;
+        }
+
+        let allocation = self
+            .pages
+            .last_mut()
+            .and_then(|page| page.allocate(width, height));
+
+        let (x, y) = match allocation {
+            Some(position) => position,
+            None => {
+                let mut page = AtlasPage::new();
+                let position = page
+                    .allocate(width, height)
+                    .expect("Glyph must fit on an empty page");
+
+                self.pages.push(page);
+                position
+            },
+        };
+
+        let index = self.pages.len() - 1;
+        let page = &mut self.pages[index];
+
+        for (i, j, pixel) in bitmap.image.enumerate_pixels() {
+            page.image.put_pixel(x + i, y + j, *pixel);
+        }
+
+        page.dirty = true;
+
+        glyph.page = index;
+        glyph.rect = [x, y, width, height];
+        glyph
+    }
+
+    /// Position every glyph of a text, lines are split on `\n`
+    pub fn layout(&mut self, text: &str) -> Vec<PlacedGlyph> {
+        let mut glyphs = Vec::with_capacity(text.len());
+        let mut cursor = [0.0, 0.0];
+
+        for character in text.chars() {
+            match character {
+                '\n' => cursor = [0.0, cursor[1] + LINE_HEIGHT],
+                ' ' => cursor[0] += SPACE_ADVANCE,
+                _ => {
+                    let glyph = self.glyph(character).or_else(|| self.glyph('?'));
+
+                    if let Some(glyph) = glyph {
+                        if glyph.visible() {
+                            glyphs.push(PlacedGlyph {
+                                glyph,
+                                position: [cursor[0] + glyph.bearing_x, cursor[1] + glyph.bearing_y],
+                                character,
+                            });
+                        }
+
+                        cursor[0] += glyph.advance;
+                    }
+                },
+            }
+        }
+
+        glyphs
+    }
+
+    /// Get the advance of a character in font units
+    pub fn advance(&mut self, character: char) -> f32 {
+        match character {
+            ' ' => SPACE_ADVANCE,
+            _ => self
+                .glyph(character)
+                .or_else(|| self.glyph('?'))
+                .map_or(0.0, |glyph| glyph.advance),
+        }
+    }
+
+    /// Get width of the widest line of a text in font units
+    pub fn width(&mut self, text: &str) -> f32 {
+        text.split('\n')
+            .map(|line| line.chars().map(|character| self.advance(character)).sum::<f32>())
+            .fold(0.0, f32::max)
+    }
+
+    /// Upload modified atlas pages to OpenGL
+    pub fn tick(&mut self, textures: &mut TextureManager, display: &Display) {
+        for (index, page) in self.pages.iter_mut().enumerate().filter(|(_, page)| page.dirty) {
+            debug!("Uploading font atlas page {} to GPU", index);
+
+            // Reverse texture
+            let data = page
+                .image
+                .clone()
+                .into_raw()
+                .chunks(PAGE_SIZE as usize * 4)
+                .rev()
+                .flat_map(|row| row.iter())
+                .cloned()
+                .collect();
+
+            textures.upload(display, FontManager::page(index), data, (PAGE_SIZE, PAGE_SIZE));
+            page.dirty = false;
+        }
+    }
+}
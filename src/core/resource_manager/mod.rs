@@ -13,11 +13,14 @@
 // LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
 // IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
+use core::controls::Controls;
+use core::resource_manager::font_manager::FontManager;
 use core::resource_manager::resource::Resource;
 use core::resource_manager::shader_manager::ShaderManager;
 use core::resource_manager::texture_manager::TextureManager;
 
 use core::settings::Settings;
+use core::text::language::Language;
 
 use gfx::shapes::Shapes;
 
@@ -30,6 +33,7 @@ use std::error::Error;
 use std::sync::Mutex;
 use std::time::Instant;
 
+pub mod font_manager;
 pub mod resource;
 pub mod resource_type;
 pub mod shader_manager;
@@ -47,12 +51,25 @@ lazy_static! {
 pub struct ResourceManager {
     texture_manager: TextureManager,
     shader_manager: ShaderManager,
-    shapes: Shapes,
+    font_manager: FontManager,
+    language: Language,
+    controls: Controls,
+    shapes: Option<Shapes>,
 }
 
 impl ResourceManager {
     /// Create Litecraft's resource manager
     pub fn new(display: &Display, settings: &Settings) -> ResourceManager {
+        // Bind vertex data
+        let shapes = Shapes::new(display).expect("Failed to create required vertex data");
+
+        ResourceManager::create(Some(shapes), settings)
+    }
+
+    /// Create resource manager without vertex data, used when there is no display
+    pub fn headless(settings: &Settings) -> ResourceManager { ResourceManager::create(None, settings) }
+
+    fn create(shapes: Option<Shapes>, settings: &Settings) -> ResourceManager {
         let mut resourcepacks = RESOURCE_PACKS
             .lock()
             .expect("Failed to lock resourcepacks manager!");
@@ -60,13 +77,13 @@ impl ResourceManager {
         // Get enabled resourcepacks from config
         *resourcepacks = SmallVec::from_vec(settings.resourcepacks().clone());
 
-        // Bind vertex data
-        let shapes = Shapes::new(display).expect("Failed to create required vertex data");
-
         ResourceManager {
             shapes,
             texture_manager: TextureManager::new(),
             shader_manager: ShaderManager::new(),
+            font_manager: FontManager::new(),
+            language: Language::empty(),
+            controls: settings.controls().clone(),
         }
     }
 
@@ -105,11 +122,14 @@ impl ResourceManager {
 
     /// Tick all resource managers
     #[inline]
-    pub fn tick(&mut self, display: &Display) { self.texture_manager.tick(display); }
+    pub fn tick(&mut self, display: &Display) {
+        self.texture_manager.tick(display);
+        self.font_manager.tick(&mut self.texture_manager, display);
+    }
 
-    /// Get vertex data
+    /// Get vertex data, none without a display
     #[inline]
-    pub fn shapes(&self) -> &Shapes { &self.shapes }
+    pub fn shapes(&self) -> Option<&Shapes> { self.shapes.as_ref() }
 
     /// Get texture manager
     #[inline]
@@ -119,6 +139,10 @@ impl ResourceManager {
     #[inline]
     pub fn shaders(&self) -> &ShaderManager { &self.shader_manager }
 
+    /// Get font manager
+    #[inline]
+    pub fn fonts(&self) -> &FontManager { &self.font_manager }
+
     /// Get texture manager
     #[inline]
     pub fn textures_mut(&mut self) -> &mut TextureManager { &mut self.texture_manager }
@@ -127,6 +151,37 @@ impl ResourceManager {
     #[inline]
     pub fn shaders_mut(&mut self) -> &mut ShaderManager { &mut self.shader_manager }
 
+    /// Get font manager
+    #[inline]
+    pub fn fonts_mut(&mut self) -> &mut FontManager { &mut self.font_manager }
+
+    /// Get font manager along with the textures where its atlas lives
+    #[inline]
+    pub fn fonts_with_textures(&mut self) -> (&mut FontManager, &TextureManager) {
+        (&mut self.font_manager, &self.texture_manager)
+    }
+
+    /// Get current language
+    #[inline]
+    pub fn language(&self) -> &Language { &self.language }
+
+    /// Load and switch to a language
+    pub fn set_language(&mut self, code: &str) -> Result<(), Box<Error>> {
+        self.language = Language::load(code)?;
+        Ok(())
+    }
+
+    /// Get key bindings shown by keybind text components
+    #[inline]
+    pub fn controls(&self) -> &Controls { &self.controls }
+
+    /// Show the key bindings from settings in text
+    pub fn set_controls(&mut self, controls: &Controls) {
+        if self.controls != *controls {
+            self.controls = controls.clone();
+        }
+    }
+
     /// Check if resource manager is loaded
     #[inline]
     pub fn loaded(&self) -> bool { self.texture_manager.loaded() }
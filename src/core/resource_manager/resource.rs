@@ -21,7 +21,7 @@ use std::fmt;
 use std::fmt::{Display, Formatter};
 
 use std::borrow::Cow;
-use std::fs::{create_dir_all, File};
+use std::fs::{create_dir_all, read_dir, File};
 
 use std::io::Read;
 use std::io::{Error, ErrorKind};
@@ -31,7 +31,7 @@ use zip::read::ZipArchive;
 
 type Result<T> = std::result::Result<T, Box<error::Error>>;
 
-#[derive(PartialEq, Eq, Hash)]
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
 /// Represents a resource URI and allows loading resource data
 pub struct Resource {
     namespace: Cow<'static, str>,
@@ -112,26 +112,25 @@ impl Resource {
 
     /// Get asset folder
     pub fn folder(&self, parent: &str) -> String {
+        let mut path = vec![
+            parent,                      // Ex. resources
+            &*self.namespace,            // Ex. minecraft
+            self.resource_type.folder(), // Ex. textures
+        ];
+
         if let Some(ref resource_path) = self.resource_path {
-            format!(
-                "{}/{}/{}/{}/{}.{}",
-                parent,                         // Ex. resources
-                self.namespace,                 // Ex. minecraft
-                self.resource_type.folder(),    // Ex. textures
-                resource_path,                  // Ex. entity
-                self.name,                      // Ex. creeper
-                self.resource_type.extension(), // Ex. png
-            )
-        } else {
-            format!(
-                "{}/{}/{}/{}.{}",
-                parent,                         // Ex. assets
-                self.namespace,                 // Ex. litecraft
-                self.resource_type.folder(),    // Ex. textures
-                self.name,                      // Ex. logo
-                self.resource_type.extension(), // Ex. png
-            )
+            path.push(resource_path); // Ex. entity
         }
+
+        // Some resources like sounds.json live at namespace root
+        path.retain(|part| !part.is_empty());
+
+        format!(
+            "{}/{}.{}",
+            path.join("/"),
+            self.name,                      // Ex. creeper
+            self.resource_type.extension(), // Ex. png
+        )
     }
 
     /// Get a resource as binary
@@ -186,6 +185,97 @@ impl Resource {
         ))
     }
 
+    /// Get a resource from resources folder and every resource pack that has it, from lowest to highest priority
+    pub fn load_all(&self) -> Result<Vec<String>> {
+        let mut found = Vec::new();
+
+        let path = self.folder("resources");
+        let path = Path::new(&path);
+
+        if path.exists() {
+            let mut buffer = String::new();
+            File::open(path)?.read_to_string(&mut buffer)?;
+
+            found.push(buffer);
+        }
+
+        // First enabled resource pack has the highest priority
+        let resourcepacks = ResourceManager::resourcepacks();
+
+        let resourcepacks = resourcepacks
+            .into_iter()
+            .rev()
+            .map(|entry| PathBuf::from(format!("resourcepacks/{}.zip", entry)))
+            .filter(|entry| entry.is_file());
+
+        for entry in resourcepacks {
+            let zipfile = File::open(entry)?;
+            let mut zipfile = ZipArchive::new(zipfile)?;
+
+            if let Ok(mut file) = zipfile.by_name(&self.folder("assets")) {
+                let mut buffer = String::new();
+                file.read_to_string(&mut buffer)?;
+
+                found.push(buffer);
+            }
+        }
+
+        Ok(found)
+    }
+
+    /// Get sorted names of every resource of a type in resources folder and enabled resource packs
+    pub fn list(namespace: &str, resource_type: ResourceType) -> Result<Vec<String>> {
+        // Path of a resource named `*`, ex. minecraft/lang/*.json
+        let pattern = Resource::new(namespace.to_owned(), "*", resource_type).folder("");
+        let (prefix, suffix) = pattern.split_at(pattern.find('*').unwrap_or(0));
+        let suffix = &suffix[1..];
+
+        let mut paths = Vec::new();
+
+        let folder = Path::new("resources").join(prefix);
+
+        if folder.is_dir() {
+            for entry in read_dir(folder)? {
+                if let Some(name) = entry?.file_name().to_str() {
+                    paths.push(format!("{}{}", prefix, name));
+                }
+            }
+        }
+
+        let resourcepacks = ResourceManager::resourcepacks();
+
+        let resourcepacks = resourcepacks
+            .into_iter()
+            .map(|entry| PathBuf::from(format!("resourcepacks/{}.zip", entry)))
+            .filter(|entry| entry.is_file());
+
+        for entry in resourcepacks {
+            let zipfile = File::open(entry)?;
+            let mut zipfile = ZipArchive::new(zipfile)?;
+
+            for i in 0..zipfile.len() {
+                let file = zipfile.by_index(i)?;
+
+                if file.name().starts_with("assets/") {
+                    paths.push(file.name()["assets/".len()..].to_owned());
+                }
+            }
+        }
+
+        let mut names: Vec<String> = paths
+            .iter()
+            .filter(|path| path.len() > prefix.len() + suffix.len())
+            .filter(|path| path.starts_with(prefix) && path.ends_with(suffix))
+            .map(|path| path[prefix.len()..path.len() - suffix.len()].to_owned())
+            .filter(|name| !name.contains('/'))
+            .collect();
+
+        names.sort();
+        names.dedup();
+
+        Ok(names)
+    }
+
     /// Get a resource as plain test
     pub fn load(&self) -> Result<String> { String::from_utf8(self.load_binary()?).map_err(|e| e.into()) }
 }
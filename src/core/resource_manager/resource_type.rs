@@ -15,17 +15,21 @@
 
 use std::fmt;
 
-#[derive(PartialEq, Eq, Hash, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 /// Type of resource
 pub enum ResourceType {
     Language,
     Blockstate,
     Model,
     Sound,
+    SoundDefinition,
     Texture,
     Animation,
     Colormap,
     Font,
+    FontProvider,
+    GlyphSizes,
+    TrueTypeFont,
     Property,
     Text,
     FragmentShader,
@@ -39,7 +43,11 @@ impl fmt::Display for ResourceType {
             ResourceType::Blockstate => write!(f, "blockstate"),
             ResourceType::Model => write!(f, "model"),
             ResourceType::Sound => write!(f, "sound"),
+            ResourceType::SoundDefinition => write!(f, "sound_definition"),
             ResourceType::Font => write!(f, "font"),
+            ResourceType::FontProvider => write!(f, "font_provider"),
+            ResourceType::GlyphSizes => write!(f, "glyph_sizes"),
+            ResourceType::TrueTypeFont => write!(f, "truetype_font"),
             ResourceType::Text => write!(f, "text"),
             ResourceType::VertexShader => write!(f, "vertex_shader"),
             ResourceType::FragmentShader => write!(f, "fragment_shader"),
@@ -59,7 +67,11 @@ impl ResourceType {
             ResourceType::Blockstate => "blockstates",
             ResourceType::Model => "models",
             ResourceType::Sound => "sounds",
+            ResourceType::SoundDefinition => "",
             ResourceType::Font => "fonts",
+            ResourceType::FontProvider => "font",
+            ResourceType::GlyphSizes => "font",
+            ResourceType::TrueTypeFont => "font",
             ResourceType::Text => "texts",
             ResourceType::VertexShader => "shaders",
             ResourceType::FragmentShader => "shaders",
@@ -73,11 +85,15 @@ impl ResourceType {
     /// Get resource extension
     pub fn extension(&self) -> &str {
         match self {
-            ResourceType::Language => "lang",
+            ResourceType::Language => "json",
             ResourceType::Blockstate => "json",
             ResourceType::Model => "json",
             ResourceType::Sound => "ogg",
+            ResourceType::SoundDefinition => "json",
             ResourceType::Font => "ttf",
+            ResourceType::FontProvider => "json",
+            ResourceType::GlyphSizes => "bin",
+            ResourceType::TrueTypeFont => "ttf",
             ResourceType::Text => "txt",
             ResourceType::VertexShader => "vsh",
             ResourceType::FragmentShader => "fsh",
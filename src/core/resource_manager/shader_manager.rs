@@ -41,6 +41,9 @@ impl ShaderManager {
     /// Get a compiled shader program
     pub fn get(&self, name: &str) -> Option<&Program> { self.shaders.get(name) }
 
+    /// Get number of compiled shader programs
+    pub fn count(&self) -> usize { self.shaders.len() }
+
     /// Load and build a shader
     pub fn load(&mut self, name: &'static str, display: &Display) -> Result<()> {
         if self.get(name).is_some() {
@@ -15,7 +15,8 @@
 
 use core::resource_manager::resource::Resource;
 
-use glium::texture::{CompressedSrgbTexture2d, RawImage2d};
+use glium::texture::{CompressedSrgbTexture2d, RawImage2d, SrgbTexture2d};
+use glium::uniforms::{SamplerBehavior, UniformValue};
 use glium::Display;
 
 use std::collections::HashMap;
@@ -37,12 +38,38 @@ struct RGBAImageData {
     ui: bool,
 }
 
+/// Texture uploaded to OpenGL
+pub enum Texture {
+    /// Texture loaded from a resource pack
+    Compressed(CompressedSrgbTexture2d),
+    /// Texture uploaded from memory, glyph atlases would be smeared by lossy compression
+    Uncompressed(SrgbTexture2d),
+}
+
+impl Texture {
+    /// Get texture width and height in pixels
+    pub fn dimensions(&self) -> (u32, u32) {
+        match *self {
+            Texture::Compressed(ref texture) => texture.dimensions(),
+            Texture::Uncompressed(ref texture) => texture.dimensions(),
+        }
+    }
+
+    /// Get uniform value to sample this texture in a shader
+    pub fn uniform(&self, sampler: SamplerBehavior) -> UniformValue {
+        match *self {
+            Texture::Compressed(ref texture) => UniformValue::CompressedSrgbTexture2d(texture, Some(sampler)),
+            Texture::Uncompressed(ref texture) => UniformValue::SrgbTexture2d(texture, Some(sampler)),
+        }
+    }
+}
+
 /// Texture tracker and loader
 pub struct TextureManager {
     pending: u16,
-    ui_images: Map<CompressedSrgbTexture2d>,
+    ui_images: Map<Resource>,
     ui_textures: HashMap<Resource, UiTexture>,
-    textures: HashMap<Resource, CompressedSrgbTexture2d>,
+    textures: HashMap<Resource, Texture>,
     sender: Sender<RGBAImageData>,
     receiver: Receiver<RGBAImageData>,
     pool: ThreadPool,
@@ -61,7 +88,7 @@ impl TextureManager {
 
             pool: ThreadPool::new(8),
 
-            ui_images: Map::<CompressedSrgbTexture2d>::new(),
+            ui_images: Map::<Resource>::new(),
 
             pending: 0,
 
@@ -73,12 +100,21 @@ impl TextureManager {
     // Check if we need to load another texture
     pub fn loaded(&self) -> bool { self.pending == 0 }
 
-    /// Get a texture
-    pub fn get(&self, name: &Resource) -> Option<&CompressedSrgbTexture2d> { self.textures.get(name) }
+    /// Get number of uploaded textures, a texture used for 3D and user interface is counted once
+    pub fn count(&self) -> usize { self.textures.len() }
+
+    /// Get a texture loaded for 3D or for user interface
+    pub fn get(&self, name: &Resource) -> Option<&Texture> { self.textures.get(name) }
 
     /// Get a UI texture
     pub fn get_ui(&self, name: &Resource) -> Option<UiTexture> { self.ui_textures.get(name).cloned() }
 
+    /// Get which UI texture a conrod image is
+    pub fn ui_resource(&self, id: Id) -> Option<&Resource> { self.ui_images.get(&id) }
+
+    /// Get every loaded texture, for 3D and for user interface
+    pub fn resources(&self) -> Vec<&Resource> { self.textures.keys().collect() }
+
     /// Request texture load
     pub fn load(&mut self, resource: Resource) { self.do_load(resource, false); }
 
@@ -95,31 +131,42 @@ impl TextureManager {
             let texture = CompressedSrgbTexture2d::new(display, texture);
             let texture = texture.expect("Failed to send texture to GPU.");
 
-            // Check if texture is needed for 3D or for user interface
             if image.ui {
                 debug!("Loaded UI texture {}", &image.resource);
 
-                // Add size and id to texture map
-                self.ui_textures.insert(
-                    image.resource,
-                    (
-                        // Get conrod texture Id
-                        self.ui_images.insert(texture),
-                        // Get image size
-                        (f64::from(image.dimensions.0), f64::from(image.dimensions.1)),
-                    ),
-                );
+                self.insert_ui(image.resource.clone(), image.dimensions);
             } else {
                 debug!("Loaded texture {}", &image.resource);
-
-                // Add to texture map
-                self.textures.insert(image.resource, texture);
             }
 
+            // Add to texture map, user interface uses the same texture
+            self.textures.insert(image.resource, Texture::Compressed(texture));
+
             self.pending -= 1;
         }
     }
 
+    /// Upload already decoded RGBA data for both 3D and user interface, replacing previous textures
+    pub fn upload(&mut self, display: &Display, resource: Resource, data: Vec<u8>, dimensions: (u32, u32)) {
+        let texture = RawImage2d::from_raw_rgba(data, dimensions);
+        let texture = SrgbTexture2d::new(display, texture).expect("Failed to send texture to GPU.");
+
+        self.insert_ui(resource.clone(), dimensions);
+        self.textures.insert(resource, Texture::Uncompressed(texture));
+    }
+
+    /// Add size and conrod id of a user interface texture, keeping the id if it was added before
+    fn insert_ui(&mut self, resource: Resource, dimensions: (u32, u32)) {
+        let size = (f64::from(dimensions.0), f64::from(dimensions.1));
+
+        let id = match self.get_ui(&resource) {
+            Some((id, _)) => id,
+            None => self.ui_images.insert(resource.clone()),
+        };
+
+        self.ui_textures.insert(resource, (id, size));
+    }
+
     /// Load texture async
     fn do_load(&mut self, resource: Resource, ui: bool) {
         // Prevent load twice a texture
@@ -134,6 +181,12 @@ impl TextureManager {
             return;
         }
 
+        // Texture already uploaded for 3D only needs a conrod id
+        if let Some(dimensions) = self.get(&resource).map(Texture::dimensions) {
+            self.insert_ui(resource, dimensions);
+            return;
+        }
+
         let sender = self.sender.clone();
 
         self.pending += 1;
@@ -190,7 +243,4 @@ impl TextureManager {
                 .expect("Failed to send decoded texture to main thread");
         });
     }
-
-    /// Get user interface manager
-    pub fn image_map(&self) -> &Map<CompressedSrgbTexture2d> { &self.ui_images }
 }
@@ -13,29 +13,173 @@
 // LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
 // IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
-#[derive(Serialize, Deserialize, Debug)]
+use core::controls::Controls;
+use core::text::language::DEFAULT_LANGUAGE;
+
+use sound::backend::AudioOutput;
+use sound::SoundCategory;
+
+use std::collections::BTreeMap;
+
+/// Current layout of the configuration file
+pub const SETTINGS_VERSION: u32 = 1;
+
+/// Vanilla FOV slider range
+pub const FOV_RANGE: (u8, u8) = (30, 110);
+
+/// GUI scale range
+pub const GUI_SCALE_RANGE: (f64, f64) = (0.5, 6.0);
+
+/// Smallest window allowed
+pub const MIN_WINDOW_SIZE: (u32, u32) = (320, 240);
+
+/// Highest MSAA sample count
+pub const MAX_MULTISAMPLING: u16 = 16;
+
+/// Render distance limits in chunks
+pub const RENDER_DISTANCE_RANGE: (u8, u8) = (2, 32);
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
 pub struct WindowSettings {
     width: u32,
     height: u32,
     fullscreen: bool,
     maximized: bool,
     multisampling: u16,
+    reversed_depth: bool,
     gui_scale: f64,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+impl Default for WindowSettings {
+    fn default() -> WindowSettings {
+        WindowSettings {
+            width: 800,
+            height: 600,
+            fullscreen: false,
+            maximized: true,
+            multisampling: 0,
+            reversed_depth: false,
+            gui_scale: 1.0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
 pub struct GameplaySettings {
     fov: u8,
     vsync: bool,
+    render_distance: u8,
+    mouse_sensitivity: f32,
+    invert_mouse: bool,
+}
+
+impl Default for GameplaySettings {
+    fn default() -> GameplaySettings {
+        GameplaySettings {
+            fov: 90,
+            vsync: true,
+            render_distance: 12,
+            mouse_sensitivity: 0.5,
+            invert_mouse: false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct AudioSettings {
+    output: AudioOutput,
+    volumes: BTreeMap<SoundCategory, f32>,
+}
+
+impl Default for AudioSettings {
+    fn default() -> AudioSettings {
+        AudioSettings {
+            output: AudioOutput::Null,
+            volumes: SoundCategory::all()
+                .iter()
+                .map(|&category| (category, 1.0))
+                .collect(),
+        }
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
 pub struct Settings {
+    version: u32,
     window: WindowSettings,
     gameplay: GameplaySettings,
+    audio: AudioSettings,
+    controls: Controls,
+    language: String,
     resourcepacks: Vec<String>,
 }
 
+/// Settings given on command line, used for this run only
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Overrides {
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub fullscreen: Option<bool>,
+}
+
+impl Overrides {
+    /// Apply to settings, returning the values they replaced
+    pub fn apply(&self, settings: &mut Settings) -> Overrides {
+        let replaced = Overrides {
+            width: self.width.map(|_| settings.width()),
+            height: self.height.map(|_| settings.height()),
+            fullscreen: self.fullscreen.map(|_| settings.fullscreen()),
+        };
+
+        if let Some(width) = self.width {
+            settings.set_width(width.max(MIN_WINDOW_SIZE.0));
+        }
+
+        if let Some(height) = self.height {
+            settings.set_height(height.max(MIN_WINDOW_SIZE.1));
+        }
+
+        if let Some(fullscreen) = self.fullscreen {
+            settings.set_fullscreen(fullscreen);
+        }
+
+        replaced
+    }
+
+    /// Get settings as they should be saved, values still holding an override go back to what they replaced
+    pub fn restore(&self, settings: &Settings, replaced: &Overrides) -> Settings {
+        let mut saved = settings.clone();
+
+        if let (Some(width), Some(original)) = (self.width, replaced.width) {
+            if saved.width() == width.max(MIN_WINDOW_SIZE.0) {
+                saved.set_width(original);
+            }
+        }
+
+        if let (Some(height), Some(original)) = (self.height, replaced.height) {
+            if saved.height() == height.max(MIN_WINDOW_SIZE.1) {
+                saved.set_height(original);
+            }
+        }
+
+        if let (Some(fullscreen), Some(original)) = (self.fullscreen, replaced.fullscreen) {
+            if saved.fullscreen() == fullscreen {
+                saved.set_fullscreen(original);
+            }
+        }
+
+        saved
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Settings { Settings::new() }
+}
+
 impl Settings {
     /// Create settings with default values
     pub fn new() -> Settings { Settings::new_with_size(800, 600) }
@@ -43,19 +187,92 @@ impl Settings {
     /// Create settings with width and height
     pub fn new_with_size(width: u32, height: u32) -> Settings {
         Settings {
+            version: SETTINGS_VERSION,
             window: WindowSettings {
                 width,
                 height,
-                fullscreen: false,
-                maximized: true,
-                multisampling: 0,
-                gui_scale: 1.0,
+                ..WindowSettings::default()
             },
-            gameplay: GameplaySettings { fov: 90, vsync: true },
+            gameplay: GameplaySettings::default(),
+            audio: AudioSettings::default(),
+            controls: Controls::default(),
+            language: DEFAULT_LANGUAGE.to_owned(),
             resourcepacks: Vec::new(),
         }
     }
 
+    /// Clamp out of range values, warning about each one
+    pub fn validate(&mut self) {
+        fn clamp<T: PartialOrd + Copy + ::std::fmt::Display>(
+            name: &str,
+            value: &mut T,
+            (min, max): (T, T),
+        ) {
+            // NaN can't be compared, it is out of range too
+            let nan = (*value).partial_cmp(&*value).is_none();
+
+            if nan || *value < min || *value > max {
+                let clamped = if nan || *value < min { min } else { max };
+
+                warn!(
+                    "Setting {} = {} is out of range ({} to {}), using {}",
+                    name, value, min, max, clamped
+                );
+
+                *value = clamped;
+            }
+        }
+
+        clamp("gameplay.fov", &mut self.gameplay.fov, FOV_RANGE);
+        clamp(
+            "gameplay.render_distance",
+            &mut self.gameplay.render_distance,
+            RENDER_DISTANCE_RANGE,
+        );
+        clamp(
+            "gameplay.mouse_sensitivity",
+            &mut self.gameplay.mouse_sensitivity,
+            (0.0, 1.0),
+        );
+        clamp("window.gui_scale", &mut self.window.gui_scale, GUI_SCALE_RANGE);
+        clamp(
+            "window.width",
+            &mut self.window.width,
+            (MIN_WINDOW_SIZE.0, u32::max_value()),
+        );
+        clamp(
+            "window.height",
+            &mut self.window.height,
+            (MIN_WINDOW_SIZE.1, u32::max_value()),
+        );
+        clamp(
+            "window.multisampling",
+            &mut self.window.multisampling,
+            (0, MAX_MULTISAMPLING),
+        );
+
+        for (category, volume) in &mut self.audio.volumes {
+            clamp(&format!("audio.volumes.{}", category.name()), volume, (0.0, 1.0));
+        }
+
+        for (action, other) in self.controls.conflicts() {
+            warn!(
+                "Controls {} and {} are both bound to {}",
+                action.name(),
+                other.name(),
+                self.controls.get(action)
+            );
+        }
+
+        if self.language.is_empty() {
+            warn!("Setting language is empty, using {}", DEFAULT_LANGUAGE);
+            self.language = DEFAULT_LANGUAGE.to_owned();
+        }
+    }
+
+    /// Get configuration layout version
+    pub fn version(&self) -> u32 { self.version }
+
     /// Get window width
     pub fn width(&self) -> u32 { self.window.width }
 
@@ -71,21 +288,96 @@ impl Settings {
     /// Get if user wants fullscreen
     pub fn fullscreen(&self) -> bool { self.window.fullscreen }
 
+    /// Set if user wants fullscreen
+    pub fn set_fullscreen(&mut self, value: bool) { self.window.fullscreen = value }
+
     /// Get if user wants maximized
     pub fn maximized(&self) -> bool { self.window.maximized }
 
     /// Get if user wants MSAA anti-aliasing
     pub fn multisampling(&self) -> u16 { self.window.multisampling }
 
+    /// Set MSAA sample count, applied on restart
+    pub fn set_multisampling(&mut self, value: u16) {
+        self.window.multisampling = value.min(MAX_MULTISAMPLING)
+    }
+
+    /// Get if depth buffer goes from 1 at near plane to 0 at infinity
+    pub fn reversed_depth(&self) -> bool { self.window.reversed_depth }
+
+    /// Set if depth buffer goes from 1 at near plane to 0 at infinity
+    pub fn set_reversed_depth(&mut self, value: bool) { self.window.reversed_depth = value }
+
     /// Get if user wants vsync
     pub fn vsync(&self) -> bool { self.gameplay.vsync }
 
+    /// Set if user wants vsync, applied on restart
+    pub fn set_vsync(&mut self, value: bool) { self.gameplay.vsync = value }
+
     /// Get user FOV
     pub fn fov(&self) -> u8 { self.gameplay.fov }
 
+    /// Set user FOV
+    pub fn set_fov(&mut self, value: u8) { self.gameplay.fov = value.max(FOV_RANGE.0).min(FOV_RANGE.1) }
+
+    /// Get render distance in chunks
+    pub fn render_distance(&self) -> u8 { self.gameplay.render_distance }
+
+    /// Set render distance in chunks
+    pub fn set_render_distance(&mut self, value: u8) {
+        self.gameplay.render_distance = value.max(RENDER_DISTANCE_RANGE.0).min(RENDER_DISTANCE_RANGE.1)
+    }
+
+    /// Get vanilla mouse sensitivity, from 0.0 to 1.0
+    pub fn mouse_sensitivity(&self) -> f32 { self.gameplay.mouse_sensitivity }
+
+    /// Set vanilla mouse sensitivity, from 0.0 to 1.0
+    pub fn set_mouse_sensitivity(&mut self, value: f32) {
+        self.gameplay.mouse_sensitivity = value.max(0.0).min(1.0)
+    }
+
+    /// Get if moving mouse up looks down
+    pub fn invert_mouse(&self) -> bool { self.gameplay.invert_mouse }
+
+    /// Set if moving mouse up looks down
+    pub fn set_invert_mouse(&mut self, value: bool) { self.gameplay.invert_mouse = value }
+
     /// Get user GUI scale
     pub fn scale(&self) -> f64 { self.window.gui_scale }
 
+    /// Set user GUI scale
+    pub fn set_scale(&mut self, value: f64) {
+        self.window.gui_scale = value.max(GUI_SCALE_RANGE.0).min(GUI_SCALE_RANGE.1)
+    }
+
+    /// Get where mixed sound goes
+    pub fn audio_output(&self) -> AudioOutput { self.audio.output }
+
+    /// Get sound category volume, from 0.0 to 1.0
+    pub fn volume(&self, category: SoundCategory) -> f32 {
+        self.audio.volumes.get(&category).cloned().unwrap_or(1.0)
+    }
+
+    /// Set sound category volume, from 0.0 to 1.0
+    pub fn set_volume(&mut self, category: SoundCategory, value: f32) {
+        self.audio.volumes.insert(category, value.max(0.0).min(1.0));
+    }
+
+    /// Get key bindings
+    pub fn controls(&self) -> &Controls { &self.controls }
+
+    /// Get key bindings
+    pub fn controls_mut(&mut self) -> &mut Controls { &mut self.controls }
+
+    /// Get language code, like en_us
+    pub fn language(&self) -> &str { &self.language }
+
+    /// Set language code
+    pub fn set_language(&mut self, value: &str) { self.language = value.to_owned() }
+
     /// Get enabled resourcepacks by filename
     pub fn resourcepacks(&self) -> &Vec<String> { &self.resourcepacks }
+
+    /// Set enabled resourcepacks by filename, first one has the highest priority
+    pub fn set_resourcepacks(&mut self, value: Vec<String>) { self.resourcepacks = value }
 }
@@ -14,49 +14,283 @@
 // IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 use core::constants::CONFIG_FILE;
-use core::settings::Settings;
-use serde_yaml;
+use core::settings::{Overrides, Settings, SETTINGS_VERSION};
+use serde_yaml::{self, Mapping, Value};
+use std::error::Error;
 use std::fs::File;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Upgrade a configuration layout to the next version, index is the version it upgrades from
+type Migration = fn(&mut Mapping);
+
+/// Seconds settings must stay unchanged before being written
+const SAVE_DELAY: u64 = 2;
+
+/// Every layout upgrade, in order
+const MIGRATIONS: [Migration; 1] = [migrate_unversioned];
 
 /// Load and parse yaml configuarion file
-pub fn load_config() -> Settings {
+pub fn load_config() -> Settings { load_config_from(CONFIG_FILE) }
+
+/// Load and parse a yaml configuration file from a custom path
+pub fn load_config_from<P: AsRef<Path>>(path: P) -> Settings {
     use std::fs::copy;
+    use std::io::Read;
+
+    let path = path.as_ref();
+    let mut data = String::new();
 
-    match File::open(CONFIG_FILE) {
+    if let Err(why) = File::open(path).and_then(|mut file| file.read_to_string(&mut data)) {
+        warn!("Can't read configuration file: {}", why);
+        return generate_config(path);
+    }
+
+    match parse_config(&data) {
         Err(why) => {
-            warn!("Can't read configuration file: {}", why);
-            generate_config()
+            warn!("Can't parse configuration file: {}", why);
+            warn!("Regenerating, old configuration placed at {}.bak", path.display());
+
+            if let Err(error) = copy(path, format!("{}.bak", path.display())) {
+                warn!("Failed to copy old configuration to .bak file. {}", error);
+            }
+
+            generate_config(path)
         },
-        Ok(file) => match serde_yaml::from_reader(file) {
-            Err(why) => {
-                warn!("Can't parse configuration file: {}", why);
-                warn!("Regenerating, old configuration placed at {}.bak", CONFIG_FILE);
+        Ok(settings) => settings,
+    }
+}
+
+/// Parse configuration, upgrading old layouts and using defaults for missing or invalid values
+pub fn parse_config(data: &str) -> Result<Settings, Box<Error>> {
+    let mut user = match serde_yaml::from_str(data)? {
+        Value::Mapping(mapping) => mapping,
+        _ => return Err(From::from("Configuration root is not a mapping")),
+    };
+
+    migrate(&mut user);
+
+    let defaults = serde_yaml::to_value(Settings::new())?;
+    let merged = merge(defaults, user);
+
+    let mut settings: Settings = serde_yaml::from_value(merged)?;
+    settings.validate();
+
+    Ok(settings)
+}
+
+/// Run every migration newer than the file layout
+fn migrate(config: &mut Mapping) {
+    let key = Value::String("version".to_owned());
+
+    // Files written before versioning have no version field
+    let version = config.get(&key).and_then(Value::as_u64).unwrap_or(0) as usize;
+
+    if version > SETTINGS_VERSION as usize {
+        warn!(
+            "Configuration file is from a newer Litecraft (version {}), some settings may be lost",
+            version
+        );
+        return;
+    }
+
+    for (from, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        info!(
+            "Upgrading configuration file from version {} to {}",
+            from,
+            from + 1
+        );
+        migration(config);
+    }
+
+    config.insert(key, Value::from(u64::from(SETTINGS_VERSION)));
+}
+
+/// Version 0 to 1: unversioned files just lack the sections added since, defaults fill them
+fn migrate_unversioned(_config: &mut Mapping) {}
+
+/// Lay user values over defaults one at a time, keeping defaults for missing values and values
+/// their field can't take, like numbers out of range or unknown variants
+fn merge(defaults: Value, user: Mapping) -> Value {
+    let mut values = Vec::new();
+    leaves(&defaults, Value::Mapping(user), &mut Vec::new(), &mut values);
+
+    let mut merged = defaults;
+
+    for (keys, value) in values {
+        let mut candidate = merged.clone();
+
+        if let Some(slot) = slot(&mut candidate, &keys) {
+            *slot = value;
+        }
 
-                if let Err(error) = copy(CONFIG_FILE, format!("{}.bak", CONFIG_FILE)) {
-                    warn!("Failed to copy old configuration to .bak file. {}", error);
+        // Each value is checked against its field type alone, so one bad value doesn't reject the file
+        if serde_yaml::from_value::<Settings>(candidate.clone()).is_ok() {
+            merged = candidate;
+        } else {
+            warn!("Invalid value for setting {}, using default", name(&keys));
+        }
+    }
+
+    merged
+}
+
+/// Collect user values that replace a default value, with the keys leading to them
+fn leaves(default: &Value, user: Value, keys: &mut Vec<Value>, found: &mut Vec<(Vec<Value>, Value)>) {
+    match (default, user) {
+        (Value::Mapping(default), Value::Mapping(user)) => {
+            for (key, value) in user {
+                let default = default.get(&key);
+                keys.push(key);
+
+                match default {
+                    Some(default) => leaves(default, value, keys, found),
+                    None => warn!("Unknown setting {}, ignoring it", name(keys)),
                 }
 
-                generate_config()
-            },
-            Ok(settings) => settings,
+                keys.pop();
+            }
         },
+        (_, user) => found.push((keys.clone(), user)),
     }
 }
 
-/// Generate a new configuration file using defaults
-fn generate_config() -> Settings {
-    use std::io::prelude::*;
+/// Get value at the end of some keys
+fn slot<'a>(value: &'a mut Value, keys: &[Value]) -> Option<&'a mut Value> {
+    match keys.split_first() {
+        None => Some(value),
+        Some((key, rest)) => match *value {
+            Value::Mapping(ref mut mapping) => mapping.get_mut(key).and_then(|value| slot(value, rest)),
+            _ => None,
+        },
+    }
+}
 
-    let config = Settings::new();
-    let path = Path::new(CONFIG_FILE);
+/// Get setting name from its keys, like `window.width`
+fn name(keys: &[Value]) -> String {
+    keys.iter()
+        .map(|key| match *key {
+            Value::String(ref name) => name.clone(),
+            ref key => format!("{:?}", key),
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
 
-    let serialized = serde_yaml::to_string(&config).expect("Couldn't serialize configuration");
+/// Generate a new configuration file using defaults
+fn generate_config(path: &Path) -> Settings {
+    let config = Settings::new();
 
-    File::create(&path)
-        .expect("Couldn't create configuration file")
-        .write_all(serialized.as_bytes())
-        .expect("Couldn't write to configuration file");
+    save_config_to(&config, path).expect("Couldn't write configuration file");
 
     config
 }
+
+/// Write configuration file
+pub fn save_config(settings: &Settings) -> Result<(), Box<Error>> {
+    save_config_to(settings, CONFIG_FILE)
+}
+
+/// Write configuration to a temporary file and move it over the old one, so a crash never leaves it half written
+pub fn save_config_to<P: AsRef<Path>>(settings: &Settings, path: P) -> Result<(), Box<Error>> {
+    use std::fs::rename;
+    use std::io::Write;
+
+    let path = path.as_ref();
+    let temporary = path.with_extension("yml.tmp");
+
+    let serialized = serde_yaml::to_string(settings)?;
+
+    {
+        let mut file = File::create(&temporary)?;
+        file.write_all(serialized.as_bytes())?;
+        file.sync_all()?;
+    }
+
+    rename(&temporary, path)?;
+
+    debug!("Saved configuration to {}", path.display());
+
+    Ok(())
+}
+
+/// Delays configuration writes until settings stop changing, so resizing a window doesn't write every frame
+pub struct ConfigSaver {
+    path: PathBuf,
+    changed: Option<Instant>,
+    overrides: Option<(Overrides, Overrides)>,
+    seen: Option<Settings>,
+}
+
+impl ConfigSaver {
+    /// Create a saver for a configuration file
+    pub fn new<P: Into<PathBuf>>(path: P) -> ConfigSaver {
+        ConfigSaver {
+            path: path.into(),
+            changed: None,
+            overrides: None,
+            seen: None,
+        }
+    }
+
+    /// Keep values applied from command line out of the file, `replaced` are the values they replaced
+    pub fn set_overrides(&mut self, applied: Overrides, replaced: Overrides) {
+        self.overrides = Some((applied, replaced));
+    }
+
+    /// Get configuration file path
+    pub fn path(&self) -> &Path { &self.path }
+
+    /// Remember settings changed now
+    pub fn changed(&mut self) { self.changed = Some(Instant::now()); }
+
+    /// Check if there are unsaved changes
+    pub fn pending(&self) -> bool { self.changed.is_some() }
+
+    /// Check if changes are old enough to be written
+    pub fn due(&self, now: Instant) -> bool {
+        self.changed
+            .map_or(false, |changed| now - changed >= Duration::from_secs(SAVE_DELAY))
+    }
+
+    /// Remember settings changed if they differ from the ones seen last time
+    pub fn check(&mut self, settings: &Settings) {
+        if self.seen.as_ref() == Some(settings) {
+            return;
+        }
+
+        // Settings seen first are the loaded ones
+        if self.seen.is_some() {
+            self.changed();
+        }
+
+        self.seen = Some(settings.clone());
+    }
+
+    /// Write settings if they stopped changing
+    pub fn tick(&mut self, settings: &Settings) {
+        self.check(settings);
+
+        if self.due(Instant::now()) {
+            self.flush(settings);
+        }
+    }
+
+    /// Write pending changes right now, like on shutdown
+    pub fn flush(&mut self, settings: &Settings) {
+        if self.changed.take().is_none() {
+            return;
+        }
+
+        let result = match self.overrides {
+            Some((ref applied, ref replaced)) => {
+                save_config_to(&applied.restore(settings, replaced), &self.path)
+            },
+            None => save_config_to(settings, &self.path),
+        };
+
+        if let Err(why) = result {
+            warn!("Failed to save configuration file: {}", why);
+        }
+    }
+}
@@ -0,0 +1,228 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use cgmath::prelude::*;
+use cgmath::{Point3, Vector3};
+use std::f32;
+
+/// Side of a block
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Face {
+    Down,
+    Up,
+    North,
+    South,
+    West,
+    East,
+}
+
+impl Face {
+    /// Get every face
+    pub fn all() -> &'static [Face] {
+        const FACES: [Face; 6] = [
+            Face::Down,
+            Face::Up,
+            Face::North,
+            Face::South,
+            Face::West,
+            Face::East,
+        ];
+        &FACES
+    }
+
+    /// Get name used by vanilla, like `north`
+    pub fn name(self) -> &'static str {
+        match self {
+            Face::Down => "down",
+            Face::Up => "up",
+            Face::North => "north",
+            Face::South => "south",
+            Face::West => "west",
+            Face::East => "east",
+        }
+    }
+
+    /// Get direction pointing out of the block
+    pub fn normal(self) -> Vector3<i32> {
+        match self {
+            Face::Down => Vector3::new(0, -1, 0),
+            Face::Up => Vector3::new(0, 1, 0),
+            Face::North => Vector3::new(0, 0, -1),
+            Face::South => Vector3::new(0, 0, 1),
+            Face::West => Vector3::new(-1, 0, 0),
+            Face::East => Vector3::new(1, 0, 0),
+        }
+    }
+
+    /// Get face a ray moving along an axis enters through, axis is 0 for X, 1 for Y and 2 for Z
+    pub fn entered(axis: usize, positive: bool) -> Face {
+        match (axis, positive) {
+            (0, true) => Face::West,
+            (0, false) => Face::East,
+            (1, true) => Face::Down,
+            (1, false) => Face::Up,
+            (_, true) => Face::North,
+            (_, false) => Face::South,
+        }
+    }
+}
+
+/// Axis aligned box
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoundingBox {
+    pub min: Point3<f32>,
+    pub max: Point3<f32>,
+}
+
+impl BoundingBox {
+    /// Create a box from two opposite corners
+    pub fn new(a: Point3<f32>, b: Point3<f32>) -> BoundingBox {
+        BoundingBox {
+            min: Point3::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z)),
+            max: Point3::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z)),
+        }
+    }
+
+    /// Create a box from block model coordinates, from 0 to 16
+    pub fn pixels(from: [f32; 3], to: [f32; 3]) -> BoundingBox {
+        BoundingBox::new(
+            Point3::new(from[0], from[1], from[2]) / 16.0,
+            Point3::new(to[0], to[1], to[2]) / 16.0,
+        )
+    }
+
+    /// Move box
+    pub fn offset(&self, offset: Vector3<f32>) -> BoundingBox {
+        BoundingBox {
+            min: self.min + offset,
+            max: self.max + offset,
+        }
+    }
+
+    /// Grow box on every side
+    pub fn inflate(&self, amount: f32) -> BoundingBox {
+        let amount = Vector3::new(amount, amount, amount);
+
+        BoundingBox {
+            min: self.min - amount,
+            max: self.max + amount,
+        }
+    }
+
+    /// Get distance along a ray where it enters this box and the face it enters through
+    ///
+    /// Rays starting inside the box don't hit it, like vanilla.
+    pub fn ray(&self, origin: Point3<f32>, direction: Vector3<f32>) -> Option<(f32, Face)> {
+        let mut enter = f32::NEG_INFINITY;
+        let mut exit = f32::INFINITY;
+        let mut face = None;
+
+        for axis in 0..3 {
+            let (start, step) = (origin[axis], direction[axis]);
+            let (min, max) = (self.min[axis], self.max[axis]);
+
+            if step.abs() < f32::EPSILON {
+                // Parallel to these sides, must already be between them
+                if start < min || start > max {
+                    return None;
+                }
+
+                continue;
+            }
+
+            let (near, far) = if step > 0.0 {
+                ((min - start) / step, (max - start) / step)
+            } else {
+                ((max - start) / step, (min - start) / step)
+            };
+
+            if near > enter {
+                enter = near;
+                face = Some(Face::entered(axis, step > 0.0));
+            }
+
+            exit = exit.min(far);
+        }
+
+        match face {
+            Some(face) if enter <= exit && enter >= 0.0 => Some((enter, face)),
+            _ => None,
+        }
+    }
+
+    /// Get the 12 edges as pairs of corners
+    pub fn edges(&self) -> Vec<(Point3<f32>, Point3<f32>)> {
+        let corner = |x: bool, y: bool, z: bool| {
+            Point3::new(
+                if x { self.max.x } else { self.min.x },
+                if y { self.max.y } else { self.min.y },
+                if z { self.max.z } else { self.min.z },
+            )
+        };
+
+        let mut edges = Vec::with_capacity(12);
+
+        for &a in &[false, true] {
+            for &b in &[false, true] {
+                edges.push((corner(false, a, b), corner(true, a, b)));
+                edges.push((corner(a, false, b), corner(a, true, b)));
+                edges.push((corner(a, b, false), corner(a, b, true)));
+            }
+        }
+
+        edges
+    }
+}
+
+/// Shape of a block state made of boxes in block coordinates, from 0 to 1
+#[derive(Clone, Debug, PartialEq)]
+pub struct VoxelShape {
+    boxes: Vec<BoundingBox>,
+}
+
+impl VoxelShape {
+    /// Shape of air, nothing to hit
+    pub fn empty() -> VoxelShape { VoxelShape { boxes: Vec::new() } }
+
+    /// Shape of a full block
+    pub fn cube() -> VoxelShape { VoxelShape::new(vec![BoundingBox::pixels([0.0; 3], [16.0; 3])]) }
+
+    /// Create a shape from boxes
+    pub fn new(boxes: Vec<BoundingBox>) -> VoxelShape { VoxelShape { boxes } }
+
+    /// Get boxes
+    pub fn boxes(&self) -> &[BoundingBox] { &self.boxes }
+
+    /// Check if there is nothing to hit
+    pub fn is_empty(&self) -> bool { self.boxes.is_empty() }
+
+    /// Get closest box hit by a ray when the shape is placed at a block position
+    pub fn ray(
+        &self,
+        block: Point3<i32>,
+        origin: Point3<f32>,
+        direction: Vector3<f32>,
+    ) -> Option<(f32, Face)> {
+        let offset = block.cast::<f32>().unwrap_or_else(Point3::origin).to_vec();
+
+        self.boxes
+            .iter()
+            .filter_map(|bounds| bounds.offset(offset).ray(origin, direction))
+            .fold(None, |closest: Option<(f32, Face)>, hit| match closest {
+                Some(closest) if closest.0 <= hit.0 => Some(closest),
+                _ => Some(hit),
+            })
+    }
+}
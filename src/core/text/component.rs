@@ -0,0 +1,451 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::controls::{Action, Controls};
+use core::text::language::{Language, TranslationPiece};
+use core::text::legacy;
+use core::text::style::{ClickAction, ClickEvent, HoverAction, HoverEvent, Style, TextColor};
+
+use serde_json::{self, Map, Value};
+
+use std::error::Error;
+
+type Result<T> = std::result::Result<T, Box<Error>>;
+
+/// Content of a text component
+#[derive(Clone, Debug, PartialEq)]
+pub enum ComponentKind {
+    Text(String),
+    Translation {
+        key: String,
+        with: Vec<TextComponent>,
+    },
+    Score {
+        name: String,
+        objective: String,
+        value: Option<String>,
+    },
+    Selector(String),
+    Keybind(String),
+}
+
+/// Minecraft's JSON text component
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextComponent {
+    kind: ComponentKind,
+    style: Style,
+    extra: Vec<TextComponent>,
+}
+
+/// Piece of text with every style value resolved
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextRun {
+    pub text: String,
+    pub style: Style,
+}
+
+impl TextRun {
+    /// Create a text run
+    pub fn new<S>(text: S, style: Style) -> TextRun
+    where
+        S: Into<String>,
+    {
+        TextRun {
+            text: text.into(),
+            style,
+        }
+    }
+}
+
+/// Get an optional boolean from a JSON object
+fn flag(object: &Map<String, Value>, key: &str) -> Result<Option<bool>> {
+    match object.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::Bool(value)) => Ok(Some(*value)),
+        Some(value) => Err(From::from(format!(
+            "Expected boolean on '{}', found {}",
+            key, value
+        ))),
+    }
+}
+
+/// Get a string from a JSON object
+fn string(object: &Map<String, Value>, key: &str) -> Result<String> {
+    match object.get(key) {
+        Some(Value::String(value)) => Ok(value.clone()),
+        Some(value) => Err(From::from(format!(
+            "Expected string on '{}', found {}",
+            key, value
+        ))),
+        None => Err(From::from(format!("Missing '{}'", key))),
+    }
+}
+
+impl TextComponent {
+    /// Create a component with a component kind
+    pub fn new(kind: ComponentKind) -> TextComponent {
+        TextComponent {
+            kind,
+            style: Style::default(),
+            extra: Vec::new(),
+        }
+    }
+
+    /// Create a plain text component
+    pub fn text<S>(text: S) -> TextComponent
+    where
+        S: Into<String>,
+    {
+        TextComponent::new(ComponentKind::Text(text.into()))
+    }
+
+    /// Create a translated component
+    pub fn translate<S>(key: S, with: Vec<TextComponent>) -> TextComponent
+    where
+        S: Into<String>,
+    {
+        TextComponent::new(ComponentKind::Translation {
+            key: key.into(),
+            with,
+        })
+    }
+
+    /// Parse a JSON text component
+    pub fn parse(json: &str) -> Result<TextComponent> {
+        TextComponent::from_json(&serde_json::from_str(json)?)
+    }
+
+    /// Create a component from a JSON value
+    pub fn from_json(value: &Value) -> Result<TextComponent> {
+        match value {
+            Value::String(text) => Ok(TextComponent::text(text.as_str())),
+            Value::Number(number) => Ok(TextComponent::text(number.to_string())),
+            Value::Bool(value) => Ok(TextComponent::text(value.to_string())),
+
+            // First element is the parent of the following ones
+            Value::Array(values) => {
+                let mut values = values.iter();
+
+                let mut component = match values.next() {
+                    Some(value) => TextComponent::from_json(value)?,
+                    None => return Err(From::from("Empty text component array")),
+                };
+
+                for value in values {
+                    component.extra.push(TextComponent::from_json(value)?);
+                }
+
+                Ok(component)
+            },
+
+            Value::Object(object) => TextComponent::from_object(object),
+
+            Value::Null => Err(From::from("Null is not a valid text component")),
+        }
+    }
+
+    /// Create a component from a JSON object
+    fn from_object(object: &Map<String, Value>) -> Result<TextComponent> {
+        let kind = if object.contains_key("text") {
+            match &object["text"] {
+                Value::String(text) => ComponentKind::Text(text.clone()),
+                value => ComponentKind::Text(value.to_string()),
+            }
+        } else if object.contains_key("translate") {
+            let with = match object.get("with") {
+                Some(Value::Array(values)) => values
+                    .iter()
+                    .map(TextComponent::from_json)
+                    .collect::<Result<Vec<_>>>()?,
+                _ => Vec::new(),
+            };
+
+            ComponentKind::Translation {
+                key: string(object, "translate")?,
+                with,
+            }
+        } else if let Some(Value::Object(score)) = object.get("score") {
+            ComponentKind::Score {
+                name: string(score, "name")?,
+                objective: string(score, "objective")?,
+                value: string(score, "value").ok(),
+            }
+        } else if object.contains_key("selector") {
+            ComponentKind::Selector(string(object, "selector")?)
+        } else if object.contains_key("keybind") {
+            ComponentKind::Keybind(string(object, "keybind")?)
+        } else {
+            return Err(From::from(format!(
+                "Don't know how to turn {:?} into a component",
+                object
+            )));
+        };
+
+        let mut component = TextComponent::new(kind);
+        component.style = TextComponent::parse_style(object)?;
+
+        if let Some(Value::Array(extra)) = object.get("extra") {
+            for value in extra {
+                component.extra.push(TextComponent::from_json(value)?);
+            }
+        }
+
+        Ok(component)
+    }
+
+    /// Read formatting values from a JSON object
+    fn parse_style(object: &Map<String, Value>) -> Result<Style> {
+        let color = match object.get("color") {
+            // Reset means no color at all
+            Some(Value::String(ref name)) if name == "reset" => None,
+            Some(Value::String(name)) => {
+                Some(TextColor::from_name(name).ok_or_else(|| format!("Unknown color '{}'", name))?)
+            },
+            _ => None,
+        };
+
+        let click_event = match object.get("clickEvent") {
+            Some(Value::Object(event)) => {
+                let action = string(event, "action")?;
+
+                ClickAction::from_name(&action).map(|action| ClickEvent {
+                    action,
+                    value: event.get("value").map_or_else(String::new, |value| match value {
+                        Value::String(value) => value.clone(),
+                        value => value.to_string(),
+                    }),
+                })
+            },
+            _ => None,
+        };
+
+        let hover_event = match object.get("hoverEvent") {
+            Some(Value::Object(event)) => {
+                let action = string(event, "action")?;
+
+                match (HoverAction::from_name(&action), event.get("value")) {
+                    (Some(action), Some(value)) => Some(HoverEvent {
+                        action,
+                        value: Box::new(TextComponent::from_json(value)?),
+                    }),
+                    _ => None,
+                }
+            },
+            _ => None,
+        };
+
+        Ok(Style {
+            color,
+            bold: flag(object, "bold")?,
+            italic: flag(object, "italic")?,
+            underlined: flag(object, "underlined")?,
+            strikethrough: flag(object, "strikethrough")?,
+            obfuscated: flag(object, "obfuscated")?,
+            insertion: string(object, "insertion").ok(),
+            click_event,
+            hover_event,
+        })
+    }
+
+    /// Convert component to JSON
+    pub fn to_json(&self) -> Value {
+        let mut object = Map::new();
+
+        match &self.kind {
+            ComponentKind::Text(text) => {
+                object.insert("text".to_owned(), Value::String(text.clone()));
+            },
+            ComponentKind::Translation { key, with } => {
+                object.insert("translate".to_owned(), Value::String(key.clone()));
+
+                if !with.is_empty() {
+                    object.insert(
+                        "with".to_owned(),
+                        Value::Array(with.iter().map(|c| c.to_json()).collect()),
+                    );
+                }
+            },
+            ComponentKind::Score {
+                name,
+                objective,
+                value,
+            } => {
+                let mut score = Map::new();
+
+                score.insert("name".to_owned(), Value::String(name.clone()));
+                score.insert("objective".to_owned(), Value::String(objective.clone()));
+
+                if let Some(value) = value {
+                    score.insert("value".to_owned(), Value::String(value.clone()));
+                }
+
+                object.insert("score".to_owned(), Value::Object(score));
+            },
+            ComponentKind::Selector(selector) => {
+                object.insert("selector".to_owned(), Value::String(selector.clone()));
+            },
+            ComponentKind::Keybind(keybind) => {
+                object.insert("keybind".to_owned(), Value::String(keybind.clone()));
+            },
+        }
+
+        let style = &self.style;
+
+        if let Some(color) = style.color {
+            object.insert("color".to_owned(), Value::String(color.name().to_owned()));
+        }
+
+        let flags = [
+            ("bold", style.bold),
+            ("italic", style.italic),
+            ("underlined", style.underlined),
+            ("strikethrough", style.strikethrough),
+            ("obfuscated", style.obfuscated),
+        ];
+
+        for (key, value) in flags.iter() {
+            if let Some(value) = value {
+                object.insert((*key).to_owned(), Value::Bool(*value));
+            }
+        }
+
+        if let Some(insertion) = &style.insertion {
+            object.insert("insertion".to_owned(), Value::String(insertion.clone()));
+        }
+
+        if let Some(event) = &style.click_event {
+            object.insert(
+                "clickEvent".to_owned(),
+                json!({ "action": event.action.name(), "value": event.value }),
+            );
+        }
+
+        if let Some(event) = &style.hover_event {
+            object.insert(
+                "hoverEvent".to_owned(),
+                json!({ "action": event.action.name(), "value": event.value.to_json() }),
+            );
+        }
+
+        if !self.extra.is_empty() {
+            object.insert(
+                "extra".to_owned(),
+                Value::Array(self.extra.iter().map(|c| c.to_json()).collect()),
+            );
+        }
+
+        Value::Object(object)
+    }
+
+    /// Get component content
+    pub fn kind(&self) -> &ComponentKind { &self.kind }
+
+    /// Get component style
+    pub fn style(&self) -> &Style { &self.style }
+
+    /// Get component style
+    pub fn style_mut(&mut self) -> &mut Style { &mut self.style }
+
+    /// Set component style
+    pub fn with_style(mut self, style: Style) -> TextComponent {
+        self.style = style;
+        self
+    }
+
+    /// Get child components
+    pub fn extra(&self) -> &Vec<TextComponent> { &self.extra }
+
+    /// Add a child component
+    pub fn append(&mut self, component: TextComponent) -> &mut TextComponent {
+        self.extra.push(component);
+        self
+    }
+
+    /// Flatten component into styled runs, resolving translations and key bindings
+    pub fn runs(&self, language: &Language, controls: &Controls) -> Vec<TextRun> {
+        let mut runs = Vec::new();
+        self.collect(&Style::default(), language, controls, &mut runs);
+        runs
+    }
+
+    /// Get text without formatting
+    pub fn plain(&self, language: &Language, controls: &Controls) -> String {
+        self.runs(language, controls).into_iter().map(|run| run.text).collect()
+    }
+
+    fn collect(&self, parent: &Style, language: &Language, controls: &Controls, runs: &mut Vec<TextRun>) {
+        let style = self.style.inherit(parent);
+
+        let push = |runs: &mut Vec<TextRun>, text: &str| {
+            if !text.is_empty() {
+                runs.push(TextRun::new(text, style.clone()));
+            }
+        };
+
+        match &self.kind {
+            ComponentKind::Text(text) => push(runs, text),
+            ComponentKind::Translation { key, with } => {
+                for piece in language.format(key) {
+                    match piece {
+                        TranslationPiece::Literal(text) => push(runs, &text),
+                        TranslationPiece::Argument(index) => {
+                            if let Some(argument) = with.get(index) {
+                                argument.collect(&style, language, controls, runs);
+                            }
+                        },
+                    }
+                }
+            },
+            ComponentKind::Score { value, .. } => {
+                push(runs, value.as_ref().map_or("", |value| value.as_str()))
+            },
+            ComponentKind::Selector(selector) => push(runs, selector),
+            // Key bound to the action like vanilla, unknown actions show their translation
+            ComponentKind::Keybind(keybind) => match Action::from_vanilla_name(keybind) {
+                Some(action) => push(runs, &controls.get(action).to_string()),
+                None => push(runs, language.translate(keybind)),
+            },
+        }
+
+        for child in &self.extra {
+            child.collect(&style, language, controls, runs);
+        }
+    }
+}
+
+/// Text that can be drawn with styles
+pub trait IntoRuns {
+    /// Get styled runs, translations are resolved with a language and key bindings with controls
+    fn into_runs(self, language: &Language, controls: &Controls) -> Vec<TextRun>;
+}
+
+/// Plain strings may contain legacy formatting codes
+impl<'a> IntoRuns for &'a str {
+    fn into_runs(self, _: &Language, _: &Controls) -> Vec<TextRun> { legacy::parse(self) }
+}
+
+impl<'a> IntoRuns for &'a String {
+    fn into_runs(self, _: &Language, _: &Controls) -> Vec<TextRun> { legacy::parse(self) }
+}
+
+impl<'a> IntoRuns for &'a TextComponent {
+    fn into_runs(self, language: &Language, controls: &Controls) -> Vec<TextRun> {
+        self.runs(language, controls)
+    }
+}
+
+impl IntoRuns for Vec<TextRun> {
+    fn into_runs(self, _: &Language, _: &Controls) -> Vec<TextRun> { self }
+}
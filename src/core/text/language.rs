@@ -0,0 +1,158 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::resource_manager::resource::Resource;
+use core::resource_manager::resource_type::ResourceType;
+
+use serde_json;
+
+use std::collections::HashMap;
+use std::error::Error;
+
+type Result<T> = std::result::Result<T, Box<Error>>;
+
+/// Language used when nothing else is configured
+pub const DEFAULT_LANGUAGE: &str = "en_us";
+
+/// Part of a translated string
+#[derive(Clone, Debug, PartialEq)]
+pub enum TranslationPiece {
+    Literal(String),
+    Argument(usize),
+}
+
+/// Translation table loaded from `lang/<code>.json`
+pub struct Language {
+    code: String,
+    translations: HashMap<String, String>,
+}
+
+impl Language {
+    /// Create a language without translations, keys are shown as is
+    pub fn empty() -> Language {
+        Language {
+            code: DEFAULT_LANGUAGE.to_owned(),
+            translations: HashMap::new(),
+        }
+    }
+
+    /// Load a language file
+    pub fn load(code: &str) -> Result<Language> {
+        info!("Loading language '{}'", code);
+
+        let data = Resource::minecraft(code.to_owned(), ResourceType::Language).load()?;
+
+        Ok(Language {
+            code: code.to_owned(),
+            translations: serde_json::from_str(&data)?,
+        })
+    }
+
+    /// Get language code
+    pub fn code(&self) -> &str { &self.code }
+
+    /// Check if a translation exists
+    pub fn has(&self, key: &str) -> bool { self.translations.contains_key(key) }
+
+    /// Get a translation, the key itself if missing
+    pub fn translate<'a>(&'a self, key: &'a str) -> &'a str {
+        self.translations.get(key).map_or(key, |value| value.as_str())
+    }
+
+    /// Get a translation, a fallback text if missing
+    pub fn translate_or<'a>(&'a self, key: &str, fallback: &'a str) -> &'a str {
+        self.translations
+            .get(key)
+            .map_or(fallback, |value| value.as_str())
+    }
+
+    /// Get codes of every language found on resources, like en_us
+    pub fn available() -> Result<Vec<String>> { Resource::list("minecraft", ResourceType::Language) }
+
+    /// Split a translation into literals and `%s` or `%1$s` arguments
+    pub fn format(&self, key: &str) -> Vec<TranslationPiece> {
+        let mut pieces = Vec::new();
+        let mut literal = String::new();
+        let mut next = 0;
+
+        let mut chars = self.translate(key).chars().peekable();
+
+        while let Some(character) = chars.next() {
+            if character != '%' {
+                literal.push(character);
+                continue;
+            }
+
+            if chars.peek() == Some(&'%') {
+                chars.next();
+                literal.push('%');
+                continue;
+            }
+
+            // Optional explicit position like `%2$s`
+            let mut digits = String::new();
+
+            while let Some(&digit) = chars.peek() {
+                if !digit.is_ascii_digit() {
+                    break;
+                }
+
+                digits.push(digit);
+                chars.next();
+            }
+
+            let explicit = !digits.is_empty() && chars.peek() == Some(&'$');
+
+            if explicit {
+                chars.next();
+            }
+
+            // Conversion letter, always treated as a string
+            let letter = chars.peek().map_or(false, |letter| letter.is_ascii_alphabetic());
+
+            if !letter || (!digits.is_empty() && !explicit) {
+                literal.push('%');
+                literal.push_str(&digits);
+
+                if explicit {
+                    literal.push('$');
+                }
+
+                continue;
+            }
+
+            chars.next();
+
+            let index = if explicit {
+                digits.parse::<usize>().unwrap_or(1).saturating_sub(1)
+            } else {
+                next += 1;
+                next - 1
+            };
+
+            if !literal.is_empty() {
+                pieces.push(TranslationPiece::Literal(literal.split_off(0)));
+            }
+
+            pieces.push(TranslationPiece::Argument(index));
+        }
+
+        if !literal.is_empty() {
+            pieces.push(TranslationPiece::Literal(literal));
+        }
+
+        pieces
+    }
+}
@@ -0,0 +1,167 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::controls::Controls;
+use core::text::component::{TextComponent, TextRun};
+use core::text::language::Language;
+use core::text::style::{Style, TextColor};
+
+/// Prefix of legacy formatting codes
+pub const SECTION_SIGN: char = '§';
+
+/// Apply a formatting code to a style, none if the code is unknown
+fn apply(style: &Style, code: char) -> Option<Style> {
+    // Colors reset every other format
+    if let Some(color) = TextColor::from_code(code) {
+        return Some(Style {
+            color: Some(color),
+            ..Style::default()
+        });
+    }
+
+    let mut style = style.clone();
+
+    match code {
+        'k' => style.obfuscated = Some(true),
+        'l' => style.bold = Some(true),
+        'm' => style.strikethrough = Some(true),
+        'n' => style.underlined = Some(true),
+        'o' => style.italic = Some(true),
+        'r' => return Some(Style::default()),
+        _ => return None,
+    }
+
+    Some(style)
+}
+
+/// Get format codes enabled on a style
+fn flags(style: &Style) -> [(char, bool); 5] {
+    [
+        ('k', style.obfuscated()),
+        ('l', style.bold()),
+        ('m', style.strikethrough()),
+        ('n', style.underlined()),
+        ('o', style.italic()),
+    ]
+}
+
+/// Parse a legacy formatted string like `§aGreen §lbold` into styled runs
+pub fn parse(text: &str) -> Vec<TextRun> {
+    let mut runs = Vec::new();
+    let mut style = Style::default();
+    let mut current = String::new();
+
+    let mut chars = text.chars();
+
+    while let Some(character) = chars.next() {
+        if character != SECTION_SIGN {
+            current.push(character);
+            continue;
+        }
+
+        // Unknown codes are hidden but change nothing
+        let code = match chars.next() {
+            Some(code) => code.to_ascii_lowercase(),
+            None => break,
+        };
+
+        if let Some(next) = apply(&style, code) {
+            if next != style && !current.is_empty() {
+                runs.push(TextRun::new(current.split_off(0), style));
+            }
+
+            style = next;
+        }
+    }
+
+    if !current.is_empty() {
+        runs.push(TextRun::new(current, style));
+    }
+
+    runs
+}
+
+/// Remove every formatting code
+pub fn strip(text: &str) -> String { parse(text).into_iter().map(|run| run.text).collect() }
+
+/// Convert a legacy formatted string to a text component
+pub fn to_component(text: &str) -> TextComponent {
+    let mut runs = parse(text).into_iter();
+
+    let component = match runs.next() {
+        Some(run) => TextComponent::text(run.text).with_style(run.style),
+        None => return TextComponent::text(""),
+    };
+
+    if runs.len() == 0 {
+        return component;
+    }
+
+    // Children would inherit the first run style, so use an empty parent
+    let mut parent = TextComponent::text("");
+    parent.append(component);
+
+    for run in runs {
+        parent.append(TextComponent::text(run.text).with_style(run.style));
+    }
+
+    parent
+}
+
+/// Convert styled runs to a legacy formatted string
+pub fn format(runs: &[TextRun]) -> String {
+    let mut text = String::new();
+    let mut previous = Style::default();
+
+    for run in runs {
+        let previous_flags = flags(&previous);
+        let next_flags = flags(&run.style);
+
+        let same_color = previous.color() == run.style.color();
+        let changed = !same_color || previous_flags != next_flags;
+
+        if changed {
+            // Codes can only add formats, anything else needs to start over
+            let additive = same_color
+                && previous_flags
+                    .iter()
+                    .zip(next_flags.iter())
+                    .all(|(a, b)| !a.1 || b.1);
+
+            if !additive {
+                text.push(SECTION_SIGN);
+                text.push(run.style.color.map_or('r', |color| color.code()));
+            }
+
+            for (index, &(code, enabled)) in next_flags.iter().enumerate() {
+                if enabled && (!additive || !previous_flags[index].1) {
+                    text.push(SECTION_SIGN);
+                    text.push(code);
+                }
+            }
+
+            previous = run.style.clone();
+        }
+
+        text.push_str(&run.text);
+    }
+
+    text
+}
+
+/// Convert a text component to a legacy formatted string
+pub fn from_component(component: &TextComponent, language: &Language, controls: &Controls) -> String {
+    format(&component.runs(language, controls))
+}
@@ -0,0 +1,266 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::text::component::TextComponent;
+
+/// Minecraft's named text colors
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TextColor {
+    Black,
+    DarkBlue,
+    DarkGreen,
+    DarkAqua,
+    DarkRed,
+    DarkPurple,
+    Gold,
+    Gray,
+    DarkGray,
+    Blue,
+    Green,
+    Aqua,
+    Red,
+    LightPurple,
+    Yellow,
+    White,
+}
+
+/// Every color sorted by formatting code
+const COLORS: [TextColor; 16] = [
+    TextColor::Black,
+    TextColor::DarkBlue,
+    TextColor::DarkGreen,
+    TextColor::DarkAqua,
+    TextColor::DarkRed,
+    TextColor::DarkPurple,
+    TextColor::Gold,
+    TextColor::Gray,
+    TextColor::DarkGray,
+    TextColor::Blue,
+    TextColor::Green,
+    TextColor::Aqua,
+    TextColor::Red,
+    TextColor::LightPurple,
+    TextColor::Yellow,
+    TextColor::White,
+];
+
+impl TextColor {
+    /// Get color by its JSON name
+    pub fn from_name(name: &str) -> Option<TextColor> {
+        COLORS.iter().cloned().find(|color| color.name() == name)
+    }
+
+    /// Get color by its formatting code index, from 0 to 15
+    pub fn from_index(index: usize) -> Option<TextColor> { COLORS.get(index).cloned() }
+
+    /// Get formatting code index
+    pub fn index(self) -> usize { COLORS.iter().position(|&color| color == self).unwrap_or(15) }
+
+    /// Get color by its legacy formatting code, like `a` for green
+    pub fn from_code(code: char) -> Option<TextColor> {
+        code.to_digit(16).and_then(|index| TextColor::from_index(index as usize))
+    }
+
+    /// Get legacy formatting code
+    pub fn code(self) -> char { ::std::char::from_digit(self.index() as u32, 16).unwrap_or('f') }
+
+    /// Get JSON name
+    pub fn name(self) -> &'static str {
+        match self {
+            TextColor::Black => "black",
+            TextColor::DarkBlue => "dark_blue",
+            TextColor::DarkGreen => "dark_green",
+            TextColor::DarkAqua => "dark_aqua",
+            TextColor::DarkRed => "dark_red",
+            TextColor::DarkPurple => "dark_purple",
+            TextColor::Gold => "gold",
+            TextColor::Gray => "gray",
+            TextColor::DarkGray => "dark_gray",
+            TextColor::Blue => "blue",
+            TextColor::Green => "green",
+            TextColor::Aqua => "aqua",
+            TextColor::Red => "red",
+            TextColor::LightPurple => "light_purple",
+            TextColor::Yellow => "yellow",
+            TextColor::White => "white",
+        }
+    }
+
+    /// Get color as RGB bytes
+    pub fn rgb(self) -> [u8; 3] {
+        match self {
+            TextColor::Black => [0, 0, 0],
+            TextColor::DarkBlue => [0, 0, 170],
+            TextColor::DarkGreen => [0, 170, 0],
+            TextColor::DarkAqua => [0, 170, 170],
+            TextColor::DarkRed => [170, 0, 0],
+            TextColor::DarkPurple => [170, 0, 170],
+            TextColor::Gold => [255, 170, 0],
+            TextColor::Gray => [170, 170, 170],
+            TextColor::DarkGray => [85, 85, 85],
+            TextColor::Blue => [85, 85, 255],
+            TextColor::Green => [85, 255, 85],
+            TextColor::Aqua => [85, 255, 255],
+            TextColor::Red => [255, 85, 85],
+            TextColor::LightPurple => [255, 85, 255],
+            TextColor::Yellow => [255, 255, 85],
+            TextColor::White => [255, 255, 255],
+        }
+    }
+
+    /// Get color as normalized RGBA
+    pub fn rgba(self) -> [f32; 4] {
+        let [r, g, b] = self.rgb();
+        [
+            f32::from(r) / 255.0,
+            f32::from(g) / 255.0,
+            f32::from(b) / 255.0,
+            1.0,
+        ]
+    }
+}
+
+/// Action executed when a text is clicked
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClickAction {
+    OpenUrl,
+    OpenFile,
+    RunCommand,
+    SuggestCommand,
+    ChangePage,
+}
+
+impl ClickAction {
+    /// Get action by its JSON name
+    pub fn from_name(name: &str) -> Option<ClickAction> {
+        match name {
+            "open_url" => Some(ClickAction::OpenUrl),
+            "open_file" => Some(ClickAction::OpenFile),
+            "run_command" => Some(ClickAction::RunCommand),
+            "suggest_command" => Some(ClickAction::SuggestCommand),
+            "change_page" => Some(ClickAction::ChangePage),
+            _ => None,
+        }
+    }
+
+    /// Get JSON name
+    pub fn name(self) -> &'static str {
+        match self {
+            ClickAction::OpenUrl => "open_url",
+            ClickAction::OpenFile => "open_file",
+            ClickAction::RunCommand => "run_command",
+            ClickAction::SuggestCommand => "suggest_command",
+            ClickAction::ChangePage => "change_page",
+        }
+    }
+}
+
+/// Text `clickEvent`
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClickEvent {
+    pub action: ClickAction,
+    pub value: String,
+}
+
+/// Tooltip shown when a text is hovered
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HoverAction {
+    ShowText,
+    ShowItem,
+    ShowEntity,
+    ShowAchievement,
+}
+
+impl HoverAction {
+    /// Get action by its JSON name
+    pub fn from_name(name: &str) -> Option<HoverAction> {
+        match name {
+            "show_text" => Some(HoverAction::ShowText),
+            "show_item" => Some(HoverAction::ShowItem),
+            "show_entity" => Some(HoverAction::ShowEntity),
+            "show_achievement" => Some(HoverAction::ShowAchievement),
+            _ => None,
+        }
+    }
+
+    /// Get JSON name
+    pub fn name(self) -> &'static str {
+        match self {
+            HoverAction::ShowText => "show_text",
+            HoverAction::ShowItem => "show_item",
+            HoverAction::ShowEntity => "show_entity",
+            HoverAction::ShowAchievement => "show_achievement",
+        }
+    }
+}
+
+/// Text `hoverEvent`
+#[derive(Clone, Debug, PartialEq)]
+pub struct HoverEvent {
+    pub action: HoverAction,
+    pub value: Box<TextComponent>,
+}
+
+/// Text formatting, unset values are inherited from the parent component
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Style {
+    pub color: Option<TextColor>,
+    pub bold: Option<bool>,
+    pub italic: Option<bool>,
+    pub underlined: Option<bool>,
+    pub strikethrough: Option<bool>,
+    pub obfuscated: Option<bool>,
+    pub insertion: Option<String>,
+    pub click_event: Option<ClickEvent>,
+    pub hover_event: Option<HoverEvent>,
+}
+
+impl Style {
+    /// Fill unset values from a parent style
+    pub fn inherit(&self, parent: &Style) -> Style {
+        Style {
+            color: self.color.or(parent.color),
+            bold: self.bold.or(parent.bold),
+            italic: self.italic.or(parent.italic),
+            underlined: self.underlined.or(parent.underlined),
+            strikethrough: self.strikethrough.or(parent.strikethrough),
+            obfuscated: self.obfuscated.or(parent.obfuscated),
+            insertion: self.insertion.clone().or_else(|| parent.insertion.clone()),
+            click_event: self.click_event.clone().or_else(|| parent.click_event.clone()),
+            hover_event: self.hover_event.clone().or_else(|| parent.hover_event.clone()),
+        }
+    }
+
+    /// Check if nothing is set
+    pub fn is_empty(&self) -> bool { *self == Style::default() }
+
+    /// Get color, white if unset
+    pub fn color(&self) -> TextColor { self.color.unwrap_or(TextColor::White) }
+
+    /// Get if text is bold
+    pub fn bold(&self) -> bool { self.bold.unwrap_or(false) }
+
+    /// Get if text is italic
+    pub fn italic(&self) -> bool { self.italic.unwrap_or(false) }
+
+    /// Get if text is underlined
+    pub fn underlined(&self) -> bool { self.underlined.unwrap_or(false) }
+
+    /// Get if text is crossed out
+    pub fn strikethrough(&self) -> bool { self.strikethrough.unwrap_or(false) }
+
+    /// Get if text glyphs should be randomized
+    pub fn obfuscated(&self) -> bool { self.obfuscated.unwrap_or(false) }
+}
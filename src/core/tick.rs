@@ -0,0 +1,73 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use std::time::Duration;
+
+/// Game ticks per second, same as vanilla
+pub const TICKS_PER_SECOND: u32 = 20;
+
+/// Most ticks run in a single frame, time beyond them is dropped after a hitch
+pub const MAX_TICKS_PER_FRAME: u32 = 10;
+
+const NANOS_PER_SECOND: u64 = 1_000_000_000;
+
+/// Runs game ticks at a fixed rate no matter how fast frames are drawn
+pub struct TickTimer {
+    // Tick length and time not yet ticked in nanoseconds
+    length: u64,
+    accumulated: u64,
+
+    ticks: u64,
+}
+
+impl TickTimer {
+    /// Create timer running some ticks per second
+    pub fn new(ticks_per_second: u32) -> TickTimer {
+        TickTimer {
+            length: NANOS_PER_SECOND / u64::from(ticks_per_second.max(1)),
+            accumulated: 0,
+            ticks: 0,
+        }
+    }
+
+    /// Add time since last frame, returning how many ticks should run now
+    pub fn advance(&mut self, elapsed: Duration) -> u32 {
+        self.accumulated += elapsed.as_secs() * NANOS_PER_SECOND + u64::from(elapsed.subsec_nanos());
+
+        let pending = self.accumulated / self.length;
+        self.accumulated %= self.length;
+
+        let ticks = if pending > u64::from(MAX_TICKS_PER_FRAME) {
+            warn!(
+                "Can't keep up! Running {}ms behind, skipping {} ticks",
+                pending * self.length / 1_000_000,
+                pending - u64::from(MAX_TICKS_PER_FRAME)
+            );
+
+            MAX_TICKS_PER_FRAME
+        } else {
+            pending as u32
+        };
+
+        self.ticks += u64::from(ticks);
+        ticks
+    }
+
+    /// Get progress towards next tick from 0 to 1, used to draw between ticks
+    pub fn partial_tick(&self) -> f32 { self.accumulated as f32 / self.length as f32 }
+
+    /// Get ticks run since the timer was created
+    pub fn ticks(&self) -> u64 { self.ticks }
+}
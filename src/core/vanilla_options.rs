@@ -0,0 +1,210 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::controls::{Action, Binding};
+use core::settings::Settings;
+
+use gfx::text::GUI_SCALE;
+
+use sound::SoundCategory;
+
+use serde_json;
+
+use std::env;
+use std::error::Error;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Vanilla stores FOV as an offset from 70 degrees, scaled by this
+const FOV_SCALE: f32 = 40.0;
+
+/// Smallest window vanilla keeps at automatic GUI scale, in GUI pixels
+const AUTO_GUI_SIZE: (u32, u32) = (320, 240);
+
+/// Vanilla keys that describe the file, not a setting
+const IGNORED_KEYS: [&str; 1] = ["version"];
+
+/// What happened to each key of an imported `options.txt`
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    /// Keys copied into settings
+    pub imported: Vec<String>,
+
+    /// Keys without a Litecraft setting or with a value we don't understand
+    pub unmapped: Vec<String>,
+}
+
+impl ImportReport {
+    /// Log import summary and every unmapped key
+    pub fn log(&self) {
+        info!(
+            "Imported {} vanilla options, {} could not be mapped",
+            self.imported.len(),
+            self.unmapped.len()
+        );
+
+        for key in &self.unmapped {
+            debug!("Vanilla option '{}' was not imported", key);
+        }
+    }
+}
+
+/// Get default vanilla `options.txt` location on this platform
+pub fn vanilla_options_path() -> Option<PathBuf> {
+    let minecraft = if cfg!(target_os = "windows") {
+        PathBuf::from(env::var_os("APPDATA")?).join(".minecraft")
+    } else if cfg!(target_os = "macos") {
+        PathBuf::from(env::var_os("HOME")?).join("Library/Application Support/minecraft")
+    } else {
+        PathBuf::from(env::var_os("HOME")?).join(".minecraft")
+    };
+
+    Some(minecraft.join("options.txt"))
+}
+
+/// Read a vanilla `options.txt` into settings
+pub fn import_options_file<P: AsRef<Path>>(
+    path: P,
+    settings: &mut Settings,
+) -> Result<ImportReport, Box<Error>> {
+    let mut data = String::new();
+    File::open(path.as_ref())?.read_to_string(&mut data)?;
+
+    info!("Importing vanilla options from {}", path.as_ref().display());
+
+    Ok(import_options(&data, settings))
+}
+
+/// Copy every vanilla option with a Litecraft equivalent into settings
+pub fn import_options(data: &str, settings: &mut Settings) -> ImportReport {
+    let mut report = ImportReport::default();
+
+    for line in data.lines() {
+        let line = line.trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        let (key, value) = match line.find(':') {
+            Some(colon) => (&line[..colon], &line[colon + 1..]),
+            None => {
+                report.unmapped.push(line.to_owned());
+                continue;
+            },
+        };
+
+        if IGNORED_KEYS.contains(&key) {
+            continue;
+        }
+
+        if import_option(key, value, settings).is_some() {
+            report.imported.push(key.to_owned());
+        } else {
+            report.unmapped.push(key.to_owned());
+        }
+    }
+
+    settings.validate();
+    report
+}
+
+/// Import a single option, None if it has no equivalent or its value is invalid
+fn import_option(key: &str, value: &str, settings: &mut Settings) -> Option<()> {
+    match key {
+        "fov" => {
+            let offset: f32 = value.parse().ok()?;
+            settings.set_fov((70.0 + offset * FOV_SCALE).round() as u8);
+        },
+
+        "guiScale" => {
+            // Vanilla scale N draws a GUI pixel as N screen pixels, ours draws it as GUI_SCALE at scale 1
+            let scale = match value.parse::<u32>().ok()? {
+                0 => auto_gui_scale(settings.width(), settings.height()),
+                scale => scale,
+            };
+
+            settings.set_scale(f64::from(scale) / GUI_SCALE);
+        },
+
+        "renderDistance" => settings.set_render_distance(value.parse().ok()?),
+        "enableVsync" => settings.set_vsync(value.parse().ok()?),
+        "fullscreen" => settings.set_fullscreen(value.parse().ok()?),
+
+        "overrideWidth" | "overrideHeight" => {
+            let size: u32 = value.parse().ok()?;
+
+            // Zero means window size is not overridden
+            if size > 0 {
+                if key == "overrideWidth" {
+                    settings.set_width(size);
+                } else {
+                    settings.set_height(size);
+                }
+            }
+        },
+
+        "mouseSensitivity" => settings.set_mouse_sensitivity(value.parse().ok()?),
+        "invertYMouse" => settings.set_invert_mouse(value.parse().ok()?),
+
+        "lang" => settings.set_language(&value.to_lowercase()),
+
+        "resourcePacks" => {
+            let packs: Vec<String> = serde_json::from_str(value).ok()?;
+
+            // Vanilla lists packs from lowest to highest priority, we do the opposite
+            let packs = packs
+                .iter()
+                .rev()
+                .filter(|pack| *pack != "vanilla" && *pack != "programer_art")
+                .map(|pack| {
+                    pack.trim_start_matches("file/")
+                        .trim_end_matches(".zip")
+                        .to_owned()
+                })
+                .collect();
+
+            settings.set_resourcepacks(packs);
+        },
+
+        _ if key.starts_with("soundCategory_") => {
+            let category = SoundCategory::from_name(&key["soundCategory_".len()..])?;
+            settings.set_volume(category, value.parse().ok()?);
+        },
+
+        _ if key.starts_with("key_") => {
+            let action = Action::from_vanilla_name(&key["key_".len()..])?;
+            let binding = Binding::from_vanilla_name(value)?;
+
+            settings.controls_mut().set(action, binding);
+        },
+
+        _ => return None,
+    }
+
+    Some(())
+}
+
+/// Get the scale vanilla picks on automatic GUI scale, the biggest one keeping the window at least `AUTO_GUI_SIZE`
+fn auto_gui_scale(width: u32, height: u32) -> u32 {
+    let mut scale = 1;
+
+    while width / (scale + 1) >= AUTO_GUI_SIZE.0 && height / (scale + 1) >= AUTO_GUI_SIZE.1 {
+        scale += 1;
+    }
+
+    scale
+}
@@ -13,44 +13,105 @@
 // LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
 // IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
+use core::arguments::Arguments;
+use core::controls::{Action, Binding, Key, Modifiers};
+use core::input::{InputEvent, InputState};
 use core::resource_manager::resource::Resource;
 use core::resource_manager::resource_type::ResourceType;
 use core::resource_manager::ResourceManager;
 use core::settings::Settings;
+use core::settings_manager::ConfigSaver;
+use core::tick::{TickTimer, TICKS_PER_SECOND};
 
 use scenes::loading::LoadingScene;
 
-use gfx::scene::{Scene, SceneAction};
-
-use glium::glutin::{ContextBuilder, ControlFlow, Event, EventsLoop, WindowBuilder, WindowEvent};
+use sound::SoundManager;
+
+use gfx::clip_control::ClipControl;
+use gfx::debug::{draw_overlay, DebugOverlay};
+use gfx::device::{GliumDevice, RecordingDevice};
+use gfx::pencil::clear_depth;
+use gfx::scene::SceneStack;
+use gfx::screenshot::{capture_high_res, Screenshots, SCREENSHOTS_DIRECTORY};
+use gfx::toast::{draw_toasts, Toasts};
+use gfx::transition::{draw_transition, Transitions};
+use gfx::ui_renderer::{glyph_texture, white_texture, UiRenderer, GLYPH_CACHE_SIZE};
+
+use glium::glutin::{
+    ContextBuilder, ControlFlow, ElementState, Event, EventsLoop, KeyboardInput, WindowBuilder,
+    WindowEvent,
+};
+use glium::texture::RawImage2d;
 use glium::{Display, Surface};
 
-use conrod::backend::glium::Renderer;
 use conrod::{Ui, UiBuilder};
 
 use rhai::Engine;
 
+use std::time::Instant;
+
 /// Main game struct, its role is draw and manage everything in existence
 pub struct Canvas {
     resource_manager: ResourceManager,
-    display: Display,
+    sound_manager: SoundManager,
+    display: Option<Display>,
+    clip_control: Option<ClipControl>,
     settings: Settings,
+    config: ConfigSaver,
     engine: Engine,
     ui: Ui,
+    pressed: Option<Binding>,
+    paused: bool,
+    transitions: Transitions,
+    input: InputState,
+    cursor_grab: bool,
+    focused: bool,
+    timer: TickTimer,
+    debug: DebugOverlay,
+    screenshots: Screenshots,
+    toasts: Toasts,
+    arguments: Arguments,
 }
 
 impl Canvas {
     /// Create and start drawing Canvas
-    pub fn start() {
-        use core::settings_manager::load_config;
+    pub fn start(arguments: Arguments) {
+        use core::settings_manager::load_config_from;
+        use core::vanilla_options::import_options_file;
 
         let mut events_loop = EventsLoop::new();
 
         // Default action: Keep running
         let mut status = ControlFlow::Continue;
 
-        // Load settings file
-        let settings = load_config();
+        // Load settings file, command line values win but are never saved
+        let mut settings = load_config_from(&arguments.config);
+
+        // Imported options are kept, unlike other command line values
+        let mut imported = false;
+
+        if let Some(ref path) = arguments.import_options {
+            match import_options_file(path, &mut settings) {
+                Ok(report) => {
+                    report.log();
+                    imported = true;
+                },
+                Err(why) => warn!(
+                    "Failed to import vanilla options from {}: {}",
+                    path.display(),
+                    why
+                ),
+            }
+        }
+
+        let replaced = arguments.overrides.apply(&mut settings);
+
+        let mut config = ConfigSaver::new(arguments.config.clone());
+        config.set_overrides(arguments.overrides.clone(), replaced);
+
+        if imported {
+            config.changed();
+        }
 
         // Create game window
         let window = Canvas::create_window(&settings, &events_loop);
@@ -65,106 +126,262 @@ impl Canvas {
         let display = Display::new(window, context, &events_loop);
         let display = display.expect("Failed to initialize display");
 
-        // Create UI Manager
-        let mut ui = UiBuilder::new([settings.width().into(), settings.height().into()]).build();
+        // Create canvas manager
+        let mut canvas = Canvas::create(arguments, settings, config, Some(display.clone()));
 
         // Load default font
-        ui.fonts.insert(
+        canvas.ui.fonts.insert(
             ResourceManager::font(&Resource::litecraft("default", ResourceType::Font))
                 .expect("Failed to load default font file"),
         );
 
-        // Conrod surface renderer
-        let mut renderer = Renderer::new(&display).expect("Failed to initialize user interface manager");
-
-        // Assets and resources manager
-        let resource_manager = ResourceManager::new(&display, &settings);
+        // Solid user interface shapes are tinted white pixels
+        canvas
+            .resource_manager
+            .textures_mut()
+            .upload(&display, white_texture(), vec![255; 4], (1, 1));
 
-        // Create default scene
-        let mut scene: Box<Scene> = Box::new(LoadingScene::new());
+        // User interface renderer
+        let mut renderer = UiRenderer::new();
 
-        info!("Starting script engine!");
+        // Create default scene and load its resources
+        let mut scenes = SceneStack::new(&mut canvas, Box::new(LoadingScene::new()));
 
-        // Rhai engine
-        let engine = Engine::new();
-
-        // Create canvas manager
-        let mut canvas = Canvas {
-            resource_manager,
-            settings,
-            display,
-            engine,
-            ui,
-        };
-
-        // Load initial scene resources
-        scene.load(&mut canvas);
+        let mut last_frame = Instant::now();
 
         // Main game loop
         while status != ControlFlow::Break {
+            canvas.pressed = None;
+
             // Check for events
             events_loop.poll_events(|events| {
                 use conrod::backend::winit::convert_event;
 
-                if let Some(event) = convert_event(events.clone(), &canvas.display) {
+                if let Some(event) = convert_event(events.clone(), &display) {
                     canvas.ui.handle_event(event);
                 }
 
+                // Scenes get input first, the game handles what they leave
+                if let Some(input) = canvas.input.translate(&events) {
+                    if !scenes.input(&mut canvas, &input) {
+                        canvas.input_handler(&input);
+                    }
+                }
+
                 if let Event::WindowEvent { event, .. } = events {
                     status = canvas.event_handler(&event);
                 }
             });
 
-            let mut target = canvas.display.draw();
+            let mut target = display.draw();
 
             // Tick resource manager
-            canvas.resource_manager.tick(&canvas.display);
-
-            // Draw user interface
-            if let Some(primitives) = canvas.ui.draw_if_changed() {
-                renderer.fill(
-                    &canvas.display,
-                    primitives,
-                    &canvas.resources().textures().image_map(),
-                );
+            canvas.resource_manager.tick(&display);
+
+            // Mix playing sounds
+            canvas.sound_manager.tick();
+
+            // Write settings once they stop changing
+            canvas.config.tick(&canvas.settings);
+
+            // Keybind text shows keys as they are bound now
+            canvas.resource_manager.set_controls(canvas.settings.controls());
+
+            // Reversed depth maps clip space depth from 0 to 1
+            let reversed_depth = canvas.reversed_depth();
+
+            if let Some(ref mut clip_control) = canvas.clip_control {
+                clip_control.set_zero_to_one(reversed_depth);
             }
 
             // Clear buffers
-            target.clear_color_and_depth((0.0, 0.0, 0.0, 0.0), 1.0);
+            target.clear_color_and_depth((0.0, 0.0, 0.0, 1.0), clear_depth(&canvas));
 
-            // Draw current scene
-            let draw = scene.draw(&mut canvas, &mut target);
+            // Run game ticks at a fixed rate, however long the last frame took
+            let now = Instant::now();
+            let ticks = canvas.timer.advance(now - last_frame);
+            canvas.debug.begin_frame(now - last_frame);
+            last_frame = now;
 
-            // Get scene action
-            match draw {
-                SceneAction::ChangeScene(new_scene) => {
-                    scene = new_scene;
-                    scene.load(&mut canvas);
-                },
-                SceneAction::Quit => status = ControlFlow::Break,
-                _ => (),
+            for _ in 0..ticks {
+                scenes.tick(&mut canvas);
+            }
+
+            // Draw open scenes and apply top scene action
+            let partial_tick = canvas.timer.partial_tick();
+
+            // Scenes draw on a list while they change canvas, it is drawn once they are done
+            let mut device = canvas.recording_device();
+
+            if scenes.draw(&mut canvas, &mut device, partial_tick) == ControlFlow::Break {
+                status = ControlFlow::Break;
+            }
+
+            GliumDevice::new(&mut target, &canvas)
+                .replay(device.calls())
+                .expect("Couldn't draw scenes");
+
+            // Blend scenes shown before and after a scene change, user interface is drawn over them.
+            // Outgoing scenes reuse the scenes list, incoming ones only need their own while blending
+            device.clear();
+
+            let mut to = if canvas.transitions().active() {
+                canvas.recording_device()
+            } else {
+                RecordingDevice::new()
+            };
+
+            if scenes.draw_transition(&mut canvas, &mut device, &mut to, partial_tick) {
+                draw_transition(&display, &mut canvas, &mut target, device.calls(), to.calls());
+            }
+
+            // Draw diagnostics over scenes
+            draw_overlay(&mut canvas, &mut target);
+
+            // Render user interface
+            let (width, height) = target.get_dimensions();
+
+            if let Some(glyphs) = renderer.cache_glyphs(&canvas.ui, width) {
+                canvas.resource_manager.textures_mut().upload(
+                    &display,
+                    glyph_texture(),
+                    glyphs,
+                    (GLYPH_CACHE_SIZE, GLYPH_CACHE_SIZE),
+                );
+            }
+
+            renderer.draw(
+                &mut GliumDevice::new(&mut target, &canvas),
+                &canvas.ui,
+                canvas.resources().textures(),
+                width,
+                height,
+            );
+
+            draw_toasts(&mut canvas, &mut target);
+
+            // High resolution screenshots draw scenes again on a bigger target
+            let mut screenshot = canvas.screenshots.take_request();
+
+            // Scenes may be gone once the game is closing
+            if status == ControlFlow::Break {
+                screenshot = None;
             }
 
-            // Render user interface surface
-            renderer
-                .draw(
-                    &canvas.display,
-                    &mut target,
-                    &canvas.resources().textures().image_map(),
-                )
-                .expect("Couldn't draw UI");
+            if screenshot == Some(true) {
+                match capture_high_res(&mut canvas, &mut scenes, partial_tick) {
+                    Ok(Some((data, width, height))) => {
+                        canvas.screenshots.save(data, width, height);
+                        screenshot = None;
+                    },
+                    Ok(None) => {
+                        info!("Current scene can't be drawn offscreen, taking normal screenshot")
+                    },
+                    Err(why) => warn!("Couldn't take high resolution screenshot: {}", why),
+                }
+            }
 
             // Draw to window
             target.finish().expect("Couldn't render scene");
+
+            // Read back the finished frame
+            if screenshot.is_some() {
+                match display.read_front_buffer::<RawImage2d<u8>>() {
+                    Ok(image) => {
+                        canvas
+                            .screenshots
+                            .save(image.data.into_owned(), image.width, image.height)
+                    },
+                    Err(why) => warn!("Couldn't read screen for screenshot: {:?}", why),
+                }
+            }
+
+            for result in canvas.screenshots.finished() {
+                match result {
+                    Ok(name) => {
+                        info!("Saved screenshot as {}", name);
+                        canvas.toasts.push("Screenshot", format!("Saved as {}", name));
+                    },
+                    Err(why) => {
+                        warn!("Couldn't save screenshot: {}", why);
+                        canvas.toasts.push("Screenshot", "Couldn't save screenshot");
+                    },
+                }
+            }
         }
 
         // Main loop end, now dispose resources...
         info!("Stopping Litecraft...");
+
+        canvas.sound_manager.stop_all();
+        canvas.config.flush(&canvas.settings);
+    }
+
+    /// Create canvas without a window, scenes can draw on a recording device but nothing is shown
+    pub fn headless(arguments: Arguments, settings: Settings) -> Canvas {
+        let config = ConfigSaver::new(arguments.config.clone());
+
+        Canvas::create(arguments, settings, config, None)
+    }
+
+    /// Create canvas drawing on a display if there is one
+    fn create(
+        arguments: Arguments,
+        settings: Settings,
+        config: ConfigSaver,
+        display: Option<Display>,
+    ) -> Canvas {
+        // Create UI Manager
+        let ui = UiBuilder::new([settings.width().into(), settings.height().into()]).build();
+
+        // Clip space depth range control, for reversed depth
+        let clip_control = display.as_ref().and_then(ClipControl::load);
+
+        if clip_control.is_none() && display.is_some() {
+            info!("glClipControl is not available, depth won't be reversed");
+        }
+
+        // Assets and resources manager
+        let resource_manager = match display {
+            Some(ref display) => ResourceManager::new(display, &settings),
+            None => ResourceManager::headless(&settings),
+        };
+
+        // Sound events and mixer
+        let sound_manager = SoundManager::new(&settings);
+
+        info!("Starting script engine!");
+
+        // Rhai engine
+        let engine = Engine::new();
+
+        Canvas {
+            resource_manager,
+            sound_manager,
+            settings,
+            config,
+            display,
+            clip_control,
+            engine,
+            ui,
+            pressed: None,
+            paused: false,
+            transitions: Transitions::new(),
+            input: InputState::new(),
+            cursor_grab: false,
+            focused: true,
+            timer: TickTimer::new(TICKS_PER_SECOND),
+            debug: DebugOverlay::new(),
+            screenshots: Screenshots::new(SCREENSHOTS_DIRECTORY),
+            toasts: Toasts::new(),
+            arguments,
+        }
     }
 
     /// Create a custom Window
     fn create_window(settings: &Settings, events_loop: &EventsLoop) -> WindowBuilder {
         use core::constants::{LITECRAFT_VERSION, MINECRAFT_VERSION};
+        use core::settings::MIN_WINDOW_SIZE;
         use glium::glutin::Icon;
 
         // If user wants fullscreen get primary monitor and attach Litecraft to it
@@ -181,7 +398,8 @@ impl Canvas {
 
         // Create or window
         WindowBuilder::new()
-            .with_min_dimensions((settings.width(), settings.height()).into())
+            .with_dimensions((settings.width(), settings.height()).into())
+            .with_min_dimensions(MIN_WINDOW_SIZE.into())
             .with_title(format!("Litecraft {} {}", MINECRAFT_VERSION, LITECRAFT_VERSION))
             .with_window_icon(icon)
             .with_maximized(settings.maximized())
@@ -200,6 +418,44 @@ impl Canvas {
                 ControlFlow::Continue
             },
 
+            WindowEvent::KeyboardInput {
+                input:
+                    KeyboardInput {
+                        state: ElementState::Pressed,
+                        virtual_keycode: Some(code),
+                        modifiers,
+                        ..
+                    },
+                ..
+            } => {
+                if let Some(key) = Key::from_glutin(*code) {
+                    let modifiers = Modifiers::from_glutin(*modifiers).without(key);
+                    self.pressed = Some(Binding::key(key).with_modifiers(modifiers));
+                }
+
+                ControlFlow::Continue
+            },
+
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button,
+                modifiers,
+                ..
+            } => {
+                let modifiers = Modifiers::from_glutin(*modifiers);
+                self.pressed = Some(Binding::mouse(*button).with_modifiers(modifiers));
+
+                ControlFlow::Continue
+            },
+
+            // Cursor is given back while the window is not focused
+            WindowEvent::Focused(focused) => {
+                self.focused = *focused;
+                self.apply_cursor_grab();
+
+                ControlFlow::Continue
+            },
+
             // TODO: Allow drop resourcepacks
             WindowEvent::DroppedFile(_) => ControlFlow::Continue,
 
@@ -207,21 +463,178 @@ impl Canvas {
         }
     }
 
+    /// Input events no scene consumed
+    fn input_handler(&mut self, event: &InputEvent) {
+        if let InputEvent::KeyDown {
+            key,
+            modifiers,
+            repeat: false,
+        } = *event
+        {
+            let actions = self.settings.controls().key_actions(key, modifiers);
+
+            if actions.contains(&Action::Fullscreen) {
+                let fullscreen = !self.settings.fullscreen();
+                self.set_fullscreen(fullscreen);
+            }
+
+            let controls = self.settings.controls();
+
+            // Holding Alt with the debug key shows the frame time graph instead
+            if actions.contains(&Action::DebugOverlay) {
+                if modifiers.alt && !controls.get(Action::DebugOverlay).modifiers.alt {
+                    let graph = !self.debug.graph();
+                    self.debug.set_graph(graph);
+                } else {
+                    let visible = !self.debug.visible();
+                    self.debug.set_visible(visible);
+                }
+            }
+
+            // Holding Shift with the screenshot key renders it bigger than the window
+            if actions.contains(&Action::Screenshot) {
+                let high_res = modifiers.shift && !controls.get(Action::Screenshot).modifiers.shift;
+                self.screenshots.request(high_res);
+            }
+        }
+    }
+
     /// Get resource manager
     pub fn resources(&self) -> &ResourceManager { &self.resource_manager }
 
     /// Get resource manager
     pub fn resources_mut(&mut self) -> &mut ResourceManager { &mut self.resource_manager }
 
-    /// Get display manager
-    pub fn display(&self) -> &Display { &self.display }
+    /// Get sound manager
+    pub fn sounds(&self) -> &SoundManager { &self.sound_manager }
+
+    /// Get sound manager
+    pub fn sounds_mut(&mut self) -> &mut SoundManager { &mut self.sound_manager }
+
+    /// Get command line arguments, like username or server to join
+    pub fn arguments(&self) -> &Arguments { &self.arguments }
+
+    /// Get display manager, headless canvas has none
+    pub fn display(&self) -> &Display { self.display.as_ref().expect("Canvas has no display") }
+
+    /// Get display manager if canvas has one
+    pub fn try_display(&self) -> Option<&Display> { self.display.as_ref() }
+
+    /// Get an empty recording device knowing loaded textures and depth range, scenes draw on it
+    pub fn recording_device(&self) -> RecordingDevice {
+        let mut device = RecordingDevice::new();
+        device.set_reversed_depth(self.reversed_depth());
+
+        for texture in self.resource_manager.textures().resources() {
+            device.add_texture(texture.clone());
+        }
+
+        device
+    }
+
+    /// Check if depth is reversed, the driver must be able to map clip space depth from 0 to 1
+    pub fn reversed_depth(&self) -> bool {
+        self.settings.reversed_depth() && self.clip_control.is_some()
+    }
 
     /// Get settings
     pub fn settings(&self) -> &Settings { &self.settings }
 
+    /// Get settings to change them, they will be saved once they stop changing
+    pub fn settings_mut(&mut self) -> &mut Settings { &mut self.settings }
+
+    /// Write settings now instead of waiting for them to stop changing
+    pub fn save_settings(&mut self) {
+        self.config.changed();
+        self.config.flush(&self.settings);
+    }
+
+    /// Enter or leave fullscreen and remember it
+    pub fn set_fullscreen(&mut self, fullscreen: bool) {
+        if let Some(ref display) = self.display {
+            let window = display.gl_window();
+
+            let monitor = if fullscreen {
+                Some(window.get_current_monitor())
+            } else {
+                None
+            };
+
+            window.set_fullscreen(monitor);
+        }
+
+        self.settings_mut().set_fullscreen(fullscreen);
+    }
+
+    /// Get game ticks run since start
+    pub fn ticks(&self) -> u64 { self.timer.ticks() }
+
+    /// Get progress towards next game tick, from 0 to 1
+    pub fn partial_tick(&self) -> f32 { self.timer.partial_tick() }
+
+    /// Get debug overlay, scenes report what it shows every frame
+    pub fn debug(&self) -> &DebugOverlay { &self.debug }
+
+    /// Get debug overlay, scenes report what it shows every frame
+    pub fn debug_mut(&mut self) -> &mut DebugOverlay { &mut self.debug }
+
+    /// Get messages shown at the top right corner
+    pub fn toasts(&self) -> &Toasts { &self.toasts }
+
+    /// Get messages shown at the top right corner
+    pub fn toasts_mut(&mut self) -> &mut Toasts { &mut self.toasts }
+
+    /// Get held keys
+    pub fn input(&self) -> &InputState { &self.input }
+
+    /// Get if cursor is grabbed right now, it is given back while the window is not focused
+    pub fn cursor_grabbed(&self) -> bool { self.cursor_grab && self.focused }
+
+    /// Grab and hide cursor so raw mouse motion can turn the camera, or give it back.
+    /// Scenes grabbing it should give it back when they exit
+    pub fn set_cursor_grab(&mut self, grab: bool) {
+        self.cursor_grab = grab;
+        self.apply_cursor_grab();
+    }
+
+    /// Make window cursor match grab state
+    fn apply_cursor_grab(&self) {
+        let grab = self.cursor_grabbed();
+        let window = match self.display {
+            Some(ref display) => display.gl_window(),
+            None => return,
+        };
+
+        if let Err(why) = window.grab_cursor(grab) {
+            warn!("Failed to grab cursor: {}", why);
+        }
+
+        window.hide_cursor(grab);
+    }
+
+    /// Get if a scene above the one being drawn pauses the game
+    pub fn paused(&self) -> bool { self.paused }
+
+    /// Set if the scene being drawn is paused, used by the scene stack
+    pub fn set_paused(&mut self, paused: bool) { self.paused = paused }
+
+    /// Get scene transitions player
+    pub fn transitions(&self) -> &Transitions { &self.transitions }
+
+    /// Get scene transitions player
+    pub fn transitions_mut(&mut self) -> &mut Transitions { &mut self.transitions }
+
+    /// Get key or mouse button pressed since last frame, used to rebind controls
+    pub fn pressed(&self) -> Option<Binding> { self.pressed }
+
     /// Get user interface manager
     pub fn ui(&self) -> &Ui { &self.ui }
 
     /// Get user interface manager
     pub fn ui_mut(&mut self) -> &mut Ui { &mut self.ui }
+
+    /// Get user interface manager along with resource manager
+    pub fn ui_with_resources(&mut self) -> (&mut Ui, &mut ResourceManager) {
+        (&mut self.ui, &mut self.resource_manager)
+    }
 }
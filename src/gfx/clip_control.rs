@@ -0,0 +1,67 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use glium::glutin::GlContext;
+use glium::{Api, Display, Version};
+
+use std::mem;
+
+const GL_LOWER_LEFT: u32 = 0x8CA1;
+const GL_NEGATIVE_ONE_TO_ONE: u32 = 0x935E;
+const GL_ZERO_TO_ONE: u32 = 0x935F;
+
+/// `glClipControl`, which glium doesn't expose. It is core since OpenGL 4.5
+pub struct ClipControl {
+    function: unsafe extern "system" fn(u32, u32),
+    zero_to_one: bool,
+}
+
+impl ClipControl {
+    /// Load `glClipControl` from the display context, none if the driver doesn't have it
+    pub fn load(display: &Display) -> Option<ClipControl> {
+        if *display.get_opengl_version() < Version(Api::Gl, 4, 5) {
+            return None;
+        }
+
+        let address = display.gl_window().get_proc_address("glClipControl");
+
+        if address.is_null() {
+            return None;
+        }
+
+        Some(ClipControl {
+            function: unsafe { mem::transmute(address) },
+            zero_to_one: false,
+        })
+    }
+
+    /// Map clip space depth from 0 to 1 instead of OpenGL's default -1 to 1.
+    /// Reversed depth needs it to keep float precision far away
+    pub fn set_zero_to_one(&mut self, zero_to_one: bool) {
+        if self.zero_to_one == zero_to_one {
+            return;
+        }
+
+        let depth = if zero_to_one {
+            GL_ZERO_TO_ONE
+        } else {
+            GL_NEGATIVE_ONE_TO_ONE
+        };
+
+        unsafe { (self.function)(GL_LOWER_LEFT, depth) };
+
+        self.zero_to_one = zero_to_one;
+    }
+}
@@ -0,0 +1,388 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::camera::Camera;
+use core::constants::{LITECRAFT_VERSION, MINECRAFT_VERSION};
+use core::raycast::RayHit;
+use core::resource_manager::font_manager::{FontManager, LINE_HEIGHT};
+
+use gfx::canvas::Canvas;
+use gfx::text::{screen_quads, solid_quad, TextMesh, TextQuad, GUI_SCALE};
+
+use glium::Surface;
+
+use std::collections::VecDeque;
+use std::f32;
+use std::fs;
+use std::time::Duration;
+
+/// Frames kept for statistics and the graph
+const FRAME_HISTORY: usize = 240;
+
+/// Line background, same as vanilla
+const BACKGROUND: [f32; 4] = [0.314, 0.314, 0.314, 0.565];
+
+const TEXT_COLOR: [f32; 4] = [0.878, 0.878, 0.878, 1.0];
+
+/// Frame graph bar height in font units for each millisecond, and tallest bar
+const GRAPH_SCALE: f32 = 1.0;
+const GRAPH_HEIGHT: f32 = 60.0;
+
+/// Frame times for 60 and 30 frames per second, bars are colored by the one they reach
+const GRAPH_LIMITS: [f32; 2] = [1000.0 / 60.0, 1000.0 / 30.0];
+const GRAPH_COLORS: [[f32; 4]; 3] = [
+    [0.0, 1.0, 0.0, 0.75],
+    [1.0, 1.0, 0.0, 0.75],
+    [1.0, 0.0, 0.0, 0.75],
+];
+
+fn millis(duration: Duration) -> f32 {
+    duration.as_secs() as f32 * 1000.0 + duration.subsec_nanos() as f32 / 1_000_000.0
+}
+
+/// Durations of the last frames, newest last
+pub struct FrameTimes {
+    times: VecDeque<Duration>,
+}
+
+impl FrameTimes {
+    pub fn new() -> FrameTimes {
+        FrameTimes {
+            times: VecDeque::with_capacity(FRAME_HISTORY),
+        }
+    }
+
+    /// Record how long a frame took
+    pub fn push(&mut self, frame: Duration) {
+        if self.times.len() == FRAME_HISTORY {
+            self.times.pop_front();
+        }
+
+        self.times.push_back(frame);
+    }
+
+    /// Get recorded frame durations, newest last
+    pub fn times(&self) -> &VecDeque<Duration> { &self.times }
+
+    /// Get frames drawn during the last second
+    pub fn fps(&self) -> u32 {
+        let mut elapsed = Duration::from_secs(0);
+        let mut frames = 0;
+
+        for &time in self.times.iter().rev() {
+            if elapsed >= Duration::from_secs(1) {
+                break;
+            }
+
+            elapsed += time;
+            frames += 1;
+        }
+
+        frames
+    }
+
+    /// Get shortest, average and longest frame in milliseconds
+    pub fn stats(&self) -> Option<(f32, f32, f32)> {
+        if self.times.is_empty() {
+            return None;
+        }
+
+        let times = self.times.iter().map(|&time| millis(time));
+        let (min, sum, max) = times.fold((f32::INFINITY, 0.0, 0.0), |(min, sum, max), time| {
+            (time.min(min), sum + time, time.max(max))
+        });
+
+        Some((min, sum / self.times.len() as f32, max))
+    }
+}
+
+/// Game state shown on the debug overlay, scenes report it again every frame
+#[derive(Clone, Debug, Default)]
+pub struct DebugInfo {
+    pub camera: Option<Camera>,
+    /// Targeted block and its state, like `minecraft:stone`
+    pub target: Option<(RayHit, String)>,
+    pub chunks: Option<usize>,
+    pub entities: Option<usize>,
+}
+
+/// Diagnostics shown with F3, Alt+F3 adds a frame time graph
+pub struct DebugOverlay {
+    visible: bool,
+    graph: bool,
+    frames: FrameTimes,
+    info: DebugInfo,
+}
+
+impl DebugOverlay {
+    pub fn new() -> DebugOverlay {
+        DebugOverlay {
+            visible: false,
+            graph: false,
+            frames: FrameTimes::new(),
+            info: DebugInfo::default(),
+        }
+    }
+
+    /// Check if overlay is shown
+    pub fn visible(&self) -> bool { self.visible }
+
+    /// Show or hide overlay
+    pub fn set_visible(&mut self, visible: bool) { self.visible = visible }
+
+    /// Check if frame time graph is shown with the overlay
+    pub fn graph(&self) -> bool { self.graph }
+
+    /// Show or hide frame time graph, showing the overlay if needed
+    pub fn set_graph(&mut self, graph: bool) {
+        self.graph = graph;
+        self.visible |= graph;
+    }
+
+    /// Get last frame times
+    pub fn frames(&self) -> &FrameTimes { &self.frames }
+
+    /// Get game state reported by scenes this frame
+    pub fn info(&self) -> &DebugInfo { &self.info }
+
+    /// Get game state to report it
+    pub fn info_mut(&mut self) -> &mut DebugInfo { &mut self.info }
+
+    /// Record last frame time and forget what scenes reported for it
+    pub fn begin_frame(&mut self, elapsed: Duration) {
+        self.frames.push(elapsed);
+        self.info = DebugInfo::default();
+    }
+
+    /// Game lines, shown on the left
+    fn game_lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("Litecraft {} ({})", MINECRAFT_VERSION, LITECRAFT_VERSION)];
+
+        if let Some((min, average, max)) = self.frames.stats() {
+            lines.push(format!(
+                "{} fps, {:.1} ms (min {:.1}, max {:.1})",
+                self.frames.fps(),
+                average,
+                min,
+                max
+            ));
+        }
+
+        if let Some(ref camera) = self.info.camera {
+            let position = camera.position();
+            let front = camera.front();
+
+            let (direction, towards) = if front.x.abs() > front.z.abs() {
+                if front.x > 0.0 {
+                    ("east", "positive X")
+                } else {
+                    ("west", "negative X")
+                }
+            } else if front.z > 0.0 {
+                ("south", "positive Z")
+            } else {
+                ("north", "negative Z")
+            };
+
+            lines.push(String::new());
+            lines.push(format!(
+                "XYZ: {:.3} / {:.5} / {:.3}",
+                position.x, position.y, position.z
+            ));
+            lines.push(format!(
+                "Block: {} {} {}",
+                position.x.floor(),
+                position.y.floor(),
+                position.z.floor()
+            ));
+            lines.push(format!(
+                "Facing: {} (Towards {}) ({:.1} / {:.1})",
+                direction,
+                towards,
+                camera.yaw(),
+                camera.pitch()
+            ));
+        }
+
+        if let Some(chunks) = self.info.chunks {
+            lines.push(format!("Chunks: {}", chunks));
+        }
+
+        if let Some(entities) = self.info.entities {
+            lines.push(format!("Entities: {}", entities));
+        }
+
+        if let Some((ref hit, ref state)) = self.info.target {
+            lines.push(String::new());
+            lines.push(format!(
+                "Targeted Block: {} {} {}",
+                hit.block.x, hit.block.y, hit.block.z
+            ));
+            lines.push(state.clone());
+            lines.push(format!("Face: {}", hit.face.name()));
+        }
+
+        lines
+    }
+}
+
+/// Get resident memory in bytes, where the system tells it
+fn memory_usage() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kilobytes: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+
+    Some(kilobytes * 1024)
+}
+
+/// System lines, shown on the right
+fn system_lines(canvas: &Canvas) -> Vec<String> {
+    let memory = match memory_usage() {
+        Some(bytes) => format!("Mem: {} MB", bytes / 1024 / 1024),
+        None => "Mem: unknown".to_owned(),
+    };
+
+    let mut lines = vec![
+        memory,
+        String::new(),
+        format!(
+            "Display: {}x{}",
+            canvas.settings().width(),
+            canvas.settings().height()
+        ),
+        canvas.display().get_opengl_renderer_string().to_owned(),
+        canvas.display().get_opengl_version_string().to_owned(),
+        String::new(),
+        format!(
+            "Textures: {}, Shaders: {}",
+            canvas.resources().textures().count(),
+            canvas.resources().shaders().count()
+        ),
+    ];
+
+    if !canvas.settings().resourcepacks().is_empty() {
+        lines.push(String::new());
+        lines.push("Resource packs:".to_owned());
+        lines.extend(canvas.settings().resourcepacks().iter().cloned());
+    }
+
+    lines
+}
+
+/// Add lines with a background each, right aligned lines end at `x`
+fn text_lines(
+    quads: &mut Vec<TextQuad>,
+    fonts: &mut FontManager,
+    lines: &[String],
+    x: f32,
+    right: bool,
+) {
+    for (i, line) in lines.iter().enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+
+        let mesh = TextMesh::plain(fonts, line);
+        let width = mesh.size()[0];
+
+        let left = if right { x - width } else { x };
+        let top = 2.0 + i as f32 * LINE_HEIGHT;
+
+        quads.push(solid_quad(
+            fonts,
+            [left - 1.0, top - 1.0, width + 1.0, LINE_HEIGHT],
+            BACKGROUND,
+        ));
+
+        quads.extend(mesh.quads().iter().map(|quad| TextQuad {
+            position: [quad.position[0] + left, quad.position[1] + top],
+            color: [
+                quad.color[0] * TEXT_COLOR[0],
+                quad.color[1] * TEXT_COLOR[1],
+                quad.color[2] * TEXT_COLOR[2],
+                quad.color[3] * TEXT_COLOR[3],
+            ],
+            ..*quad
+        }));
+    }
+}
+
+/// Add a bar for every recorded frame at the bottom left corner
+fn frame_graph(quads: &mut Vec<TextQuad>, fonts: &mut FontManager, frames: &[Duration], bottom: f32) {
+    let count = frames.len() as f32;
+
+    quads.push(solid_quad(
+        fonts,
+        [0.0, bottom - GRAPH_HEIGHT, count, GRAPH_HEIGHT],
+        [0.0, 0.0, 0.0, 0.5],
+    ));
+
+    for (i, &time) in frames.iter().enumerate() {
+        let time = millis(time);
+        let height = (time * GRAPH_SCALE).min(GRAPH_HEIGHT);
+
+        let color = if time <= GRAPH_LIMITS[0] {
+            GRAPH_COLORS[0]
+        } else if time <= GRAPH_LIMITS[1] {
+            GRAPH_COLORS[1]
+        } else {
+            GRAPH_COLORS[2]
+        };
+
+        quads.push(solid_quad(fonts, [i as f32, bottom - height, 1.0, height], color));
+    }
+
+    for &limit in &GRAPH_LIMITS {
+        let y = bottom - limit * GRAPH_SCALE;
+        quads.push(solid_quad(fonts, [0.0, y, count, 0.5], [1.0, 1.0, 1.0, 0.5]));
+    }
+}
+
+/// Draw debug overlay over everything on screen if it is visible
+pub fn draw_overlay<S>(canvas: &mut Canvas, frame: &mut S)
+where
+    S: Surface,
+{
+    if !canvas.debug().visible() {
+        return;
+    }
+
+    let (width, height) = frame.get_dimensions();
+    let scale = (GUI_SCALE * canvas.settings().scale()) as f32;
+
+    // Screen size in font units
+    let (units_x, units_y) = (width as f32 / scale, height as f32 / scale);
+
+    let left = canvas.debug().game_lines();
+    let right = system_lines(canvas);
+
+    let mut quads = Vec::new();
+
+    {
+        let fonts = canvas.resources_mut().fonts_mut();
+
+        text_lines(&mut quads, fonts, &left, 2.0, false);
+        text_lines(&mut quads, fonts, &right, units_x - 2.0, true);
+    }
+
+    if canvas.debug().graph() {
+        let frames: Vec<Duration> = canvas.debug().frames().times().iter().cloned().collect();
+        let fonts = canvas.resources_mut().fonts_mut();
+
+        frame_graph(&mut quads, fonts, &frames, units_y);
+    }
+
+    screen_quads(frame, canvas, &quads, scale);
+}
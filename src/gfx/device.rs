@@ -0,0 +1,382 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::resource_manager::resource::Resource;
+use core::resource_manager::texture_manager::Texture;
+
+use gfx::canvas::Canvas;
+use gfx::pencil::draw_parameters;
+use gfx::shapes::{SpriteVertex, Vertex2D, Vertex3D};
+
+use glium::draw_parameters::Blend;
+use glium::index::{NoIndices, PrimitiveType};
+use glium::uniforms::{
+    MagnifySamplerFilter, SamplerBehavior, SamplerWrapFunction, UniformValue, Uniforms,
+};
+use glium::{DrawParameters, IndexBuffer, Rect, Surface, VertexBuffer};
+
+use std::collections::HashSet;
+use std::error::Error;
+
+/// Value given to a shader uniform
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Uniform {
+    Float(f32),
+    Vec2([f32; 2]),
+    Vec4([f32; 4]),
+    Mat4([[f32; 4]; 4]),
+}
+
+/// Vertices drawn by a draw call
+#[derive(Clone, Copy)]
+pub enum Geometry<'a> {
+    /// Shared quad from -1 to 1
+    Quad,
+    /// Shared quad twice as wide as it is tall
+    Rectangle,
+    /// Triangle list built for this call
+    Triangles(&'a [Vertex2D], &'a [u16]),
+    /// Triangle list of tinted sprites built for this call
+    Sprites(&'a [SpriteVertex], &'a [u16]),
+    /// Line list on 3D space and line width in pixels
+    Lines(&'a [Vertex3D], f32),
+}
+
+/// How geometry is blended with what is already drawn
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DrawMode {
+    /// Depth tested and culled on 3D space
+    World,
+    /// Drawn over everything in the order it comes
+    Overlay,
+}
+
+/// Everything needed to draw some geometry with a shader program
+#[derive(Clone, Copy)]
+pub struct DrawCall<'a> {
+    pub program: &'a str,
+    pub geometry: Geometry<'a>,
+    pub uniforms: &'a [(&'static str, Uniform)],
+    /// Texture bound as `tex`, with its size as `resolution`
+    pub texture: Option<&'a Resource>,
+    /// Sample texture with linear filtering instead of nearest
+    pub linear: bool,
+    pub mode: DrawMode,
+    /// Only draw inside a rectangle in pixels: left, bottom, width and height
+    pub scissor: Option<[u32; 4]>,
+}
+
+/// Something draw calls are sent to, like the screen or a log of them
+pub trait RenderDevice {
+    /// Check if a texture is loaded and can be drawn
+    fn has_texture(&self, texture: &Resource) -> bool;
+
+    /// Check if depth is reversed, near plane at 1 and infinity at 0
+    fn reversed_depth(&self) -> bool;
+
+    /// Draw geometry, failing if its shader program or texture is not loaded
+    fn draw(&mut self, call: &DrawCall) -> Result<(), Box<Error>>;
+}
+
+/// Draws on a glium surface with resources loaded by canvas
+pub struct GliumDevice<'a, S: 'a> {
+    surface: &'a mut S,
+    canvas: &'a Canvas,
+}
+
+impl<'a, S> GliumDevice<'a, S>
+where
+    S: Surface,
+{
+    pub fn new(surface: &'a mut S, canvas: &'a Canvas) -> GliumDevice<'a, S> {
+        GliumDevice { surface, canvas }
+    }
+
+    /// Draw calls kept by a recording device, in the order they were made
+    pub fn replay(&mut self, calls: &[RecordedCall]) -> Result<(), Box<Error>> {
+        for call in calls {
+            self.draw(&call.draw_call())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Uniforms of a draw call with its texture
+struct CallUniforms<'a> {
+    values: &'a [(&'static str, Uniform)],
+    texture: Option<(&'a Texture, SamplerBehavior)>,
+}
+
+impl<'a> Uniforms for CallUniforms<'a> {
+    fn visit_values<'b, F>(&'b self, mut visit: F)
+    where
+        F: FnMut(&str, UniformValue<'b>),
+    {
+        for &(name, value) in self.values {
+            match value {
+                Uniform::Float(value) => visit(name, UniformValue::Float(value)),
+                Uniform::Vec2(value) => visit(name, UniformValue::Vec2(value)),
+                Uniform::Vec4(value) => visit(name, UniformValue::Vec4(value)),
+                Uniform::Mat4(value) => visit(name, UniformValue::Mat4(value)),
+            }
+        }
+
+        if let Some((texture, sampler)) = self.texture {
+            let (width, height) = texture.dimensions();
+
+            visit("tex", texture.uniform(sampler));
+            visit("resolution", UniformValue::Vec2([width as f32, height as f32]));
+        }
+    }
+}
+
+impl<'a, S> RenderDevice for GliumDevice<'a, S>
+where
+    S: Surface,
+{
+    fn has_texture(&self, texture: &Resource) -> bool {
+        self.canvas.resources().textures().get(texture).is_some()
+    }
+
+    fn reversed_depth(&self) -> bool { self.canvas.reversed_depth() }
+
+    fn draw(&mut self, call: &DrawCall) -> Result<(), Box<Error>> {
+        let resources = self.canvas.resources();
+
+        let program = resources
+            .shaders()
+            .get(call.program)
+            .ok_or_else(|| format!("Shader {} not found", call.program))?;
+
+        let texture = match call.texture {
+            Some(resource) => {
+                let texture = resources
+                    .textures()
+                    .get(resource)
+                    .ok_or_else(|| format!("Texture {} not loaded", resource))?;
+
+                let magnify_filter = if call.linear {
+                    MagnifySamplerFilter::Linear
+                } else {
+                    MagnifySamplerFilter::Nearest
+                };
+
+                let sampler = SamplerBehavior {
+                    wrap_function: (
+                        SamplerWrapFunction::BorderClamp,
+                        SamplerWrapFunction::BorderClamp,
+                        SamplerWrapFunction::BorderClamp,
+                    ),
+                    magnify_filter,
+                    ..Default::default()
+                };
+
+                Some((texture, sampler))
+            },
+            None => None,
+        };
+
+        let uniforms = CallUniforms {
+            values: call.uniforms,
+            texture,
+        };
+
+        let mut parameters = match call.mode {
+            DrawMode::World => draw_parameters(self.canvas),
+            DrawMode::Overlay => DrawParameters {
+                blend: Blend::alpha_blending(),
+                ..Default::default()
+            },
+        };
+
+        parameters.scissor = call.scissor.map(|[left, bottom, width, height]| Rect {
+            left,
+            bottom,
+            width,
+            height,
+        });
+
+        match call.geometry {
+            Geometry::Quad | Geometry::Rectangle => {
+                let shapes = resources.shapes().ok_or("Vertex data is not created")?;
+                let (vertices, indices) = match call.geometry {
+                    Geometry::Quad => shapes.quad(),
+                    _ => shapes.rectangle(),
+                };
+
+                self.surface
+                    .draw(vertices, indices, program, &uniforms, &parameters)?;
+            },
+            Geometry::Triangles(vertices, indices) => {
+                let display = self.canvas.display();
+
+                let vertices = VertexBuffer::new(display, vertices)?;
+                let indices = IndexBuffer::new(display, PrimitiveType::TrianglesList, indices)?;
+
+                self.surface
+                    .draw(&vertices, &indices, program, &uniforms, &parameters)?;
+            },
+            Geometry::Sprites(vertices, indices) => {
+                let display = self.canvas.display();
+
+                let vertices = VertexBuffer::new(display, vertices)?;
+                let indices = IndexBuffer::new(display, PrimitiveType::TrianglesList, indices)?;
+
+                self.surface
+                    .draw(&vertices, &indices, program, &uniforms, &parameters)?;
+            },
+            Geometry::Lines(vertices, width) => {
+                let vertices = VertexBuffer::new(self.canvas.display(), vertices)?;
+                parameters.line_width = Some(width);
+
+                self.surface.draw(
+                    &vertices,
+                    NoIndices(PrimitiveType::LinesList),
+                    program,
+                    &uniforms,
+                    &parameters,
+                )?;
+            },
+        }
+
+        Ok(())
+    }
+}
+
+/// Geometry of a recorded draw call, kept to draw it later
+#[derive(Clone, Debug, PartialEq)]
+pub enum RecordedGeometry {
+    Quad,
+    Rectangle,
+    Triangles(Vec<Vertex2D>, Vec<u16>),
+    Sprites(Vec<SpriteVertex>, Vec<u16>),
+    Lines(Vec<Vertex3D>, f32),
+}
+
+/// Draw call kept by a recording device
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordedCall {
+    pub program: String,
+    pub geometry: RecordedGeometry,
+    pub uniforms: Vec<(&'static str, Uniform)>,
+    pub texture: Option<Resource>,
+    pub linear: bool,
+    pub mode: DrawMode,
+    pub scissor: Option<[u32; 4]>,
+}
+
+impl RecordedCall {
+    /// Get value given to a uniform
+    pub fn uniform(&self, name: &str) -> Option<Uniform> {
+        self.uniforms
+            .iter()
+            .find(|&&(uniform, _)| uniform == name)
+            .map(|&(_, value)| value)
+    }
+
+    /// Get draw call to make it again on another device
+    pub fn draw_call(&self) -> DrawCall {
+        let geometry = match self.geometry {
+            RecordedGeometry::Quad => Geometry::Quad,
+            RecordedGeometry::Rectangle => Geometry::Rectangle,
+            RecordedGeometry::Triangles(ref vertices, ref indices) => {
+                Geometry::Triangles(vertices, indices)
+            },
+            RecordedGeometry::Sprites(ref vertices, ref indices) => Geometry::Sprites(vertices, indices),
+            RecordedGeometry::Lines(ref vertices, width) => Geometry::Lines(vertices, width),
+        };
+
+        DrawCall {
+            program: &self.program,
+            geometry,
+            uniforms: &self.uniforms,
+            texture: self.texture.as_ref(),
+            linear: self.linear,
+            mode: self.mode,
+            scissor: self.scissor,
+        }
+    }
+}
+
+/// Keeps draw calls instead of drawing them, so they can be drawn later or checked without a display
+pub struct RecordingDevice {
+    calls: Vec<RecordedCall>,
+    textures: HashSet<Resource>,
+    reversed_depth: bool,
+}
+
+impl RecordingDevice {
+    pub fn new() -> RecordingDevice {
+        RecordingDevice {
+            calls: Vec::new(),
+            textures: HashSet::new(),
+            reversed_depth: false,
+        }
+    }
+
+    /// Pretend a texture is loaded
+    pub fn add_texture(&mut self, texture: Resource) { self.textures.insert(texture); }
+
+    /// Set if depth is reversed
+    pub fn set_reversed_depth(&mut self, reversed: bool) { self.reversed_depth = reversed }
+
+    /// Get draw calls in the order they were made
+    pub fn calls(&self) -> &[RecordedCall] { &self.calls }
+
+    /// Get shader programs used, in the order they were used
+    pub fn programs(&self) -> Vec<&str> { self.calls.iter().map(|call| call.program.as_str()).collect() }
+
+    /// Forget recorded draw calls
+    pub fn clear(&mut self) { self.calls.clear() }
+}
+
+impl RenderDevice for RecordingDevice {
+    fn has_texture(&self, texture: &Resource) -> bool { self.textures.contains(texture) }
+
+    fn reversed_depth(&self) -> bool { self.reversed_depth }
+
+    fn draw(&mut self, call: &DrawCall) -> Result<(), Box<Error>> {
+        if let Some(texture) = call.texture {
+            if !self.has_texture(texture) {
+                return Err(format!("Texture {} not loaded", texture).into());
+            }
+        }
+
+        let geometry = match call.geometry {
+            Geometry::Quad => RecordedGeometry::Quad,
+            Geometry::Rectangle => RecordedGeometry::Rectangle,
+            Geometry::Triangles(vertices, indices) => {
+                RecordedGeometry::Triangles(vertices.to_vec(), indices.to_vec())
+            },
+            Geometry::Sprites(vertices, indices) => {
+                RecordedGeometry::Sprites(vertices.to_vec(), indices.to_vec())
+            },
+            Geometry::Lines(vertices, width) => RecordedGeometry::Lines(vertices.to_vec(), width),
+        };
+
+        self.calls.push(RecordedCall {
+            program: call.program.to_owned(),
+            geometry,
+            uniforms: call.uniforms.to_vec(),
+            texture: call.texture.cloned(),
+            linear: call.linear,
+            mode: call.mode,
+            scissor: call.scissor,
+        });
+
+        Ok(())
+    }
+}
@@ -14,7 +14,17 @@
 // IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 pub mod canvas;
+pub mod clip_control;
+pub mod debug;
+pub mod device;
+pub mod outline;
 pub mod pencil;
 pub mod scene;
+pub mod screenshot;
 pub mod shapes;
+pub mod sprites;
+pub mod text;
+pub mod toast;
+pub mod transition;
 pub mod ui_helper;
+pub mod ui_renderer;
@@ -0,0 +1,66 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::camera::Camera;
+use core::raycast::{BlockView, RayHit, ShapeKind};
+
+use gfx::device::RenderDevice;
+use gfx::pencil::Pencil;
+use gfx::shapes::Vertex3D;
+
+use cgmath::prelude::*;
+
+/// Vanilla outline color, translucent black
+const OUTLINE_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 0.4];
+
+/// Outline is slightly bigger than its block so faces don't hide it
+const OUTLINE_OFFSET: f32 = 0.002;
+
+/// Outline width in pixels
+const OUTLINE_WIDTH: f32 = 2.0;
+
+/// Draw black lines around the outline shape of a targeted block
+pub fn draw_selection<W>(device: &mut RenderDevice, camera: &Camera, world: &W, hit: &RayHit)
+where
+    W: BlockView,
+{
+    let shape = world.shape(hit.block, ShapeKind::Outline);
+    let offset = hit
+        .block
+        .cast::<f32>()
+        .map_or_else(Zero::zero, |block| block.to_vec());
+
+    let vertices: Vec<Vertex3D> = shape
+        .boxes()
+        .iter()
+        .flat_map(|bounds| bounds.offset(offset).inflate(OUTLINE_OFFSET).edges())
+        .flat_map(|(a, b)| {
+            vec![
+                Vertex3D::new(a.into(), [0.0; 2], 0),
+                Vertex3D::new(b.into(), [0.0; 2], 0),
+            ]
+        })
+        .collect();
+
+    if vertices.is_empty() {
+        return;
+    }
+
+    Pencil::new(device, "outline")
+        .lines(&vertices, OUTLINE_WIDTH)
+        .camera(camera)
+        .color(OUTLINE_COLOR)
+        .draw();
+}
@@ -18,153 +18,173 @@ use cgmath::{Matrix4, One};
 use std::borrow::Cow;
 
 use core::camera::Camera;
+use core::resource_manager::resource::Resource;
 use core::resource_manager::ResourceManager;
 
 use gfx::canvas::Canvas;
-use gfx::shapes::VertexData2D;
+use gfx::device::{DrawCall, DrawMode, Geometry, RenderDevice, Uniform};
+use gfx::shapes::{Vertex2D, Vertex3D};
 
 use glium::draw_parameters::Blend;
-use glium::texture::CompressedSrgbTexture2d;
-use glium::{BackfaceCullingMode, DrawParameters, Surface};
+use glium::{BackfaceCullingMode, DrawParameters};
 
 /// Utility for drawing on screen
-pub struct Pencil<'a, S> {
+pub struct Pencil<'a> {
     // Shader program
     program: Cow<'a, str>,
     linear: bool,
 
-    frame: &'a mut S,
+    device: &'a mut RenderDevice,
 
     // Uniforms
     persp_matrix: Option<[[f32; 4]; 4]>,
     view_matrix: Option<[[f32; 4]; 4]>,
     transform: Option<[[f32; 4]; 4]>,
+    color: Option<[f32; 4]>,
 
     // Shape vertices
-    vertices: &'a VertexData2D,
+    geometry: Geometry<'a>,
 
-    texture: Option<&'a CompressedSrgbTexture2d>,
-
-    canvas: &'a Canvas,
+    texture: Option<&'a Resource>,
 }
 
-impl<'a, S> Pencil<'a, S>
-where
-    S: Surface,
-{
-    /// Create a new Pencil
-    pub fn new<T>(frame: &'a mut S, program: T, canvas: &'a Canvas) -> Pencil<'a, S>
+impl<'a> Pencil<'a> {
+    /// Create a new Pencil drawing a quad
+    pub fn new<T>(device: &'a mut RenderDevice, program: T) -> Pencil<'a>
     where
         T: Into<Cow<'a, str>>,
     {
         Pencil {
             program: program.into(),
-            vertices: canvas.resources().shapes().quad(),
+            geometry: Geometry::Quad,
 
             linear: false,
 
             view_matrix: None,
             persp_matrix: None,
             transform: None,
+            color: None,
 
             texture: None,
 
-            canvas,
-            frame,
+            device,
         }
     }
 
-    /// Add vertices to draw
-    pub fn vertices(&'a mut self, vertices: &'a VertexData2D) -> &'a mut Pencil<S> {
-        self.vertices = vertices;
+    /// Add vertices to draw as a triangle list
+    pub fn vertices(&mut self, vertices: &'a [Vertex2D], indices: &'a [u16]) -> &mut Pencil<'a> {
+        self.geometry = Geometry::Triangles(vertices, indices);
+        self
+    }
+
+    /// Add vertices to draw as a line list with a width in pixels
+    pub fn lines(&mut self, vertices: &'a [Vertex3D], width: f32) -> &mut Pencil<'a> {
+        self.geometry = Geometry::Lines(vertices, width);
         self
     }
 
     /// Set if rendering should be linear
-    pub fn linear(&'a mut self, linear: bool) -> &'a mut Pencil<S> {
+    pub fn linear(&mut self, linear: bool) -> &mut Pencil<'a> {
         self.linear = linear;
         self
     }
 
-    /// Add texture to draw
-    pub fn texture(&'a mut self, texture: &'a CompressedSrgbTexture2d) -> &'a mut Pencil<S> {
+    /// Add texture to draw, it must be loaded
+    pub fn texture(&mut self, texture: &'a Resource) -> &mut Pencil<'a> {
         self.texture = Some(texture);
         self
     }
 
     /// Add camera to draw
-    pub fn camera(&'a mut self, camera: &'a Camera) -> &'a mut Pencil<S> {
-        self.persp_matrix = Some(camera.perspective().into());
+    pub fn camera(&mut self, camera: &Camera) -> &mut Pencil<'a> {
+        let projection = if self.device.reversed_depth() {
+            camera.reversed_perspective()
+        } else {
+            camera.perspective()
+        };
+
+        self.persp_matrix = Some(projection.into());
         self.view_matrix = Some(camera.view().into());
         self
     }
 
     /// Add transform to draw
-    pub fn transform(&'a mut self, transform: Matrix4<f32>) -> &'a mut Pencil<S> {
+    pub fn transform(&mut self, transform: Matrix4<f32>) -> &mut Pencil<'a> {
         self.transform = Some(transform.into());
         self
     }
 
+    /// Add color tint to draw
+    pub fn color(&mut self, color: [f32; 4]) -> &mut Pencil<'a> {
+        self.color = Some(color);
+        self
+    }
+
     /// Draw shape to 3D space
     pub fn draw(&mut self) {
-        use glium::draw_parameters::DepthTest;
-        use glium::uniforms::{MagnifySamplerFilter, SamplerWrapFunction};
-        use glium::Depth;
-
-        let uniforms = uniform! {
-            time: ResourceManager::time(),
-            persp_matrix: self.persp_matrix.unwrap_or_else(|| Matrix4::one().into()),
-            view_matrix: self.view_matrix.unwrap_or_else(|| Matrix4::one().into()),
-            transform: self.transform.unwrap_or_else(|| Matrix4::one().into()),
+        let uniforms = [
+            ("time", Uniform::Float(ResourceManager::time())),
+            (
+                "persp_matrix",
+                Uniform::Mat4(self.persp_matrix.unwrap_or_else(|| Matrix4::one().into())),
+            ),
+            (
+                "view_matrix",
+                Uniform::Mat4(self.view_matrix.unwrap_or_else(|| Matrix4::one().into())),
+            ),
+            (
+                "transform",
+                Uniform::Mat4(self.transform.unwrap_or_else(|| Matrix4::one().into())),
+            ),
+            ("color", Uniform::Vec4(self.color.unwrap_or([1.0, 1.0, 1.0, 1.0]))),
+        ];
+
+        let call = DrawCall {
+            program: &self.program,
+            geometry: self.geometry,
+            uniforms: &uniforms,
+            texture: self.texture,
+            linear: self.linear,
+            mode: DrawMode::World,
+            scissor: None,
         };
 
-        // Get previously loaded shader program
-        let program = self
-            .canvas
-            .resources()
-            .shaders()
-            .get(&self.program)
-            .expect("Required shader not found");
-
-        let parameters = DrawParameters {
-            depth: Depth {
-                test: DepthTest::IfLess,
-                write: true,
-                ..Default::default()
-            },
-            blend: Blend::alpha_blending(),
-            multisampling: true,
-            backface_culling: BackfaceCullingMode::CullCounterClockwise,
-            ..Default::default()
-        };
+        self.device
+            .draw(&call)
+            .expect("Failed to draw geometry to screen");
+    }
+}
 
-        let (vertex_buffer, index_buffer) = self.vertices;
-
-        // Check if we need to attach a texture
-        if let Some(texture) = self.texture {
-            let magnify_filter = if self.linear {
-                MagnifySamplerFilter::Linear
-            } else {
-                MagnifySamplerFilter::Nearest
-            };
-
-            let uniforms = uniforms
-                .add(
-                    "tex",
-                    texture
-                        .sampled()
-                        .wrap_function(SamplerWrapFunction::BorderClamp)
-                        .magnify_filter(magnify_filter),
-                )
-                .add("resolution", [texture.width() as f32, texture.height() as f32]);
-
-            self.frame
-                .draw(vertex_buffer, index_buffer, program, &uniforms, &parameters)
-                .expect("Failed to draw textured geometry to screen");
-        } else {
-            self.frame
-                .draw(vertex_buffer, index_buffer, program, &uniforms, &parameters)
-                .expect("Failed to draw geometry to screen");
-        };
+/// Farthest depth, used to clear the depth buffer
+pub fn clear_depth(canvas: &Canvas) -> f32 {
+    if canvas.reversed_depth() {
+        0.0
+    } else {
+        1.0
+    }
+}
+
+/// Depth, blending and culling used by everything drawn on 3D space
+pub fn draw_parameters<'a>(canvas: &Canvas) -> DrawParameters<'a> {
+    use glium::draw_parameters::DepthTest;
+    use glium::Depth;
+
+    // Closer fragments have greater depth when it is reversed
+    let test = if canvas.reversed_depth() {
+        DepthTest::IfMore
+    } else {
+        DepthTest::IfLess
+    };
+
+    DrawParameters {
+        depth: Depth {
+            test,
+            write: true,
+            ..Default::default()
+        },
+        blend: Blend::alpha_blending(),
+        multisampling: true,
+        backface_culling: BackfaceCullingMode::CullCounterClockwise,
+        ..Default::default()
     }
 }
@@ -13,16 +13,305 @@
 // LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
 // IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
+use core::camera::Camera;
+use core::input::InputEvent;
+
 use gfx::canvas::Canvas;
-use glium::Frame;
+use gfx::device::RenderDevice;
+use gfx::transition::Transition;
+use glium::glutin::ControlFlow;
 
 pub enum SceneAction {
     None,
-    ChangeScene(Box<Scene>),
+    /// Open a scene over the current one
+    Push(Box<Scene>),
+    /// Close current scene, going back to the one below
+    Pop,
+    /// Close current scene and open another one in its place
+    Replace(Box<Scene>),
+    /// Apply an action playing a transition from current scene
+    Transition(Box<SceneAction>, Transition),
     Quit,
 }
 
+impl SceneAction {
+    /// Play a transition when this action is applied
+    pub fn with_transition(self, transition: Transition) -> SceneAction {
+        SceneAction::Transition(Box::new(self), transition)
+    }
+}
+
 pub trait Scene {
     fn load(&mut self, &mut Canvas);
-    fn draw(&mut self, &mut Canvas, &mut Frame) -> SceneAction;
+    /// Draw scene, partial tick goes from 0 to 1 between game ticks to smooth movement
+    fn draw(&mut self, &mut Canvas, &mut RenderDevice, f32) -> SceneAction;
+
+    /// Draw scene without user interface for an offscreen target, used by high resolution screenshots
+    /// and scene transitions. Returns false if this scene can't be drawn offscreen
+    fn draw_offscreen(
+        &mut self,
+        _canvas: &mut Canvas,
+        _device: &mut RenderDevice,
+        _partial_tick: f32,
+    ) -> bool {
+        false
+    }
+
+    /// Run one game tick, called at a fixed rate apart from drawing
+    fn tick(&mut self, _canvas: &mut Canvas) {}
+
+    /// Handle an input event, returning true when scenes below and the game should not see it
+    fn input(&mut self, _canvas: &mut Canvas, _event: &InputEvent) -> bool { false }
+
+    /// Called when scene becomes the top one, after load or once the scene over it is closed
+    fn on_enter(&mut self, _canvas: &mut Canvas) {}
+
+    /// Called when scene stops being the top one, before closing or when another scene is pushed over it
+    fn on_exit(&mut self, _canvas: &mut Canvas) {}
+
+    /// Get camera sounds are heard from, the top scene having one moves the listener every tick
+    fn camera(&self) -> Option<&Camera> { None }
+
+    /// Scenes below keep drawing while an overlay is on top
+    fn is_overlay(&self) -> bool { false }
+
+    /// Game beneath stops updating while this scene is open
+    fn pauses(&self) -> bool { false }
+}
+
+/// Scenes shown when a transition started, drawn offscreen until it ends
+struct Outgoing {
+    /// Scenes still open at the bottom of the stack
+    open: usize,
+    /// Scenes closed by the transition action, kept above the open ones
+    closed: Vec<Box<Scene>>,
+}
+
+/// Open scenes, only the top one changes scenes.
+/// Input goes to the top one first and then to the ones visible below it
+pub struct SceneStack {
+    scenes: Vec<Box<Scene>>,
+    outgoing: Option<Outgoing>,
+}
+
+impl SceneStack {
+    /// Create stack with the first scene open
+    pub fn new(canvas: &mut Canvas, mut first: Box<Scene>) -> SceneStack {
+        first.load(canvas);
+        first.on_enter(canvas);
+
+        SceneStack {
+            scenes: vec![first],
+            outgoing: None,
+        }
+    }
+
+    /// Get lowest scene not hidden by an opaque scene above it, nothing once the last scene is closed
+    fn bottom(&self) -> Option<usize> {
+        let mut bottom = self.scenes.len().checked_sub(1)?;
+
+        while bottom > 0 && self.scenes[bottom].is_overlay() {
+            bottom -= 1;
+        }
+
+        Some(bottom)
+    }
+
+    /// Give input event to visible scenes from the top, returning true if one of them consumed it
+    pub fn input(&mut self, canvas: &mut Canvas, event: &InputEvent) -> bool {
+        let bottom = match self.bottom() {
+            Some(bottom) => bottom,
+            None => return false,
+        };
+
+        self.scenes[bottom..]
+            .iter_mut()
+            .rev()
+            .any(|scene| scene.input(canvas, event))
+    }
+
+    /// Run a game tick on every scene not paused by a scene above it
+    pub fn tick(&mut self, canvas: &mut Canvas) {
+        if let Some(camera) = self.scenes.iter().rev().filter_map(|scene| scene.camera()).next() {
+            canvas.sounds_mut().set_listener(camera);
+        }
+
+        for i in 0..self.scenes.len() {
+            if !self.scenes[i + 1..].iter().any(|scene| scene.pauses()) {
+                self.scenes[i].tick(canvas);
+            }
+        }
+    }
+
+    /// Draw top scene and every scene visible under overlays, bottom first
+    pub fn draw(
+        &mut self,
+        canvas: &mut Canvas,
+        device: &mut RenderDevice,
+        partial_tick: f32,
+    ) -> ControlFlow {
+        let bottom = match self.bottom() {
+            Some(bottom) => bottom,
+            None => return ControlFlow::Break,
+        };
+
+        let top = self.scenes.len() - 1;
+
+        let mut action = SceneAction::None;
+
+        for i in bottom..=top {
+            let paused = self.scenes[i + 1..].iter().any(|scene| scene.pauses());
+            canvas.set_paused(paused);
+
+            let scene_action = self.scenes[i].draw(canvas, device, partial_tick);
+
+            // Covered scenes are only drawn, what they ask for is ignored
+            if i == top {
+                action = scene_action;
+            }
+        }
+
+        canvas.set_paused(false);
+
+        self.apply(canvas, action)
+    }
+
+    /// Draw visible scenes offscreen, bottom first, returning true if any of them was drawn
+    pub fn draw_offscreen(
+        &mut self,
+        canvas: &mut Canvas,
+        device: &mut RenderDevice,
+        partial_tick: f32,
+    ) -> bool {
+        let bottom = match self.bottom() {
+            Some(bottom) => bottom,
+            None => return false,
+        };
+
+        let mut drawn = false;
+
+        for scene in &mut self.scenes[bottom..] {
+            drawn |= scene.draw_offscreen(canvas, device, partial_tick);
+        }
+
+        drawn
+    }
+
+    /// Draw scenes shown before and after the playing transition offscreen,
+    /// returning false when no transition is playing or none of them was drawn
+    pub fn draw_transition(
+        &mut self,
+        canvas: &mut Canvas,
+        from: &mut RenderDevice,
+        to: &mut RenderDevice,
+        partial_tick: f32,
+    ) -> bool {
+        if !canvas.transitions().active() {
+            self.outgoing = None;
+            return false;
+        }
+
+        let drawn = match self.outgoing {
+            Some(ref mut outgoing) => {
+                let open = outgoing.open.min(self.scenes.len());
+
+                let mut scenes: Vec<&mut Box<Scene>> = self.scenes[..open]
+                    .iter_mut()
+                    .chain(outgoing.closed.iter_mut())
+                    .collect();
+
+                // Lowest scene not hidden by an opaque scene above it
+                let bottom = scenes.iter().rposition(|scene| !scene.is_overlay()).unwrap_or(0);
+
+                let mut drawn = false;
+
+                for scene in &mut scenes[bottom..] {
+                    drawn |= scene.draw_offscreen(canvas, from, partial_tick);
+                }
+
+                drawn
+            },
+            None => return false,
+        };
+
+        self.draw_offscreen(canvas, to, partial_tick) || drawn
+    }
+
+    /// Change open scenes as asked by the top one
+    pub fn apply(&mut self, canvas: &mut Canvas, action: SceneAction) -> ControlFlow {
+        self.change(canvas, action, &mut Vec::new())
+    }
+
+    /// Change open scenes, moving closed ones to a list
+    fn change(
+        &mut self,
+        canvas: &mut Canvas,
+        action: SceneAction,
+        closed: &mut Vec<Box<Scene>>,
+    ) -> ControlFlow {
+        match action {
+            SceneAction::None => (),
+            SceneAction::Push(mut scene) => {
+                if let Some(top) = self.scenes.last_mut() {
+                    top.on_exit(canvas);
+                }
+
+                scene.load(canvas);
+                scene.on_enter(canvas);
+                self.scenes.push(scene);
+            },
+            SceneAction::Pop => {
+                if let Some(mut top) = self.scenes.pop() {
+                    top.on_exit(canvas);
+                    closed.push(top);
+                }
+
+                // Closing the last scene closes the game
+                match self.scenes.last_mut() {
+                    Some(top) => top.on_enter(canvas),
+                    None => return ControlFlow::Break,
+                }
+            },
+            SceneAction::Replace(mut scene) => {
+                if let Some(mut top) = self.scenes.pop() {
+                    top.on_exit(canvas);
+                    closed.push(top);
+                }
+
+                scene.load(canvas);
+                scene.on_enter(canvas);
+                self.scenes.push(scene);
+            },
+            SceneAction::Transition(action, transition) => {
+                let open = self.scenes.len();
+                let mut outgoing = Vec::new();
+
+                let flow = self.change(canvas, *action, &mut outgoing);
+
+                // Closed scenes are kept until the transition ends
+                canvas.transitions_mut().start(transition);
+
+                self.outgoing = if canvas.transitions().active() {
+                    Some(Outgoing {
+                        open: open - outgoing.len(),
+                        closed: outgoing,
+                    })
+                } else {
+                    None
+                };
+
+                return flow;
+            },
+            SceneAction::Quit => {
+                // Scenes below already left when they were covered
+                if let Some(top) = self.scenes.last_mut() {
+                    top.on_exit(canvas);
+                }
+
+                return ControlFlow::Break;
+            },
+        }
+
+        ControlFlow::Continue
+    }
 }
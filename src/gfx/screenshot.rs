@@ -0,0 +1,203 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use gfx::canvas::Canvas;
+use gfx::device::GliumDevice;
+use gfx::pencil::clear_depth;
+use gfx::scene::SceneStack;
+
+use glium::framebuffer::{DepthRenderBuffer, SimpleFrameBuffer};
+use glium::texture::{DepthFormat, RawImage2d, Texture2d};
+use glium::Surface;
+
+use image::ColorType;
+
+use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Folder screenshots are saved to, inside the game directory
+pub const SCREENSHOTS_DIRECTORY: &str = "screenshots";
+
+/// High resolution screenshots are this many times bigger than the window
+pub const HIGH_RES_SCALE: u32 = 4;
+
+/// Get screenshot name for a time in seconds since 1970, like `2018-09-21_17.30.05`, in UTC
+pub fn timestamp(seconds: u64) -> String {
+    let days = seconds / 86400;
+    let time = seconds % 86400;
+
+    // Days to civil date, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02}_{:02}.{:02}.{:02}",
+        year,
+        month,
+        day,
+        time / 3600,
+        time / 60 % 60,
+        time % 60
+    )
+}
+
+/// Create an empty file for a screenshot, adding `_1`, `_2`... to the name if it is taken
+pub fn reserve_path(directory: &Path, name: &str) -> io::Result<PathBuf> {
+    fs::create_dir_all(directory)?;
+
+    let mut suffix = 0;
+
+    loop {
+        let file = if suffix == 0 {
+            format!("{}.png", name)
+        } else {
+            format!("{}_{}.png", name, suffix)
+        };
+
+        let path = directory.join(file);
+
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(_) => return Ok(path),
+            Err(ref why) if why.kind() == io::ErrorKind::AlreadyExists => suffix += 1,
+            Err(why) => return Err(why),
+        }
+    }
+}
+
+/// Convert RGBA pixels read from OpenGL, bottom row first, to opaque RGB pixels top row first
+pub fn flip_rows(rgba: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let row = width as usize * 4;
+    let mut rgb = Vec::with_capacity(width as usize * height as usize * 3);
+
+    for line in rgba.chunks(row).take(height as usize).rev() {
+        for pixel in line.chunks(4) {
+            rgb.extend_from_slice(&pixel[..3]);
+        }
+    }
+
+    rgb
+}
+
+/// Takes screenshots when asked and saves them in background threads
+pub struct Screenshots {
+    directory: PathBuf,
+    requested: Option<bool>,
+    sender: Sender<Result<String, String>>,
+    receiver: Receiver<Result<String, String>>,
+}
+
+impl Screenshots {
+    pub fn new<P>(directory: P) -> Screenshots
+    where
+        P: Into<PathBuf>,
+    {
+        let (sender, receiver) = channel();
+
+        Screenshots {
+            directory: directory.into(),
+            requested: None,
+            sender,
+            receiver,
+        }
+    }
+
+    /// Take a screenshot of the next frame, high resolution ones are drawn again offscreen
+    pub fn request(&mut self, high_res: bool) { self.requested = Some(high_res) }
+
+    /// Get and clear pending request, telling if it is high resolution
+    pub fn take_request(&mut self) -> Option<bool> { self.requested.take() }
+
+    /// Save pixels read from OpenGL without blocking, bottom row first
+    pub fn save(&self, rgba: Vec<u8>, width: u32, height: u32) {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_secs())
+            .unwrap_or(0);
+
+        let path = match reserve_path(&self.directory, &timestamp(seconds)) {
+            Ok(path) => path,
+            Err(why) => {
+                let _ = self.sender.send(Err(why.to_string()));
+                return;
+            },
+        };
+
+        let sender = self.sender.clone();
+
+        thread::spawn(move || {
+            let rgb = flip_rows(&rgba, width, height);
+
+            let result = image::save_buffer(&path, &rgb, width, height, ColorType::RGB(8))
+                .map(|_| {
+                    path.file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                        .unwrap_or_default()
+                })
+                .map_err(|why| why.to_string());
+
+            // Game may be closing, nobody is left to tell
+            let _ = sender.send(result);
+        });
+    }
+
+    /// Get file names of saved screenshots or why they failed since last call
+    pub fn finished(&self) -> Vec<Result<String, String>> { self.receiver.try_iter().collect() }
+}
+
+/// Draw scenes again on an offscreen target bigger than the window and read it back.
+/// User interface is not drawn, nothing is returned if no open scene can be drawn offscreen
+pub fn capture_high_res(
+    canvas: &mut Canvas,
+    scenes: &mut SceneStack,
+    partial_tick: f32,
+) -> Result<Option<(Vec<u8>, u32, u32)>, Box<Error>> {
+    let mut device = canvas.recording_device();
+
+    if !scenes.draw_offscreen(canvas, &mut device, partial_tick) {
+        return Ok(None);
+    }
+
+    let display = canvas.display().clone();
+
+    let width = canvas.settings().width() * HIGH_RES_SCALE;
+    let height = canvas.settings().height() * HIGH_RES_SCALE;
+
+    let color = Texture2d::empty(&display, width, height)?;
+    let depth = DepthRenderBuffer::new(&display, DepthFormat::I24, width, height)?;
+
+    {
+        let mut target = SimpleFrameBuffer::with_depth_buffer(&display, &color, &depth)?;
+        target.clear_color_and_depth((0.0, 0.0, 0.0, 1.0), clear_depth(canvas));
+
+        GliumDevice::new(&mut target, canvas).replay(device.calls())?;
+    }
+
+    let image: RawImage2d<u8> = color.read();
+
+    Ok(Some((image.data.into_owned(), image.width, image.height)))
+}
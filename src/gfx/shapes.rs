@@ -21,19 +21,65 @@ use std::error::Error;
 pub type VertexData2D = (VertexBuffer<Vertex2D>, IndexBuffer<u16>);
 pub type VertexData3D = (VertexBuffer<Vertex3D>, IndexBuffer<u16>);
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Vertex3D {
     position: [f32; 3],
     tex_coords: [f32; 2],
     texture: u8,
 }
 
-#[derive(Copy, Clone)]
+impl Vertex3D {
+    /// Create a vertex with position, texture coordinates and texture index
+    pub fn new(position: [f32; 3], tex_coords: [f32; 2], texture: u8) -> Vertex3D {
+        Vertex3D {
+            position,
+            tex_coords,
+            texture,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Vertex2D {
     position: [f32; 2],
     tex_coords: [f32; 2],
 }
 
+impl Vertex2D {
+    /// Create a vertex with position and texture coordinates
+    pub fn new(position: [f32; 2], tex_coords: [f32; 2]) -> Vertex2D {
+        Vertex2D { position, tex_coords }
+    }
+}
+
+/// Vertex of a tinted 2D sprite
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SpriteVertex {
+    position: [f32; 2],
+    tex_coords: [f32; 2],
+    color: [f32; 4],
+}
+
+impl SpriteVertex {
+    /// Create a vertex with position, texture coordinates and tint
+    pub fn new(position: [f32; 2], tex_coords: [f32; 2], color: [f32; 4]) -> SpriteVertex {
+        SpriteVertex {
+            position,
+            tex_coords,
+            color,
+        }
+    }
+
+    /// Get position in pixels
+    pub fn position(&self) -> [f32; 2] { self.position }
+
+    /// Get texture coordinates
+    pub fn tex_coords(&self) -> [f32; 2] { self.tex_coords }
+
+    /// Get tint color
+    pub fn color(&self) -> [f32; 4] { self.color }
+}
+
 pub struct Shapes {
     quad: VertexData2D,
     rectangle: VertexData2D,
@@ -165,3 +211,4 @@ impl Shapes {
 
 implement_vertex!(Vertex3D, position, tex_coords, texture);
 implement_vertex!(Vertex2D, position, tex_coords);
+implement_vertex!(SpriteVertex, position, tex_coords, color);
@@ -0,0 +1,302 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use cgmath::ortho;
+
+use core::resource_manager::font_manager::{FontManager, PAGE_SIZE};
+use core::resource_manager::resource::Resource;
+
+use gfx::device::{DrawCall, DrawMode, Geometry, RenderDevice, Uniform};
+use gfx::shapes::SpriteVertex;
+use gfx::text::TextQuad;
+
+use std::u16;
+
+/// Most sprites in one draw call, so vertex indices fit in 16 bits
+const MAX_SPRITES: usize = (u16::MAX as usize + 1) / 4;
+
+/// Coordinates sprites and clipping rectangles are given in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Units {
+    /// Screen pixels
+    Pixels,
+    /// Pixels multiplied by GUI scale
+    Gui,
+}
+
+/// Sprites sharing a texture, drawn with a single draw call
+pub struct Batch {
+    pub texture: Resource,
+    pub vertices: Vec<SpriteVertex>,
+    pub indices: Vec<u16>,
+}
+
+struct Sprite {
+    layer: u8,
+    texture: usize,
+    vertices: [SpriteVertex; 4],
+}
+
+/// Collects textured and tinted quads on screen and draws them with as few draw calls as possible.
+///
+/// Sprites are sorted by layer and then by texture, so sprites with different textures on the same
+/// layer should not overlap. Clipping is done while sprites are added, so it never splits a batch.
+pub struct SpriteBatch {
+    scale: f32,
+    layer: u8,
+    textures: Vec<Resource>,
+    sprites: Vec<Sprite>,
+    clips: Vec<[f32; 4]>,
+}
+
+/// Texture coordinates of a rectangle in pixels of an image loaded by the texture manager.
+/// Rows are reversed on upload, so the bottom row of the image is at 0
+pub fn image_source(rect: [f32; 4], size: [f32; 2]) -> [f32; 4] {
+    let [x, y, w, h] = rect;
+
+    [
+        x / size[0],
+        1.0 - y / size[1],
+        (x + w) / size[0],
+        1.0 - (y + h) / size[1],
+    ]
+}
+
+impl SpriteBatch {
+    /// Create empty batch, GUI units are `scale` pixels
+    pub fn new(scale: f32) -> SpriteBatch {
+        SpriteBatch {
+            scale,
+            layer: 0,
+            textures: Vec::new(),
+            sprites: Vec::new(),
+            clips: Vec::new(),
+        }
+    }
+
+    /// Get size of a GUI unit in pixels
+    pub fn scale(&self) -> f32 { self.scale }
+
+    /// Get sprites waiting to be drawn
+    pub fn len(&self) -> usize { self.sprites.len() }
+
+    /// Check if there is nothing to draw
+    pub fn is_empty(&self) -> bool { self.sprites.is_empty() }
+
+    /// Set layer of next sprites, higher layers are drawn over lower ones whatever their texture
+    pub fn set_layer(&mut self, layer: u8) { self.layer = layer }
+
+    /// Convert a rectangle to pixels
+    fn pixels(&self, rect: [f32; 4], units: Units) -> [f32; 4] {
+        match units {
+            Units::Pixels => rect,
+            Units::Gui => [
+                rect[0] * self.scale,
+                rect[1] * self.scale,
+                rect[2] * self.scale,
+                rect[3] * self.scale,
+            ],
+        }
+    }
+
+    /// Only draw next sprites inside a rectangle, also clipped by rectangles pushed before
+    pub fn push_clip(&mut self, rect: [f32; 4], units: Units) {
+        let [x, y, w, h] = self.pixels(rect, units);
+        let mut clip = [x, y, x + w, y + h];
+
+        if let Some(&[left, top, right, bottom]) = self.clips.last() {
+            clip = [
+                clip[0].max(left),
+                clip[1].max(top),
+                clip[2].min(right),
+                clip[3].min(bottom),
+            ];
+        }
+
+        self.clips.push(clip);
+    }
+
+    /// Remove last clipping rectangle
+    pub fn pop_clip(&mut self) { self.clips.pop(); }
+
+    /// Add a sprite with the part of a texture inside `source` texture coordinates, tinted with a color
+    pub fn sprite(
+        &mut self,
+        texture: &Resource,
+        rect: [f32; 4],
+        units: Units,
+        source: [f32; 4],
+        color: [f32; 4],
+    ) {
+        self.add(texture, rect, units, source, color, 0.0);
+    }
+
+    /// Add a sprite with its top edge moved right by `skew` in the same units
+    fn add(
+        &mut self,
+        texture: &Resource,
+        rect: [f32; 4],
+        units: Units,
+        source: [f32; 4],
+        color: [f32; 4],
+        skew: f32,
+    ) {
+        let [x, y, w, h] = self.pixels(rect, units);
+        let skew = self.pixels([skew, 0.0, 0.0, 0.0], units)[0];
+
+        let (mut left, mut top, mut right, mut bottom) = (x, y, x + w, y + h);
+
+        if let Some(&[clip_left, clip_top, clip_right, clip_bottom]) = self.clips.last() {
+            left = left.max(clip_left);
+            top = top.max(clip_top);
+            right = right.min(clip_right);
+            bottom = bottom.min(clip_bottom);
+        }
+
+        if left >= right || top >= bottom {
+            return;
+        }
+
+        // Keep the visible part of the texture when clipped
+        let [u0, v0, u1, v1] = source;
+        let u = |x_pos: f32| u0 + (x_pos - x) / w * (u1 - u0);
+        let v = |y_pos: f32| v0 + (y_pos - y) / h * (v1 - v0);
+
+        let vertices = [
+            SpriteVertex::new([left + skew, top], [u(left), v(top)], color),
+            SpriteVertex::new([right + skew, top], [u(right), v(top)], color),
+            SpriteVertex::new([right, bottom], [u(right), v(bottom)], color),
+            SpriteVertex::new([left, bottom], [u(left), v(bottom)], color),
+        ];
+
+        let texture = match self.textures.iter().position(|known| known == texture) {
+            Some(index) => index,
+            None => {
+                self.textures.push(texture.clone());
+                self.textures.len() - 1
+            },
+        };
+
+        self.sprites.push(Sprite {
+            layer: self.layer,
+            texture,
+            vertices,
+        });
+    }
+
+    /// Add text quads in GUI units from a position, tinted with a color
+    pub fn text(&mut self, quads: &[TextQuad], position: [f32; 2], color: [f32; 4]) {
+        let size = PAGE_SIZE as f32;
+
+        for quad in quads {
+            let [x, y, w, h] = quad.source;
+
+            // Font atlas pages are stored upside down
+            let source = [
+                x as f32 / size,
+                1.0 - y as f32 / size,
+                (x + w) as f32 / size,
+                1.0 - (y + h) as f32 / size,
+            ];
+
+            let tint = [
+                quad.color[0] * color[0],
+                quad.color[1] * color[1],
+                quad.color[2] * color[2],
+                quad.color[3] * color[3],
+            ];
+
+            let rect = [
+                position[0] + quad.position[0],
+                position[1] + quad.position[1],
+                quad.size[0],
+                quad.size[1],
+            ];
+
+            self.add(
+                &FontManager::page(quad.page),
+                rect,
+                Units::Gui,
+                source,
+                tint,
+                quad.skew,
+            );
+        }
+    }
+
+    /// Take added sprites grouped in draw calls, in the order they should be drawn
+    pub fn batches(&mut self) -> Vec<Batch> {
+        // Stable sort keeps order between sprites with the same texture
+        self.sprites.sort_by_key(|sprite| (sprite.layer, sprite.texture));
+
+        let mut batches: Vec<Batch> = Vec::new();
+        let mut current = None;
+
+        for sprite in self.sprites.drain(..) {
+            let full = batches
+                .last()
+                .map_or(false, |batch| batch.vertices.len() / 4 == MAX_SPRITES);
+
+            if current != Some(sprite.texture) || full {
+                current = Some(sprite.texture);
+                batches.push(Batch {
+                    texture: self.textures[sprite.texture].clone(),
+                    vertices: Vec::new(),
+                    indices: Vec::new(),
+                });
+            }
+
+            if let Some(batch) = batches.last_mut() {
+                let base = batch.vertices.len() as u16;
+
+                batch.vertices.extend_from_slice(&sprite.vertices);
+                batch
+                    .indices
+                    .extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+            }
+        }
+
+        self.textures.clear();
+        self.clips.clear();
+        self.layer = 0;
+
+        batches
+    }
+
+    /// Draw added sprites over everything on a screen of some size in pixels.
+    /// Textures not loaded yet are skipped
+    pub fn flush(&mut self, device: &mut RenderDevice, width: u32, height: u32) {
+        let persp_matrix: [[f32; 4]; 4] = ortho(0.0, width as f32, height as f32, 0.0, -1.0, 1.0).into();
+        let uniforms = [("persp_matrix", Uniform::Mat4(persp_matrix))];
+
+        for batch in self.batches() {
+            if !device.has_texture(&batch.texture) {
+                continue;
+            }
+
+            let call = DrawCall {
+                program: "sprite",
+                geometry: Geometry::Sprites(&batch.vertices, &batch.indices),
+                uniforms: &uniforms,
+                texture: Some(&batch.texture),
+                linear: false,
+                mode: DrawMode::Overlay,
+                scissor: None,
+            };
+
+            device.draw(&call).expect("Failed to draw sprites to screen");
+        }
+    }
+}
@@ -0,0 +1,447 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use cgmath::Matrix4;
+
+use core::camera::Camera;
+use core::resource_manager::font_manager::{FontManager, Glyph, LINE_HEIGHT, PAGE_SIZE};
+use core::resource_manager::texture_manager::TextureManager;
+use core::resource_manager::ResourceManager;
+use core::text::component::TextRun;
+use core::text::style::Style;
+
+use gfx::canvas::Canvas;
+use gfx::device::{GliumDevice, RenderDevice};
+use gfx::pencil::Pencil;
+use gfx::shapes::Vertex2D;
+use gfx::sprites::SpriteBatch;
+
+use glium::Surface;
+
+use conrod::position::rect::Rect;
+use conrod::widget::id::List;
+use conrod::{color, widget, Color, Positionable, Sizeable, UiCell, Widget};
+
+use std::collections::BTreeMap;
+
+/// Size of a font unit in pixels on screen overlays at GUI scale 1
+pub const GUI_SCALE: f64 = 2.0;
+
+/// Horizontal offset of the top of italic glyphs in font units
+const ITALIC_SKEW: f32 = 1.0;
+
+/// Offset of the second copy of bold glyphs in font units
+const BOLD_OFFSET: f32 = 1.0;
+
+/// Image widgets used to fake the slant of an italic glyph on user interface
+const ITALIC_SLICES: u32 = 4;
+
+/// Textured quad of a text mesh in font units
+#[derive(Clone, Copy, Debug)]
+pub struct TextQuad {
+    pub page: usize,
+    /// Atlas rectangle in pixels, origin is top left
+    pub source: [u32; 4],
+    pub position: [f32; 2],
+    pub size: [f32; 2],
+    /// Horizontal offset of the top edge
+    pub skew: f32,
+    pub color: [f32; 4],
+}
+
+/// Styled text converted to font atlas quads
+pub struct TextMesh {
+    quads: Vec<TextQuad>,
+    regions: Vec<([f32; 4], usize)>,
+    size: [f32; 2],
+}
+
+impl TextMesh {
+    /// Lay out styled runs, lines are split on `\n`
+    pub fn new(fonts: &mut FontManager, runs: &[TextRun]) -> TextMesh {
+        let mut mesh = TextMesh {
+            quads: Vec::new(),
+            regions: Vec::new(),
+            size: [0.0, LINE_HEIGHT],
+        };
+
+        let mut cursor = [0.0, 0.0];
+
+        for (index, run) in runs.iter().enumerate() {
+            let style = &run.style;
+            let mut start = cursor[0];
+
+            for character in run.text.chars() {
+                if character == '\n' {
+                    mesh.decorate(fonts, style, index, start, cursor);
+
+                    cursor = [0.0, cursor[1] + LINE_HEIGHT];
+                    start = 0.0;
+                    continue;
+                }
+
+                let character = if style.obfuscated() && character != ' ' {
+                    fonts.obfuscate(character)
+                } else {
+                    character
+                };
+
+                let mut advance = fonts.advance(character);
+
+                if character != ' ' {
+                    let glyph = fonts.glyph(character).or_else(|| fonts.glyph('?'));
+
+                    if let Some(glyph) = glyph.filter(|glyph| glyph.visible()) {
+                        mesh.glyph(glyph, style, cursor);
+                    }
+                }
+
+                if style.bold() {
+                    advance += BOLD_OFFSET;
+                }
+
+                cursor[0] += advance;
+                mesh.size[0] = mesh.size[0].max(cursor[0]);
+            }
+
+            mesh.decorate(fonts, style, index, start, cursor);
+        }
+
+        mesh.size[1] = cursor[1] + LINE_HEIGHT;
+        mesh
+    }
+
+    /// Create a mesh for text without formatting
+    pub fn plain(fonts: &mut FontManager, text: &str) -> TextMesh {
+        TextMesh::new(fonts, &[TextRun::new(text, Style::default())])
+    }
+
+    /// Add glyph quads
+    fn glyph(&mut self, glyph: Glyph, style: &Style, cursor: [f32; 2]) {
+        let [x, y] = glyph.bearing();
+
+        let quad = TextQuad {
+            page: glyph.page(),
+            source: glyph.rect(),
+            position: [cursor[0] + x, cursor[1] + y],
+            size: glyph.size(),
+            skew: if style.italic() { ITALIC_SKEW } else { 0.0 },
+            color: style.color().rgba(),
+        };
+
+        self.quads.push(quad);
+
+        // Bold text is the same glyph drawn twice
+        if style.bold() {
+            self.quads.push(TextQuad {
+                position: [quad.position[0] + BOLD_OFFSET, quad.position[1]],
+                ..quad
+            });
+        }
+    }
+
+    /// Add lines and hit region of a run segment on a single line
+    fn decorate(
+        &mut self,
+        fonts: &mut FontManager,
+        style: &Style,
+        run: usize,
+        start: f32,
+        cursor: [f32; 2],
+    ) {
+        let width = cursor[0] - start;
+
+        if width <= 0.0 {
+            return;
+        }
+
+        self.regions.push(([start, cursor[1], width, LINE_HEIGHT], run));
+
+        if style.underlined() {
+            self.line(
+                fonts.white(),
+                style,
+                [start - 1.0, cursor[1] + LINE_HEIGHT - 1.0],
+                width + 1.0,
+            );
+        }
+
+        if style.strikethrough() {
+            self.line(
+                fonts.white(),
+                style,
+                [start - 1.0, cursor[1] + LINE_HEIGHT / 2.0 - 1.0],
+                width + 1.0,
+            );
+        }
+    }
+
+    /// Add a solid line quad
+    fn line(&mut self, white: Glyph, style: &Style, position: [f32; 2], width: f32) {
+        self.quads.push(TextQuad {
+            page: white.page(),
+            source: white.rect(),
+            position,
+            size: [width, 1.0],
+            skew: 0.0,
+            color: style.color().rgba(),
+        });
+    }
+
+    /// Get every quad to draw
+    pub fn quads(&self) -> &[TextQuad] { &self.quads }
+
+    /// Get text width and height in font units
+    pub fn size(&self) -> [f32; 2] { self.size }
+
+    /// Get the index of the run under a point in font units, used for click and hover events
+    pub fn hit_test(&self, point: [f32; 2]) -> Option<usize> {
+        self.regions
+            .iter()
+            .find(|(rect, _)| {
+                point[0] >= rect[0]
+                    && point[0] < rect[0] + rect[2]
+                    && point[1] >= rect[1]
+                    && point[1] < rect[1] + rect[3]
+            })
+            .map(|&(_, run)| run)
+    }
+}
+
+/// Multiply two colors
+fn tint(color: [f32; 4], tint: [f32; 4]) -> [f32; 4] {
+    [
+        color[0] * tint[0],
+        color[1] * tint[1],
+        color[2] * tint[2],
+        color[3] * tint[3],
+    ]
+}
+
+/// Draw a text mesh on user interface
+///
+/// Every quad is an image widget cropped from the font atlas, so `ids` grows to one widget id per
+/// quad. Position is the top left corner of the text and scale is the size of a font unit in pixels.
+pub fn ui_mesh(
+    ui: &mut UiCell,
+    ids: &mut List,
+    textures: &TextureManager,
+    mesh: &TextMesh,
+    position: [f64; 2],
+    scale: f64,
+    color: [f32; 4],
+) {
+    let slices = |quad: &TextQuad| {
+        if quad.skew != 0.0 {
+            ITALIC_SLICES.min(quad.source[3]).max(1)
+        } else {
+            1
+        }
+    };
+    let count = mesh.quads().iter().map(|quad| slices(quad) as usize).sum();
+
+    if ids.len() < count {
+        ids.resize(count, &mut ui.widget_id_generator());
+    }
+
+    let mut ids = ids.iter();
+
+    for quad in mesh.quads() {
+        let (page_id, (_, page_height)) = match textures.get_ui(&FontManager::page(quad.page)) {
+            Some(page) => page,
+            None => continue,
+        };
+
+        let [r, g, b, a] = tint(quad.color, color);
+        let [x, y, w, h] = quad.source;
+        let slices = slices(quad);
+
+        for slice in 0..slices {
+            let id = match ids.next() {
+                Some(&id) => id,
+                None => return,
+            };
+
+            // Italic glyphs are drawn as horizontal strips moved to the right the higher they are
+            let top = y + h * slice / slices;
+            let bottom = y + h * (slice + 1) / slices;
+
+            let fraction = (slice as f32 + 0.5) / slices as f32;
+            let skew = quad.skew * (1.0 - fraction);
+
+            let width = f64::from(quad.size[0]) * scale;
+            let height = f64::from(quad.size[1]) * scale / f64::from(slices);
+
+            // UI textures are flipped, so rectangles start at the bottom
+            let source = Rect::from_corners(
+                [f64::from(x), page_height - f64::from(bottom)],
+                [f64::from(x + w), page_height - f64::from(top)],
+            );
+
+            widget::Image::new(page_id)
+                .source_rectangle(source)
+                .w_h(width, height)
+                .x_y(
+                    position[0] + f64::from(quad.position[0] + skew) * scale + width / 2.0,
+                    position[1]
+                        - f64::from(quad.position[1]) * scale
+                        - height * f64::from(slice)
+                        - height / 2.0,
+                )
+                .color(Some(color::rgba(r, g, b, a)))
+                .set(id, ui);
+        }
+    }
+}
+
+/// Draw text without formatting on user interface
+pub fn ui_text(
+    ui: &mut UiCell,
+    ids: &mut List,
+    resources: &mut ResourceManager,
+    text: &str,
+    position: [f64; 2],
+    scale: f64,
+    color: Color,
+) {
+    let (fonts, textures) = resources.fonts_with_textures();
+    let mesh = TextMesh::plain(fonts, text);
+
+    ui_mesh(ui, ids, textures, &mesh, position, scale, color.to_fsa());
+}
+
+/// Get the run under a user interface point, for a mesh drawn with `ui_mesh`
+pub fn ui_hit_test(mesh: &TextMesh, position: [f64; 2], scale: f64, point: [f64; 2]) -> Option<usize> {
+    mesh.hit_test([
+        ((point[0] - position[0]) / scale) as f32,
+        ((position[1] - point[1]) / scale) as f32,
+    ])
+}
+
+/// Vertices of text quads in font units grouped by atlas page and color, every group is a draw call
+pub fn quad_groups<'a, I>(quads: I, color: [f32; 4]) -> Vec<(usize, Vec<Vertex2D>, Vec<u16>, [f32; 4])>
+where
+    I: IntoIterator<Item = &'a TextQuad>,
+{
+    let size = PAGE_SIZE as f32;
+
+    let mut groups: BTreeMap<(usize, [u32; 4]), (Vec<Vertex2D>, Vec<u16>, [f32; 4])> = BTreeMap::new();
+
+    for quad in quads {
+        let color = tint(quad.color, color);
+        let key = (
+            quad.page,
+            [
+                color[0].to_bits(),
+                color[1].to_bits(),
+                color[2].to_bits(),
+                color[3].to_bits(),
+            ],
+        );
+
+        let (vertices, indices, _) = groups
+            .entry(key)
+            .or_insert_with(|| (Vec::new(), Vec::new(), color));
+
+        let [x, y, w, h] = quad.source;
+        let [width, height] = quad.size;
+        let [left, top] = quad.position;
+
+        let (u0, v0) = (x as f32 / size, 1.0 - y as f32 / size);
+        let (u1, v1) = ((x + w) as f32 / size, 1.0 - (y + h) as f32 / size);
+
+        let base = vertices.len() as u16;
+
+        vertices.push(Vertex2D::new([left + quad.skew, -top], [u0, v0]));
+        vertices.push(Vertex2D::new([left + quad.skew + width, -top], [u1, v0]));
+        vertices.push(Vertex2D::new([left + width, -top - height], [u1, v1]));
+        vertices.push(Vertex2D::new([left, -top - height], [u0, v1]));
+
+        indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+    }
+
+    groups
+        .into_iter()
+        .map(|((page, _), (vertices, indices, color))| (page, vertices, indices, color))
+        .collect()
+}
+
+/// Draw a text mesh on 3D space
+///
+/// Text starts at the origin of `transform` and every font unit is a world unit.
+pub fn world_mesh(
+    device: &mut RenderDevice,
+    camera: &Camera,
+    mesh: &TextMesh,
+    transform: Matrix4<f32>,
+    color: [f32; 4],
+) {
+    for (page, vertices, indices, color) in quad_groups(mesh.quads(), color) {
+        let page = FontManager::page(page);
+
+        if !device.has_texture(&page) {
+            continue;
+        }
+
+        Pencil::new(device, "text")
+            .vertices(&vertices, &indices)
+            .camera(camera)
+            .transform(transform)
+            .texture(&page)
+            .color(color)
+            .draw();
+    }
+}
+
+/// Get a solid rectangle quad in font units, drawn with the white glyph of the font atlas
+pub fn solid_quad(fonts: &mut FontManager, rect: [f32; 4], color: [f32; 4]) -> TextQuad {
+    let white = fonts.white();
+
+    TextQuad {
+        page: white.page(),
+        source: white.rect(),
+        position: [rect[0], rect[1]],
+        size: [rect[2], rect[3]],
+        skew: 0.0,
+        color,
+    }
+}
+
+/// Draw quads over everything on screen, one draw call for each atlas page
+///
+/// Quads are in font units from the top left corner and scale is the size of a font unit in pixels.
+pub fn screen_quads<S>(frame: &mut S, canvas: &Canvas, quads: &[TextQuad], scale: f32)
+where
+    S: Surface,
+{
+    let (width, height) = frame.get_dimensions();
+
+    let mut sprites = SpriteBatch::new(scale);
+    sprites.text(quads, [0.0, 0.0], [1.0, 1.0, 1.0, 1.0]);
+    sprites.flush(&mut GliumDevice::new(frame, canvas), width, height);
+}
+
+/// Draw text without formatting on 3D space
+pub fn world_text(
+    device: &mut RenderDevice,
+    fonts: &mut FontManager,
+    camera: &Camera,
+    text: &str,
+    transform: Matrix4<f32>,
+    color: [f32; 4],
+) {
+    let mesh = TextMesh::plain(fonts, text);
+    world_mesh(device, camera, &mesh, transform, color);
+}
@@ -0,0 +1,158 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::resource_manager::font_manager::FontManager;
+
+use gfx::canvas::Canvas;
+use gfx::text::{screen_quads, solid_quad, TextMesh, TextQuad, GUI_SCALE};
+
+use glium::Surface;
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Seconds a toast stays on screen
+const TOAST_TIME: u64 = 5;
+
+/// Toasts shown at the same time, older ones are dropped
+const MAX_TOASTS: usize = 5;
+
+/// Toast size in font units
+const TOAST_WIDTH: f32 = 160.0;
+const TOAST_HEIGHT: f32 = 32.0;
+
+const BACKGROUND: [f32; 4] = [0.0, 0.0, 0.0, 0.75];
+const TITLE_COLOR: [f32; 4] = [1.0, 1.0, 0.0, 1.0];
+const MESSAGE_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+
+/// Short messages shown at the top right corner for a few seconds
+pub struct Toasts {
+    toasts: VecDeque<(String, String, Instant)>,
+}
+
+impl Toasts {
+    pub fn new() -> Toasts {
+        Toasts {
+            toasts: VecDeque::new(),
+        }
+    }
+
+    /// Show a message with a title
+    pub fn push<T, M>(&mut self, title: T, message: M)
+    where
+        T: Into<String>,
+        M: Into<String>,
+    {
+        if self.toasts.len() == MAX_TOASTS {
+            self.toasts.pop_front();
+        }
+
+        self.toasts
+            .push_back((title.into(), message.into(), Instant::now()));
+    }
+
+    /// Drop toasts shown for long enough
+    fn expire(&mut self) {
+        let time = Duration::from_secs(TOAST_TIME);
+
+        while self
+            .toasts
+            .front()
+            .map_or(false, |toast| toast.2.elapsed() >= time)
+        {
+            self.toasts.pop_front();
+        }
+    }
+
+    /// Get title and message of toasts on screen, oldest first
+    pub fn toasts(&self) -> Vec<(&str, &str)> {
+        self.toasts
+            .iter()
+            .map(|&(ref title, ref message, _)| (title.as_str(), message.as_str()))
+            .collect()
+    }
+}
+
+/// Add text tinted with a color at a position in font units
+fn text(
+    quads: &mut Vec<TextQuad>,
+    fonts: &mut FontManager,
+    text: &str,
+    position: [f32; 2],
+    color: [f32; 4],
+) {
+    let mesh = TextMesh::plain(fonts, text);
+
+    quads.extend(mesh.quads().iter().map(|quad| TextQuad {
+        position: [quad.position[0] + position[0], quad.position[1] + position[1]],
+        color: [
+            quad.color[0] * color[0],
+            quad.color[1] * color[1],
+            quad.color[2] * color[2],
+            quad.color[3] * color[3],
+        ],
+        ..*quad
+    }));
+}
+
+/// Draw toasts stacked down from the top right corner
+pub fn draw_toasts<S>(canvas: &mut Canvas, frame: &mut S)
+where
+    S: Surface,
+{
+    canvas.toasts_mut().expire();
+
+    if canvas.toasts().toasts.is_empty() {
+        return;
+    }
+
+    let (width, _) = frame.get_dimensions();
+    let scale = (GUI_SCALE * canvas.settings().scale()) as f32;
+
+    let left = width as f32 / scale - TOAST_WIDTH;
+    let toasts: Vec<(String, String)> = canvas
+        .toasts()
+        .toasts()
+        .into_iter()
+        .map(|(title, message)| (title.to_owned(), message.to_owned()))
+        .collect();
+
+    let mut quads = Vec::new();
+
+    {
+        let fonts = canvas.resources_mut().fonts_mut();
+
+        for (i, &(ref title, ref message)) in toasts.iter().enumerate() {
+            let top = i as f32 * TOAST_HEIGHT;
+
+            quads.push(solid_quad(
+                fonts,
+                [left, top, TOAST_WIDTH, TOAST_HEIGHT],
+                BACKGROUND,
+            ));
+
+            text(&mut quads, fonts, title, [left + 6.0, top + 7.0], TITLE_COLOR);
+            text(
+                &mut quads,
+                fonts,
+                message,
+                [left + 6.0, top + 18.0],
+                MESSAGE_COLOR,
+            );
+        }
+    }
+
+    screen_quads(frame, canvas, &quads, scale);
+}
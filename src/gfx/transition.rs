@@ -0,0 +1,256 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use gfx::canvas::Canvas;
+use gfx::device::{GliumDevice, RecordedCall};
+use gfx::pencil::clear_depth;
+
+use glium::framebuffer::{DepthRenderBuffer, SimpleFrameBuffer};
+use glium::texture::{DepthFormat, Texture2d};
+use glium::{Display, DrawParameters, Frame, Surface};
+
+use std::error::Error;
+use std::time::{Duration, Instant};
+
+/// Screen side a sliding scene comes from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+impl Side {
+    /// Get direction towards this side, in texture coordinates
+    pub fn direction(self) -> [f32; 2] {
+        match self {
+            Side::Left => [-1.0, 0.0],
+            Side::Right => [1.0, 0.0],
+            Side::Top => [0.0, 1.0],
+            Side::Bottom => [0.0, -1.0],
+        }
+    }
+}
+
+/// How outgoing and incoming scenes are blended
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransitionKind {
+    /// Switch instantly
+    Cut,
+    /// Outgoing scene fades into incoming one
+    Fade,
+    /// Incoming scene pushes outgoing one out of the screen
+    Slide(Side),
+    /// Loaded shader program, given `from` and `to` textures, `progress` from 0 to 1 and `direction`
+    Shader(&'static str),
+}
+
+/// Animation played when scenes change
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Transition {
+    kind: TransitionKind,
+    duration: Duration,
+}
+
+impl Transition {
+    /// Create transition of some kind lasting some time
+    pub fn new(kind: TransitionKind, duration: Duration) -> Transition { Transition { kind, duration } }
+
+    /// Switch instantly
+    pub fn cut() -> Transition { Transition::new(TransitionKind::Cut, Duration::from_secs(0)) }
+
+    /// Fade outgoing scene into incoming one
+    pub fn fade(duration: Duration) -> Transition { Transition::new(TransitionKind::Fade, duration) }
+
+    /// Slide incoming scene from a side of the screen
+    pub fn slide(side: Side, duration: Duration) -> Transition {
+        Transition::new(TransitionKind::Slide(side), duration)
+    }
+
+    /// Blend scenes with a loaded shader program
+    pub fn shader(program: &'static str, duration: Duration) -> Transition {
+        Transition::new(TransitionKind::Shader(program), duration)
+    }
+
+    /// Get how scenes are blended
+    pub fn kind(&self) -> TransitionKind { self.kind }
+
+    /// Get how long the transition lasts
+    pub fn duration(&self) -> Duration { self.duration }
+
+    /// Get how far the transition is after some time, from 0 to 1
+    pub fn progress(&self, elapsed: Duration) -> f32 {
+        let duration = seconds(self.duration);
+
+        if duration <= 0.0 {
+            1.0
+        } else {
+            (seconds(elapsed) / duration).min(1.0)
+        }
+    }
+
+    /// Get shader program blending the scenes, none for a cut
+    fn program(&self) -> Option<&'static str> {
+        match self.kind {
+            TransitionKind::Cut => None,
+            TransitionKind::Fade => Some("fade"),
+            TransitionKind::Slide(_) => Some("slide"),
+            TransitionKind::Shader(program) => Some(program),
+        }
+    }
+
+    /// Get direction passed to the shader program
+    fn direction(&self) -> [f32; 2] {
+        match self.kind {
+            TransitionKind::Slide(side) => side.direction(),
+            _ => [0.0, 0.0],
+        }
+    }
+}
+
+fn seconds(duration: Duration) -> f32 {
+    duration.as_secs() as f32 + duration.subsec_nanos() as f32 / 1_000_000_000.0
+}
+
+/// Plays transitions, scene stack draws outgoing and incoming scenes every frame until the transition ends
+pub struct Transitions {
+    // Outgoing and incoming scene images
+    targets: Option<(Texture2d, Texture2d, DepthRenderBuffer)>,
+
+    active: Option<(Transition, Instant)>,
+}
+
+impl Transitions {
+    pub fn new() -> Transitions {
+        Transitions {
+            targets: None,
+            active: None,
+        }
+    }
+
+    /// Start a transition, replacing the one playing
+    pub fn start(&mut self, transition: Transition) { self.active = Some((transition, Instant::now())); }
+
+    /// Stop playing transition
+    pub fn stop(&mut self) { self.active = None; }
+
+    /// Get playing transition and how far it is, none once it ends
+    pub fn progress(&self) -> Option<(Transition, f32)> {
+        let (transition, started) = self.active?;
+        let progress = transition.progress(started.elapsed());
+
+        if progress < 1.0 && transition.program().is_some() {
+            Some((transition, progress))
+        } else {
+            None
+        }
+    }
+
+    /// Get if a transition is playing
+    pub fn active(&self) -> bool { self.progress().is_some() }
+
+    /// Create images matching window size
+    fn resize(&mut self, display: &Display, width: u32, height: u32) -> Result<(), Box<Error>> {
+        let resized = match self.targets {
+            Some((ref from, ..)) => from.get_width() != width || from.get_height() != Some(height),
+            None => true,
+        };
+
+        if resized {
+            self.targets = Some((
+                Texture2d::empty(display, width, height)?,
+                Texture2d::empty(display, width, height)?,
+                DepthRenderBuffer::new(display, DepthFormat::I24, width, height)?,
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Draw outgoing and incoming scenes offscreen and blend them on the frame
+pub fn draw_transition(
+    display: &Display,
+    canvas: &mut Canvas,
+    frame: &mut Frame,
+    from: &[RecordedCall],
+    to: &[RecordedCall],
+) {
+    if let Err(why) = blend(display, canvas, frame, from, to) {
+        warn!("Failed to draw scene transition: {}", why);
+
+        canvas.transitions_mut().stop();
+    }
+}
+
+fn blend(
+    display: &Display,
+    canvas: &mut Canvas,
+    frame: &mut Frame,
+    from: &[RecordedCall],
+    to: &[RecordedCall],
+) -> Result<(), Box<Error>> {
+    let (transition, progress) = match canvas.transitions().progress() {
+        Some(playing) => playing,
+        None => return Ok(()),
+    };
+
+    let (width, height) = frame.get_dimensions();
+    canvas.transitions_mut().resize(display, width, height)?;
+
+    let canvas = &*canvas;
+
+    let (ref from_image, ref to_image, ref depth) = *canvas
+        .transitions()
+        .targets
+        .as_ref()
+        .expect("Transition targets were just created");
+
+    for &(image, calls) in &[(from_image, from), (to_image, to)] {
+        let mut target = SimpleFrameBuffer::with_depth_buffer(display, image, depth)?;
+        target.clear_color_and_depth((0.0, 0.0, 0.0, 1.0), clear_depth(canvas));
+
+        GliumDevice::new(&mut target, canvas).replay(calls)?;
+    }
+
+    let resources = canvas.resources();
+
+    let program = transition.program().expect("Cuts are never played");
+    let program = resources
+        .shaders()
+        .get(program)
+        .ok_or_else(|| format!("shader '{}' is not loaded", program))?;
+
+    let uniforms = uniform! {
+        from: from_image.sampled(),
+        to: to_image.sampled(),
+        progress: progress,
+        direction: transition.direction(),
+    };
+
+    let shapes = resources.shapes().ok_or("Vertex data is not created")?;
+    let (vertex_buffer, index_buffer) = shapes.quad();
+
+    frame.draw(
+        vertex_buffer,
+        index_buffer,
+        program,
+        &uniforms,
+        &DrawParameters::default(),
+    )?;
+
+    Ok(())
+}
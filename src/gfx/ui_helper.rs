@@ -14,11 +14,23 @@
 // IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 use core::resource_manager::texture_manager::UiTexture;
+use core::resource_manager::ResourceManager;
+use core::text::component::IntoRuns;
+
+use gfx::text::{ui_mesh, TextMesh};
 
 use conrod::position::rect::Rect;
+use conrod::position::Align;
 use conrod::widget::button::Image;
+use conrod::widget::id::List;
 use conrod::widget::Button;
-use conrod::{color, widget, Labelable, Sizeable};
+use conrod::{color, widget, Borderable, Colorable, Labelable, Positionable, Sizeable, UiCell, Widget};
+
+/// Button label color while hovered
+const HOVER_COLOR: [f32; 4] = [1.0, 1.0, 0.627, 1.0];
+
+/// Height of a vanilla button in font units
+const BUTTON_HEIGHT: f64 = 20.0;
 
 /// Draw Litecraft button widget
 pub fn button<'a>(widgets: &UiTexture, scale: f64) -> Button<'a, Image> {
@@ -40,3 +52,159 @@ pub fn button<'a>(widgets: &UiTexture, scale: f64) -> Button<'a, Image> {
         .hover_source_rectangle(hover_rect)
         .press_source_rectangle(press_rect)
 }
+
+/// Draw Litecraft slider widget, label it with `button_label`
+pub fn slider<'a>(value: f64, min: f64, max: f64, scale: f64) -> widget::Slider<'a, f64> {
+    widget::Slider::new(value, min, max)
+        .h(45.0 * scale)
+        .w(480.0 * scale)
+        .color(color::rgb(0.44, 0.44, 0.44))
+        .border(2.0 * scale)
+        .border_color(color::BLACK)
+}
+
+/// Draw styled text aligned inside a widget, plain strings may use legacy formatting codes
+pub fn text<T>(
+    ui: &mut UiCell,
+    ids: &mut List,
+    resources: &mut ResourceManager,
+    text: T,
+    parent: widget::Id,
+    align: (Align, Align),
+    scale: f64,
+) where
+    T: IntoRuns,
+{
+    if let Some(area) = ui.kid_area_of(parent) {
+        draw_text(ui, ids, resources, text, TextStyle::new(area, align, scale));
+    }
+}
+
+/// Draw a styled label centered on a button, tinted while hovered like vanilla
+pub fn button_label<T>(
+    ui: &mut UiCell,
+    ids: &mut List,
+    resources: &mut ResourceManager,
+    text: T,
+    button: widget::Id,
+) where
+    T: IntoRuns,
+{
+    let rect = match ui.rect_of(button) {
+        Some(rect) => rect,
+        None => return,
+    };
+
+    let color = if ui.widget_input(button).mouse().is_some() {
+        HOVER_COLOR
+    } else {
+        [1.0; 4]
+    };
+
+    let scale = rect.h() / BUTTON_HEIGHT;
+
+    let style = TextStyle::new(rect, (Align::Middle, Align::Middle), scale).color(color);
+
+    draw_text(ui, ids, resources, text, style);
+}
+
+/// Where and how text is drawn inside a rectangle
+#[derive(Clone, Copy, Debug)]
+struct TextStyle {
+    rect: Rect,
+    align: (Align, Align),
+    scale: f64,
+    color: [f32; 4],
+}
+
+impl TextStyle {
+    /// White text aligned inside a rectangle
+    fn new(rect: Rect, align: (Align, Align), scale: f64) -> TextStyle {
+        TextStyle {
+            rect,
+            align,
+            scale,
+            color: [1.0; 4],
+        }
+    }
+
+    /// Tint text with a color
+    fn color(mut self, color: [f32; 4]) -> TextStyle {
+        self.color = color;
+        self
+    }
+}
+
+/// Lay out and draw styled text inside a rectangle
+fn draw_text<T>(
+    ui: &mut UiCell,
+    ids: &mut List,
+    resources: &mut ResourceManager,
+    text: T,
+    style: TextStyle,
+) where
+    T: IntoRuns,
+{
+    let TextStyle {
+        rect,
+        align,
+        scale,
+        color,
+    } = style;
+
+    let runs = text.into_runs(resources.language(), resources.controls());
+
+    // Use conrod's default font until Minecraft fonts are available
+    if !resources.fonts().loaded() {
+        let plain: String = runs.into_iter().map(|run| run.text).collect();
+        let height = 8.0 * scale * plain.lines().count().max(1) as f64;
+
+        if ids.len() < 1 {
+            ids.resize(1, &mut ui.widget_id_generator());
+        }
+
+        let y = match align.1 {
+            Align::Start => rect.bottom() + height / 2.0,
+            Align::Middle => rect.y(),
+            Align::End => rect.top() - height / 2.0,
+        };
+
+        let [r, g, b, a] = color;
+
+        let label = widget::Text::new(&plain)
+            .font_size((8.0 * scale) as u32)
+            .color(color::rgba(r, g, b, a))
+            .w(rect.w())
+            .x_y(rect.x(), y);
+
+        let label = match align.0 {
+            Align::Start => label.left_justify(),
+            Align::Middle => label.center_justify(),
+            Align::End => label.right_justify(),
+        };
+
+        label.set(ids[0], ui);
+        return;
+    }
+
+    let (fonts, textures) = resources.fonts_with_textures();
+    let mesh = TextMesh::new(fonts, &runs);
+
+    let [width, height] = mesh.size();
+    let width = f64::from(width) * scale;
+    let height = f64::from(height) * scale;
+
+    let left = match align.0 {
+        Align::Start => rect.left(),
+        Align::Middle => rect.x() - width / 2.0,
+        Align::End => rect.right() - width,
+    };
+
+    let top = match align.1 {
+        Align::Start => rect.bottom() + height,
+        Align::Middle => rect.y() + height / 2.0,
+        Align::End => rect.top(),
+    };
+
+    ui_mesh(ui, ids, textures, &mesh, [left, top], scale, color);
+}
@@ -0,0 +1,360 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use cgmath::ortho;
+
+use core::resource_manager::resource::Resource;
+use core::resource_manager::resource_type::ResourceType;
+use core::resource_manager::texture_manager::TextureManager;
+
+use gfx::device::{DrawCall, DrawMode, Geometry, RenderDevice, Uniform};
+use gfx::shapes::SpriteVertex;
+
+use conrod::color::{self, Rgba};
+use conrod::position::rect::Rect;
+use conrod::render::PrimitiveKind;
+use conrod::text::GlyphCache;
+use conrod::Ui;
+
+use std::u16;
+
+/// Width and height of the image conrod text glyphs are cached on
+pub const GLYPH_CACHE_SIZE: u32 = 512;
+
+/// How much a glyph may change before it is drawn on the cache again
+const SCALE_TOLERANCE: f32 = 0.1;
+const POSITION_TOLERANCE: f32 = 0.1;
+
+/// Texture holding glyphs of conrod fonts
+pub fn glyph_texture() -> Resource { Resource::litecraft("ui_glyphs", ResourceType::Texture) }
+
+/// Texture with a single white pixel, tinted to draw solid shapes
+pub fn white_texture() -> Resource { Resource::litecraft("white", ResourceType::Texture) }
+
+/// Conrod colors are sRGB, like the ones given to its own renderer
+fn linear(color: [f32; 4]) -> [f32; 4] {
+    let component = |value: f32| {
+        if value <= 0.04045 {
+            value / 12.92
+        } else {
+            ((value + 0.055) / 1.055).powf(2.4)
+        }
+    };
+
+    [
+        component(color[0]),
+        component(color[1]),
+        component(color[2]),
+        color[3],
+    ]
+}
+
+/// Converts user interface units, centered with y up, to screen pixels from the top left corner
+#[derive(Clone, Copy)]
+struct Screen {
+    half_width: f64,
+    half_height: f64,
+    dpi: f64,
+}
+
+impl Screen {
+    fn point(&self, point: [f64; 2]) -> [f32; 2] {
+        [
+            ((point[0] + self.half_width) * self.dpi) as f32,
+            ((self.half_height - point[1]) * self.dpi) as f32,
+        ]
+    }
+
+    /// Get a rectangle as left, bottom, width and height in pixels from the bottom left corner
+    fn scissor(&self, rect: Rect) -> [u32; 4] {
+        let pixels = |value: f64| (value * self.dpi).round().max(0.0) as u32;
+
+        [
+            pixels(rect.left() + self.half_width),
+            pixels(rect.bottom() + self.half_height),
+            pixels(rect.w()),
+            pixels(rect.h()),
+        ]
+    }
+}
+
+/// Draws conrod user interface through a render device.
+///
+/// Primitives are drawn in order, consecutive ones sharing texture and clipping rectangle are drawn
+/// with a single draw call. Text of conrod fonts is drawn from a glyph cache uploaded as `glyph_texture`.
+pub struct UiRenderer {
+    glyph_cache: GlyphCache<'static>,
+    glyphs: Vec<u8>,
+
+    // Batch being built
+    texture: Option<Resource>,
+    scissor: Option<[u32; 4]>,
+    vertices: Vec<SpriteVertex>,
+    indices: Vec<u16>,
+}
+
+impl UiRenderer {
+    pub fn new() -> UiRenderer {
+        let glyph_cache = GlyphCache::builder()
+            .dimensions(GLYPH_CACHE_SIZE, GLYPH_CACHE_SIZE)
+            .scale_tolerance(SCALE_TOLERANCE)
+            .position_tolerance(POSITION_TOLERANCE)
+            .build();
+
+        UiRenderer {
+            glyph_cache,
+            glyphs: vec![0; (GLYPH_CACHE_SIZE * GLYPH_CACHE_SIZE * 4) as usize],
+            texture: None,
+            scissor: None,
+            vertices: Vec::new(),
+            indices: Vec::new(),
+        }
+    }
+
+    /// Cache glyphs of every text on a screen some pixels wide.
+    /// Returns the new RGBA glyph image if it changed, it should be uploaded as `glyph_texture`
+    pub fn cache_glyphs(&mut self, ui: &Ui, width: u32) -> Option<Vec<u8>> {
+        let dpi = (f64::from(width) / ui.win_w) as f32;
+        let mut primitives = ui.draw();
+        let mut queued = false;
+
+        while let Some(primitive) = primitives.next() {
+            if let PrimitiveKind::Text { text, font_id, .. } = primitive.kind {
+                for glyph in text.positioned_glyphs(dpi) {
+                    self.glyph_cache.queue_glyph(font_id.index(), glyph.clone());
+                    queued = true;
+                }
+            }
+        }
+
+        if !queued {
+            return None;
+        }
+
+        let mut changed = false;
+
+        {
+            let glyphs = &mut self.glyphs;
+            let changed = &mut changed;
+
+            let result = self.glyph_cache.cache_queued(|rect, data| {
+                let rect_width = (rect.max.x - rect.min.x) as usize;
+
+                for (i, &coverage) in data.iter().enumerate() {
+                    let x = rect.min.x as usize + i % rect_width;
+                    let y = rect.min.y as usize + i / rect_width;
+                    let pixel = (y * GLYPH_CACHE_SIZE as usize + x) * 4;
+
+                    glyphs[pixel..pixel + 4].copy_from_slice(&[255, 255, 255, coverage]);
+                }
+
+                *changed = true;
+            });
+
+            if let Err(why) = result {
+                warn!("Failed to cache user interface glyphs: {:?}", why);
+            }
+        }
+
+        if changed {
+            Some(self.glyphs.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Draw user interface over everything on a screen of some size in pixels.
+    /// Images and glyphs whose texture is not loaded yet are skipped
+    pub fn draw(
+        &mut self,
+        device: &mut RenderDevice,
+        ui: &Ui,
+        textures: &TextureManager,
+        width: u32,
+        height: u32,
+    ) {
+        let screen = Screen {
+            half_width: ui.win_w / 2.0,
+            half_height: ui.win_h / 2.0,
+            dpi: f64::from(width) / ui.win_w,
+        };
+
+        let persp_matrix: [[f32; 4]; 4] = ortho(0.0, width as f32, height as f32, 0.0, -1.0, 1.0).into();
+        let uniforms = [("persp_matrix", Uniform::Mat4(persp_matrix))];
+
+        let white = white_texture();
+        let mut primitives = ui.draw();
+
+        while let Some(primitive) = primitives.next() {
+            let scissor = screen.scissor(primitive.scizzor);
+
+            match primitive.kind {
+                PrimitiveKind::Rectangle { color } => {
+                    let [left, top] = screen.point([primitive.rect.left(), primitive.rect.top()]);
+                    let [right, bottom] =
+                        screen.point([primitive.rect.right(), primitive.rect.bottom()]);
+
+                    self.batch(device, &uniforms, &white, scissor, 4);
+                    self.quad([left, top, right, bottom], [0.5; 4], linear(color.to_fsa()));
+                },
+                PrimitiveKind::TrianglesSingleColor { color, triangles } => {
+                    let Rgba(r, g, b, a) = color;
+                    let color = linear([r, g, b, a]);
+
+                    for triangle in triangles {
+                        self.batch(device, &uniforms, &white, scissor, 3);
+
+                        for &point in &triangle.0 {
+                            self.vertex(SpriteVertex::new(screen.point(point), [0.5; 2], color));
+                        }
+                    }
+                },
+                PrimitiveKind::TrianglesMultiColor { triangles } => {
+                    for triangle in triangles {
+                        self.batch(device, &uniforms, &white, scissor, 3);
+
+                        for &(point, Rgba(r, g, b, a)) in &triangle.0 {
+                            let color = linear([r, g, b, a]);
+                            self.vertex(SpriteVertex::new(screen.point(point), [0.5; 2], color));
+                        }
+                    }
+                },
+                PrimitiveKind::Image {
+                    image_id,
+                    color,
+                    source_rect,
+                } => {
+                    let texture = match textures.ui_resource(image_id) {
+                        Some(texture) => texture.clone(),
+                        None => continue,
+                    };
+
+                    let (image_width, image_height) = match textures.get_ui(&texture) {
+                        Some((_, size)) => size,
+                        None => continue,
+                    };
+
+                    // Source rectangles are in pixels from the bottom left corner, like texture coordinates
+                    let source = match source_rect {
+                        Some(source) => [
+                            (source.left() / image_width) as f32,
+                            (source.top() / image_height) as f32,
+                            (source.right() / image_width) as f32,
+                            (source.bottom() / image_height) as f32,
+                        ],
+                        None => [0.0, 1.0, 1.0, 0.0],
+                    };
+
+                    let [left, top] = screen.point([primitive.rect.left(), primitive.rect.top()]);
+                    let [right, bottom] =
+                        screen.point([primitive.rect.right(), primitive.rect.bottom()]);
+                    let color = linear(color.unwrap_or(color::WHITE).to_fsa());
+
+                    self.batch(device, &uniforms, &texture, scissor, 4);
+                    self.quad([left, top, right, bottom], source, color);
+                },
+                PrimitiveKind::Text { color, text, font_id } => {
+                    let glyphs = glyph_texture();
+                    let color = linear(color.to_fsa());
+
+                    for glyph in text.positioned_glyphs(screen.dpi as f32) {
+                        // Glyph rows are uploaded as they are, so cache coordinates are texture coordinates
+                        if let Ok(Some((source, rect))) =
+                            self.glyph_cache.rect_for(font_id.index(), glyph)
+                        {
+                            let rect = [
+                                rect.min.x as f32,
+                                rect.min.y as f32,
+                                rect.max.x as f32,
+                                rect.max.y as f32,
+                            ];
+                            let source = [source.min.x, source.min.y, source.max.x, source.max.y];
+
+                            self.batch(device, &uniforms, &glyphs, scissor, 4);
+                            self.quad(rect, source, color);
+                        }
+                    }
+                },
+                PrimitiveKind::Other(_) => (),
+            }
+        }
+
+        self.flush(device, &uniforms);
+    }
+
+    /// Draw current batch if next vertices can't join it
+    fn batch(
+        &mut self,
+        device: &mut RenderDevice,
+        uniforms: &[(&'static str, Uniform)],
+        texture: &Resource,
+        scissor: [u32; 4],
+        vertices: usize,
+    ) {
+        let full = self.vertices.len() + vertices > u16::MAX as usize + 1;
+
+        if self.texture.as_ref() != Some(texture) || self.scissor != Some(scissor) || full {
+            self.flush(device, uniforms);
+            self.texture = Some(texture.clone());
+            self.scissor = Some(scissor);
+        }
+    }
+
+    fn vertex(&mut self, vertex: SpriteVertex) {
+        self.indices.push(self.vertices.len() as u16);
+        self.vertices.push(vertex);
+    }
+
+    /// Add a quad from its left, top, right and bottom edges in pixels
+    fn quad(&mut self, rect: [f32; 4], source: [f32; 4], color: [f32; 4]) {
+        let [left, top, right, bottom] = rect;
+        let [u0, v0, u1, v1] = source;
+        let base = self.vertices.len() as u16;
+
+        self.vertices.extend_from_slice(&[
+            SpriteVertex::new([left, top], [u0, v0], color),
+            SpriteVertex::new([right, top], [u1, v0], color),
+            SpriteVertex::new([right, bottom], [u1, v1], color),
+            SpriteVertex::new([left, bottom], [u0, v1], color),
+        ]);
+        self.indices
+            .extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+    }
+
+    /// Draw current batch, skipped if its texture is not loaded yet
+    fn flush(&mut self, device: &mut RenderDevice, uniforms: &[(&'static str, Uniform)]) {
+        if let Some(texture) = self.texture.take() {
+            if !self.indices.is_empty() && device.has_texture(&texture) {
+                let call = DrawCall {
+                    program: "sprite",
+                    geometry: Geometry::Sprites(&self.vertices, &self.indices),
+                    uniforms,
+                    texture: Some(&texture),
+                    linear: false,
+                    mode: DrawMode::Overlay,
+                    scissor: self.scissor,
+                };
+
+                device
+                    .draw(&call)
+                    .expect("Failed to draw user interface to screen");
+            }
+        }
+
+        self.scissor = None;
+        self.vertices.clear();
+        self.indices.clear();
+    }
+}
@@ -25,13 +25,17 @@ extern crate glium;
 extern crate image;
 #[macro_use]
 extern crate lazy_static;
+extern crate lewton;
 #[macro_use]
 extern crate log;
 extern crate serde;
 #[macro_use]
 extern crate serde_derive;
 #[macro_use]
+extern crate serde_json;
+#[macro_use]
 extern crate conrod;
+extern crate rand;
 extern crate rhai;
 extern crate serde_yaml;
 extern crate simple_logger;
@@ -45,12 +49,26 @@ use core::constants::*;
 mod core;
 mod gfx;
 mod scenes;
+mod sound;
 mod tests;
 
 fn main() {
+    use core::arguments::{Arguments, USAGE};
+    use std::{env, process};
+
+    let mut arguments = Arguments::parse(env::args().skip(1)).unwrap_or_else(|why| {
+        eprintln!("{}\n\n{}", why, USAGE);
+        process::exit(2);
+    });
+
+    if arguments.help {
+        println!("{}", USAGE);
+        return;
+    }
+
     println!("{}", ASCII_ART);
 
-    simple_logger::init().expect("Failed to initialize logger!");
+    simple_logger::init_with_level(arguments.log_level).expect("Failed to initialize logger!");
 
     info!(
         "Starting Litecraft {} for Minecraft {}...",
@@ -59,6 +77,17 @@ fn main() {
 
     use std::path::Path;
 
+    // Every game path is relative to game folder, but configuration path is relative to where we started
+    if let Some(ref game_dir) = arguments.game_dir {
+        if let Ok(current) = env::current_dir() {
+            arguments.config = current.join(&arguments.config);
+            arguments.import_options = arguments.import_options.map(|path| current.join(path));
+        }
+
+        env::set_current_dir(game_dir)
+            .unwrap_or_else(|why| panic!("Can't use game folder {}: {}", game_dir.display(), why));
+    }
+
     if !Path::new("resources").exists() {
         panic!(
             "Resources path doesn't exist, please check that you have all required resources. Check \
@@ -68,5 +97,5 @@ fn main() {
 
     use gfx::canvas::Canvas;
 
-    Canvas::start();
+    Canvas::start(arguments);
 }
@@ -18,12 +18,30 @@ use core::resource_manager::resource::Resource;
 use core::resource_manager::resource_type::ResourceType;
 
 use gfx::canvas::Canvas;
+use gfx::device::RenderDevice;
 use gfx::pencil::Pencil;
 use gfx::scene::{Scene, SceneAction};
+use gfx::transition::Transition;
 
 use scenes::main_menu::MainMenu;
 
-use glium::{Frame, Surface};
+use std::time::Duration;
+
+/// Shader programs loaded before anything is drawn
+const SHADERS: &[&str] = &[
+    "noise",
+    "quad",
+    "wallpaper",
+    "logo",
+    "text",
+    "sprite",
+    "outline",
+    "fade",
+    "slide",
+];
+
+/// Main menu fades in for this many milliseconds once loaded
+const MENU_FADE: u64 = 750;
 
 /// Show Litecraft logo and start resource loading
 pub struct LoadingScene {
@@ -37,17 +55,17 @@ impl LoadingScene {
         }
     }
 
-    pub fn draw_logo(&mut self, canvas: &mut Canvas, frame: &mut Frame) {
-        let logo = canvas
-            .resources()
-            .textures()
-            .get(&Resource::litecraft("logo", ResourceType::Texture));
+    /// Draw noise background and Litecraft logo once it is loaded
+    pub fn draw_background(&mut self, device: &mut RenderDevice) {
+        Pencil::new(device, "noise").draw();
+
+        let logo = Resource::litecraft("logo", ResourceType::Texture);
 
         // Check if logo is now loaded
-        if let Some(logo) = logo {
-            Pencil::new(frame, "logo", &canvas)
+        if device.has_texture(&logo) {
+            Pencil::new(device, "logo")
                 .camera(&self.camera)
-                .texture(logo)
+                .texture(&logo)
                 .linear(true)
                 .draw();
         }
@@ -62,32 +80,33 @@ impl Scene for LoadingScene {
             .textures_mut()
             .load(Resource::litecraft("logo", ResourceType::Texture));
 
-        let display = canvas.display().clone();
+        // Load shaders, headless canvas has no display to compile them for
+        if let Some(display) = canvas.try_display().cloned() {
+            for &name in SHADERS {
+                canvas
+                    .resources_mut()
+                    .shaders_mut()
+                    .load(name, &display)
+                    .expect("Failed to load required shader program");
+            }
+        }
 
-        // Load shaders
-        canvas
-            .resources_mut()
-            .shaders_mut()
-            .load("noise", &display)
-            .expect("Failed to load required shader program");
+        // Load translations
+        let language = canvas.settings().language().to_owned();
 
-        canvas
-            .resources_mut()
-            .shaders_mut()
-            .load("quad", &display)
-            .expect("Failed to load required shader program");
+        if let Err(error) = canvas.resources_mut().set_language(&language) {
+            warn!("Failed to load language '{}': {}", language, error);
+        }
 
-        canvas
-            .resources_mut()
-            .shaders_mut()
-            .load("wallpaper", &display)
-            .expect("Failed to load required shader program");
+        // Load Minecraft fonts
+        if let Err(error) = canvas.resources_mut().fonts_mut().load() {
+            warn!("Failed to load Minecraft fonts: {}", error);
+        }
 
-        canvas
-            .resources_mut()
-            .shaders_mut()
-            .load("logo", &display)
-            .expect("Failed to load required shader program");
+        // Load sound events
+        if let Err(error) = canvas.sounds_mut().load() {
+            warn!("Failed to load sound events: {}", error);
+        }
 
         // Load wallpapers from 1 to 12
         for i in 0..6 {
@@ -102,25 +121,47 @@ impl Scene for LoadingScene {
         }
     }
 
-    /// Draw scene
-    fn draw(&mut self, canvas: &mut Canvas, frame: &mut Frame) -> SceneAction {
-        // Update camera aspect ratio
-        self.camera
-            .aspect_ratio(canvas.settings().width(), canvas.settings().height());
-
-        // Clear to black
-        frame.clear_color_and_depth((0.0, 0.0, 0.0, 1.0), 1.0);
+    fn camera(&self) -> Option<&Camera> { Some(&self.camera) }
+
+    /// Draw background and logo, target is already cleared
+    fn draw_offscreen(
+        &mut self,
+        _canvas: &mut Canvas,
+        device: &mut RenderDevice,
+        _partial_tick: f32,
+    ) -> bool {
+        self.draw_background(device);
+        true
+    }
 
-        // Draw background
-        Pencil::new(frame, "noise", &canvas).draw();
+    /// Draw scene over black screen
+    fn draw(
+        &mut self,
+        canvas: &mut Canvas,
+        device: &mut RenderDevice,
+        _partial_tick: f32,
+    ) -> SceneAction {
+        // Update camera aspect ratio and render distance
+        self.camera.apply_settings(canvas.settings());
 
-        // Draw litecraft logo
-        self.draw_logo(canvas, frame);
+        // Draw background and litecraft logo
+        self.draw_background(device);
 
         if canvas.resources().loaded() {
             info!("All resources are now loaded, opening main menu");
 
-            SceneAction::ChangeScene(box MainMenu::new(canvas))
+            // TODO: Join server once multiplayer exists
+            if let Some(ref server) = canvas.arguments().server {
+                warn!(
+                    "Multiplayer is not available yet, not connecting to {}:{} as {}",
+                    server.host,
+                    server.port,
+                    canvas.arguments().username
+                );
+            }
+
+            SceneAction::Replace(box MainMenu::new(canvas))
+                .with_transition(Transition::fade(Duration::from_millis(MENU_FADE)))
         } else {
             SceneAction::None
         }
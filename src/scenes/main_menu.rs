@@ -14,10 +14,15 @@
 // IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 use gfx::canvas::Canvas;
+use gfx::device::RenderDevice;
 use gfx::pencil::Pencil;
 use gfx::scene::{Scene, SceneAction};
 use gfx::ui_helper;
 
+use scenes::options::OptionsMenu;
+
+use sound::music::MusicType;
+
 use core::camera::Camera;
 use core::constants::*;
 
@@ -25,8 +30,6 @@ use core::resource_manager::resource::Resource;
 use core::resource_manager::resource_type::ResourceType;
 use core::resource_manager::ResourceManager;
 
-use glium::Frame;
-
 use conrod::position::rect::Rect;
 
 /// How many time we should wait before changing our wallpaper
@@ -57,10 +60,16 @@ widget_ids! {
         options,
         quit,
 
+        singleplayer_label[],
+        multiplayer_label[],
+        realms_label[],
+        options_label[],
+        quit_label[],
+
         footer,
 
-        version,
-        copyright,
+        version[],
+        copyright[],
     }
 }
 
@@ -79,24 +88,24 @@ impl MainMenu {
 
         MainMenu { ids, camera }
     }
+}
 
-    /// Main menu's background
-    fn draw_wallpaper(&mut self, canvas: &mut Canvas, frame: &mut Frame) {
-        let i = ResourceManager::time() as u32 / WALLPAPER_DELAY % 5;
-
-        let wallpaper = canvas.resources().textures().get(&Resource::minecraft_path(
-            format!("panorama_{}", i),
-            "gui/title/background",
-            ResourceType::Texture,
-        ));
-
-        if let Some(wallpaper) = wallpaper {
-            Pencil::new(frame, "wallpaper", &canvas)
-                .texture(wallpaper)
-                .camera(&self.camera)
-                .linear(true)
-                .draw();
-        }
+/// Main menu's background, also used by menus opened from it
+pub fn draw_wallpaper(device: &mut RenderDevice, camera: &Camera) {
+    let i = ResourceManager::time() as u32 / WALLPAPER_DELAY % 5;
+
+    let wallpaper = Resource::minecraft_path(
+        format!("panorama_{}", i),
+        "gui/title/background",
+        ResourceType::Texture,
+    );
+
+    if device.has_texture(&wallpaper) {
+        Pencil::new(device, "wallpaper")
+            .texture(&wallpaper)
+            .camera(camera)
+            .linear(true)
+            .draw();
     }
 }
 
@@ -116,11 +125,33 @@ impl Scene for MainMenu {
             .resources_mut()
             .textures_mut()
             .load_ui(Resource::minecraft_path("widgets", "gui", ResourceType::Texture));
+
+        canvas.sounds_mut().set_music(Some(MusicType::Menu));
+    }
+
+    fn camera(&self) -> Option<&Camera> { Some(&self.camera) }
+
+    /// Draw only the wallpaper, without menu
+    fn draw_offscreen(
+        &mut self,
+        canvas: &mut Canvas,
+        device: &mut RenderDevice,
+        _partial_tick: f32,
+    ) -> bool {
+        self.camera.apply_settings(canvas.settings());
+        draw_wallpaper(device, &self.camera);
+        true
     }
 
     /// Draw scene
-    fn draw(&mut self, canvas: &mut Canvas, frame: &mut Frame) -> SceneAction {
-        use conrod::{color, widget, Colorable, Labelable, Positionable, Sizeable, Widget};
+    fn draw(
+        &mut self,
+        canvas: &mut Canvas,
+        device: &mut RenderDevice,
+        _partial_tick: f32,
+    ) -> SceneAction {
+        use conrod::position::Align;
+        use conrod::{widget, Positionable, Sizeable, Widget};
 
         let logo = canvas.resources().textures().get_ui(&Resource::minecraft_path(
             "minecraft",
@@ -134,10 +165,13 @@ impl Scene for MainMenu {
             ResourceType::Texture,
         ));
 
-        self.draw_wallpaper(canvas, frame);
+        self.camera.apply_settings(canvas.settings());
+        draw_wallpaper(device, &self.camera);
 
         let scale = canvas.settings().scale();
-        let mut ui = canvas.ui_mut().set_widgets();
+
+        let (ui, resources) = canvas.ui_with_resources();
+        let mut ui = ui.set_widgets();
 
         // Construct our main `Canvas` tree.
         widget::Canvas::new()
@@ -187,17 +221,14 @@ impl Scene for MainMenu {
 
         if let Some(widgets) = widgets {
             ui_helper::button(&widgets, scale)
-                .label("Singleplayer")
                 .up_from(self.ids.multiplayer, 15.0 * scale)
                 .set(self.ids.singleplayer, &mut ui);
 
             ui_helper::button(&widgets, scale)
-                .label("Multiplayer")
                 .middle_of(self.ids.body)
                 .set(self.ids.multiplayer, &mut ui);
 
             ui_helper::button(&widgets, scale)
-                .label("Minecraft Realms")
                 .down_from(self.ids.multiplayer, 15.0 * scale)
                 .set(self.ids.realms, &mut ui);
 
@@ -210,36 +241,74 @@ impl Scene for MainMenu {
                 .down_from(self.ids.realms, 50.0 * scale)
                 .set(self.ids.body_footer, &mut ui);
 
-            ui_helper::button(&widgets, scale)
-                .label("Options")
+            let options = ui_helper::button(&widgets, scale)
                 .top_left_of(self.ids.body_footer_left)
                 .padded_w_of(self.ids.body_footer_left, 5.0)
-                .set(self.ids.options, &mut ui);
+                .set(self.ids.options, &mut ui)
+                .was_clicked();
 
-            if ui_helper::button(&widgets, scale)
-                .label("Quit Game")
+            let quit = ui_helper::button(&widgets, scale)
                 .top_right_of(self.ids.body_footer_right)
                 .padded_w_of(self.ids.body_footer_right, 5.0)
                 .set(self.ids.quit, &mut ui)
-                .was_clicked()
-            {
+                .was_clicked();
+
+            let ids = &mut self.ids;
+
+            ui_helper::button_label(
+                &mut ui,
+                &mut ids.singleplayer_label,
+                resources,
+                "Singleplayer",
+                ids.singleplayer,
+            );
+            ui_helper::button_label(
+                &mut ui,
+                &mut ids.multiplayer_label,
+                resources,
+                "Multiplayer",
+                ids.multiplayer,
+            );
+            ui_helper::button_label(
+                &mut ui,
+                &mut ids.realms_label,
+                resources,
+                "Minecraft Realms",
+                ids.realms,
+            );
+            ui_helper::button_label(&mut ui, &mut ids.options_label, resources, "Options", ids.options);
+            ui_helper::button_label(&mut ui, &mut ids.quit_label, resources, "Quit Game", ids.quit);
+
+            if options {
+                return SceneAction::Push(box OptionsMenu::new());
+            }
+
+            if quit {
                 return SceneAction::Quit;
             }
         }
 
         // Litecraft and Minecraft version
-        widget::Text::new(VERSION_TEXT)
-            .color(color::WHITE)
-            .font_size(16)
-            .bottom_left_of(self.ids.footer)
-            .set(self.ids.version, &mut ui);
+        ui_helper::text(
+            &mut ui,
+            &mut self.ids.version,
+            resources,
+            VERSION_TEXT,
+            self.ids.footer,
+            (Align::Start, Align::Start),
+            2.0 * scale,
+        );
 
         // Credits
-        widget::Text::new("© Litecraft Team")
-            .color(color::WHITE)
-            .font_size(16)
-            .bottom_right_of(self.ids.footer)
-            .set(self.ids.copyright, &mut ui);
+        ui_helper::text(
+            &mut ui,
+            &mut self.ids.copyright,
+            resources,
+            "© Litecraft Team",
+            self.ids.footer,
+            (Align::End, Align::Start),
+            2.0 * scale,
+        );
 
         SceneAction::None
     }
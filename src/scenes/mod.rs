@@ -15,3 +15,4 @@
 
 pub mod loading;
 pub mod main_menu;
+pub mod options;
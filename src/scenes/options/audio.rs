@@ -0,0 +1,92 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::camera::Camera;
+
+use gfx::canvas::Canvas;
+use gfx::device::RenderDevice;
+use gfx::scene::{Scene, SceneAction};
+
+use scenes::options::{caption, translate, Entry, OptionsScreen};
+
+use sound::SoundCategory;
+
+/// Volume of every sound category
+pub struct AudioOptions {
+    screen: OptionsScreen,
+}
+
+impl AudioOptions {
+    pub fn new() -> AudioOptions {
+        AudioOptions {
+            screen: OptionsScreen::new(),
+        }
+    }
+}
+
+impl Scene for AudioOptions {
+    fn load(&mut self, _canvas: &mut Canvas) {}
+
+    fn camera(&self) -> Option<&Camera> { Some(self.screen.camera()) }
+
+    fn draw(
+        &mut self,
+        canvas: &mut Canvas,
+        device: &mut RenderDevice,
+        _partial_tick: f32,
+    ) -> SceneAction {
+        let title = translate(canvas, "options.sounds.title", "Music & Sound Options");
+        let categories = SoundCategory::all();
+
+        let entries: Vec<Entry> = categories
+            .iter()
+            .map(|&category| {
+                let volume = canvas.settings().volume(category);
+
+                let value = if volume <= 0.0 {
+                    translate(canvas, "options.off", "OFF")
+                } else {
+                    format!("{:.0}%", volume * 100.0)
+                };
+
+                let key = format!("soundCategory.{}", category.name());
+
+                Entry::Slider(
+                    caption(canvas, &key, category.name(), &value),
+                    f64::from(volume),
+                    0.0,
+                    1.0,
+                )
+            })
+            .collect();
+
+        let interaction = self.screen.draw(canvas, device, &title, &entries);
+
+        if let Some((i, volume)) = interaction.changed {
+            let category = categories[i];
+            let volume = volume as f32;
+
+            canvas.settings_mut().set_volume(category, volume);
+            canvas.sounds_mut().set_volume(category, volume);
+        }
+
+        if interaction.done {
+            canvas.save_settings();
+            return SceneAction::Pop;
+        }
+
+        SceneAction::None
+    }
+}
@@ -0,0 +1,183 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use gfx::canvas::Canvas;
+use gfx::device::RenderDevice;
+use gfx::scene::{Scene, SceneAction};
+
+use scenes::options::{caption, toggle, translate, Entry, OptionsScreen};
+
+use core::camera::Camera;
+use core::controls::{Action, Binding, Input, Key};
+use core::input::InputEvent;
+
+/// Entries shown before key bindings
+const MOUSE_ENTRIES: usize = 2;
+
+/// Mouse settings and key bindings, an action is rebound by clicking it and pressing a key or mouse button
+pub struct ControlsOptions {
+    screen: OptionsScreen,
+    waiting: Option<Action>,
+    ignore_click: bool,
+}
+
+impl ControlsOptions {
+    pub fn new() -> ControlsOptions {
+        ControlsOptions {
+            screen: OptionsScreen::new(),
+            waiting: None,
+            ignore_click: false,
+        }
+    }
+
+    /// Bind action being edited to the last pressed input, escape leaves it unbound
+    fn rebind(&mut self, canvas: &mut Canvas) {
+        let action = match self.waiting {
+            Some(action) => action,
+            None => return,
+        };
+
+        let binding = match canvas.pressed() {
+            Some(binding) => binding,
+            None => return,
+        };
+
+        let binding = if binding == Binding::key(Key::Escape) {
+            Binding::unbound()
+        } else {
+            binding
+        };
+
+        // Releasing the mouse button we just bound would click the entry again
+        self.ignore_click = match binding.input {
+            Input::Mouse(_) => true,
+            _ => false,
+        };
+
+        canvas.settings_mut().controls_mut().set(action, binding);
+        self.waiting = None;
+    }
+}
+
+impl Scene for ControlsOptions {
+    fn load(&mut self, _canvas: &mut Canvas) {}
+
+    fn camera(&self) -> Option<&Camera> { Some(self.screen.camera()) }
+
+    /// Keys pressed while rebinding are only used for the new binding
+    fn input(&mut self, _canvas: &mut Canvas, event: &InputEvent) -> bool {
+        match event {
+            InputEvent::KeyDown { .. } | InputEvent::ButtonDown { .. } => self.waiting.is_some(),
+            _ => false,
+        }
+    }
+
+    fn draw(
+        &mut self,
+        canvas: &mut Canvas,
+        device: &mut RenderDevice,
+        _partial_tick: f32,
+    ) -> SceneAction {
+        self.rebind(canvas);
+
+        let title = translate(canvas, "controls.title", "Controls");
+        let actions = Action::all();
+
+        let sensitivity = canvas.settings().mouse_sensitivity();
+        let invert = canvas.settings().invert_mouse();
+
+        let mut entries = Vec::with_capacity(MOUSE_ENTRIES + actions.len() * 2 + 2);
+
+        entries.push(Entry::Slider(
+            caption(
+                canvas,
+                "options.sensitivity",
+                "Sensitivity",
+                &format!("{:.0}%", sensitivity * 200.0),
+            ),
+            f64::from(sensitivity),
+            0.0,
+            1.0,
+        ));
+        entries.push(Entry::Button(toggle(
+            canvas,
+            "options.invertMouse",
+            "Invert Mouse",
+            invert,
+        )));
+
+        for &action in actions {
+            let binding = canvas.settings().controls().get(action);
+
+            let value = if self.waiting == Some(action) {
+                format!("§f> §e{}§f <", binding)
+            } else if canvas.settings().controls().conflicting(action) {
+                format!("§c{}", binding)
+            } else {
+                binding.to_string()
+            };
+
+            entries.push(Entry::Label(translate(
+                canvas,
+                action.vanilla_name(),
+                action.name(),
+            )));
+            entries.push(Entry::Button(value));
+        }
+
+        entries.push(Entry::Label(String::new()));
+        entries.push(Entry::Button(translate(canvas, "controls.reset", "Reset Keys")));
+
+        let interaction = self.screen.draw(canvas, device, &title, &entries);
+
+        let released = canvas
+            .ui()
+            .global_input()
+            .current
+            .mouse
+            .buttons
+            .pressed()
+            .next()
+            .is_none();
+
+        if let Some((_, sensitivity)) = interaction.changed {
+            canvas.settings_mut().set_mouse_sensitivity(sensitivity as f32);
+        }
+
+        if let Some(i) = interaction.clicked {
+            if self.ignore_click {
+                // Click made by releasing the bound button
+            } else if i < MOUSE_ENTRIES {
+                canvas.settings_mut().set_invert_mouse(!invert);
+            } else if let Some(&action) = actions.get((i - MOUSE_ENTRIES) / 2) {
+                self.waiting = Some(action);
+            } else {
+                self.waiting = None;
+                canvas.settings_mut().controls_mut().reset();
+            }
+        }
+
+        if released {
+            self.ignore_click = false;
+        }
+
+        if interaction.done {
+            canvas.save_settings();
+            return SceneAction::Pop;
+        }
+
+        SceneAction::None
+    }
+}
@@ -0,0 +1,87 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use gfx::canvas::Canvas;
+use gfx::device::RenderDevice;
+use gfx::scene::{Scene, SceneAction};
+
+use scenes::options::{translate, Entry, OptionsScreen};
+
+use core::camera::Camera;
+use core::text::language::{Language, DEFAULT_LANGUAGE};
+
+/// Pick a language from those found on resources
+pub struct LanguageOptions {
+    screen: OptionsScreen,
+    languages: Vec<String>,
+}
+
+impl LanguageOptions {
+    pub fn new() -> LanguageOptions {
+        let languages = Language::available().unwrap_or_else(|why| {
+            warn!("Failed to list languages: {}", why);
+            vec![DEFAULT_LANGUAGE.to_owned()]
+        });
+
+        LanguageOptions {
+            screen: OptionsScreen::new(),
+            languages,
+        }
+    }
+}
+
+impl Scene for LanguageOptions {
+    fn load(&mut self, _canvas: &mut Canvas) {}
+
+    fn camera(&self) -> Option<&Camera> { Some(self.screen.camera()) }
+
+    fn draw(
+        &mut self,
+        canvas: &mut Canvas,
+        device: &mut RenderDevice,
+        _partial_tick: f32,
+    ) -> SceneAction {
+        let title = translate(canvas, "options.language", "Language...");
+        let current = canvas.settings().language().to_owned();
+
+        let entries: Vec<Entry> = self
+            .languages
+            .iter()
+            .map(|code| {
+                if *code == current {
+                    Entry::Button(format!("§e{}", code))
+                } else {
+                    Entry::Button(code.clone())
+                }
+            })
+            .collect();
+
+        let interaction = self.screen.draw(canvas, device, &title, &entries);
+
+        if let Some(code) = interaction.clicked.map(|i| self.languages[i].clone()) {
+            match canvas.resources_mut().set_language(&code) {
+                Ok(_) => canvas.settings_mut().set_language(&code),
+                Err(why) => warn!("Failed to load language '{}': {}", code, why),
+            }
+        }
+
+        if interaction.done {
+            canvas.save_settings();
+            return SceneAction::Pop;
+        }
+
+        SceneAction::None
+    }
+}
@@ -0,0 +1,378 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+pub mod audio;
+pub mod controls;
+pub mod language;
+pub mod video;
+
+use gfx::canvas::Canvas;
+use gfx::device::RenderDevice;
+use gfx::scene::{Scene, SceneAction};
+use gfx::ui_helper;
+
+use scenes::main_menu::draw_wallpaper;
+
+use self::audio::AudioOptions;
+use self::controls::ControlsOptions;
+use self::language::LanguageOptions;
+use self::video::VideoOptions;
+
+use sound::SoundCategory;
+
+use core::camera::Camera;
+use core::resource_manager::resource::Resource;
+use core::resource_manager::resource_type::ResourceType;
+use core::vanilla_options::{import_options_file, vanilla_options_path};
+
+use conrod::color;
+use conrod::position::Align;
+use conrod::widget::id::List;
+use conrod::{widget, Positionable, Sizeable, Widget};
+
+/// Width of a half sized button
+const ENTRY_WIDTH: f64 = 235.0;
+
+/// Space between two columns of entries
+const ENTRY_SPACING: f64 = 10.0;
+
+/// Vertical distance between rows of entries
+const ROW_HEIGHT: f64 = 54.0;
+
+widget_ids! {
+    struct Ids {
+        master,
+        header,
+        body,
+        footer,
+
+        title[],
+
+        done,
+        done_label[],
+    }
+}
+
+/// Control shown on an options screen, laid out in two columns like vanilla
+pub enum Entry {
+    /// Clickable button with a caption
+    Button(String),
+
+    /// Slider with a caption, its value, minimum and maximum
+    Slider(String, f64, f64, f64),
+
+    /// Plain text
+    Label(String),
+}
+
+/// What the user did on an options screen this frame
+#[derive(Default)]
+pub struct Interaction {
+    /// Index of clicked button
+    pub clicked: Option<usize>,
+
+    /// Index of moved slider and its new value
+    pub changed: Option<(usize, f64)>,
+
+    /// Done button was clicked
+    pub done: bool,
+}
+
+/// Background, title, entries and Done button shared by every options screen
+pub struct OptionsScreen {
+    ids: Option<Ids>,
+    entries: List,
+    labels: Vec<List>,
+    camera: Camera,
+}
+
+impl OptionsScreen {
+    pub fn new() -> OptionsScreen {
+        let mut camera = Camera::new();
+
+        camera.set_fov(55.0);
+
+        OptionsScreen {
+            ids: None,
+            entries: List::new(),
+            labels: Vec::new(),
+            camera,
+        }
+    }
+
+    /// Get camera looking at the wallpaper
+    pub fn camera(&self) -> &Camera { &self.camera }
+
+    /// Draw screen and report interactions, entries scroll if they don't fit
+    pub fn draw(
+        &mut self,
+        canvas: &mut Canvas,
+        device: &mut RenderDevice,
+        title: &str,
+        entries: &[Entry],
+    ) -> Interaction {
+        let widgets = canvas.resources().textures().get_ui(&Resource::minecraft_path(
+            "widgets",
+            "gui",
+            ResourceType::Texture,
+        ));
+
+        self.camera.apply_settings(canvas.settings());
+        draw_wallpaper(device, &self.camera);
+
+        let scale = canvas.settings().scale();
+        let done = translate(canvas, "gui.done", "Done");
+
+        let (ui, resources) = canvas.ui_with_resources();
+        let mut ui = ui.set_widgets();
+
+        let mut interaction = Interaction::default();
+
+        if self.ids.is_none() {
+            self.ids = Some(Ids::new(ui.widget_id_generator()));
+        }
+
+        if self.entries.len() < entries.len() {
+            self.entries.resize(entries.len(), &mut ui.widget_id_generator());
+        }
+
+        while self.labels.len() < entries.len() {
+            self.labels.push(List::new());
+        }
+
+        let ids = self.ids.as_mut().unwrap();
+
+        widget::Canvas::new()
+            .flow_down(&[
+                (ids.header, widget::Canvas::new().length(100.0 * scale)),
+                (ids.body, widget::Canvas::new().scroll_kids_vertically()),
+                (ids.footer, widget::Canvas::new().length(90.0 * scale)),
+            ])
+            .set(ids.master, &mut ui);
+
+        ui_helper::text(
+            &mut ui,
+            &mut ids.title,
+            resources,
+            title,
+            ids.header,
+            (Align::Middle, Align::Middle),
+            2.0 * scale,
+        );
+
+        let widgets = match widgets {
+            Some(widgets) => widgets,
+            None => return interaction,
+        };
+
+        // Two centered columns
+        let body_width = ui.w_of(ids.body).unwrap_or(0.0);
+        let first_column = body_width / 2.0 - (ENTRY_WIDTH + ENTRY_SPACING / 2.0) * scale;
+
+        for (i, entry) in entries.iter().enumerate() {
+            let id = self.entries[i];
+            let labels = &mut self.labels[i];
+
+            let left = first_column + (i % 2) as f64 * (ENTRY_WIDTH + ENTRY_SPACING) * scale;
+            let top = (ENTRY_SPACING + (i / 2) as f64 * ROW_HEIGHT) * scale;
+
+            match entry {
+                Entry::Button(caption) => {
+                    let clicked = ui_helper::button(&widgets, scale)
+                        .w(ENTRY_WIDTH * scale)
+                        .top_left_with_margins_on(ids.body, top, left)
+                        .set(id, &mut ui)
+                        .was_clicked();
+
+                    if clicked {
+                        interaction.clicked = Some(i);
+                    }
+
+                    ui_helper::button_label(&mut ui, labels, resources, caption.as_str(), id);
+                },
+
+                Entry::Slider(caption, value, min, max) => {
+                    let changed = ui_helper::slider(*value, *min, *max, scale)
+                        .w(ENTRY_WIDTH * scale)
+                        .top_left_with_margins_on(ids.body, top, left)
+                        .set(id, &mut ui);
+
+                    if let Some(value) = changed {
+                        interaction.changed = Some((i, value));
+                    }
+
+                    ui_helper::button_label(&mut ui, labels, resources, caption.as_str(), id);
+                },
+
+                Entry::Label(text) => {
+                    widget::Rectangle::fill_with(
+                        [ENTRY_WIDTH * scale, 45.0 * scale],
+                        color::TRANSPARENT,
+                    )
+                    .top_left_with_margins_on(ids.body, top, left)
+                    .set(id, &mut ui);
+
+                    ui_helper::text(
+                        &mut ui,
+                        labels,
+                        resources,
+                        text.as_str(),
+                        id,
+                        (Align::Start, Align::Middle),
+                        2.0 * scale,
+                    );
+                },
+            }
+        }
+
+        interaction.done = ui_helper::button(&widgets, scale)
+            .middle_of(ids.footer)
+            .set(ids.done, &mut ui)
+            .was_clicked();
+
+        ui_helper::button_label(&mut ui, &mut ids.done_label, resources, done.as_str(), ids.done);
+
+        interaction
+    }
+}
+
+/// Get a translation from current language, a fallback if missing
+pub fn translate(canvas: &Canvas, key: &str, fallback: &str) -> String {
+    canvas
+        .resources()
+        .language()
+        .translate_or(key, fallback)
+        .to_owned()
+}
+
+/// Caption for an option like `FOV: 70`
+pub fn caption(canvas: &Canvas, key: &str, fallback: &str, value: &str) -> String {
+    format!("{}: {}", translate(canvas, key, fallback), value)
+}
+
+/// Caption for an on and off option
+pub fn toggle(canvas: &Canvas, key: &str, fallback: &str, value: bool) -> String {
+    let value = if value {
+        translate(canvas, "options.on", "ON")
+    } else {
+        translate(canvas, "options.off", "OFF")
+    };
+
+    caption(canvas, key, fallback, &value)
+}
+
+/// Options screen opened from main menu, links to every other options screen
+pub struct OptionsMenu {
+    screen: OptionsScreen,
+    status: String,
+}
+
+impl OptionsMenu {
+    pub fn new() -> OptionsMenu {
+        OptionsMenu {
+            screen: OptionsScreen::new(),
+            status: String::new(),
+        }
+    }
+
+    /// Import vanilla `options.txt` from default Minecraft folder and apply what can change while running
+    fn import(&mut self, canvas: &mut Canvas) {
+        let path = match vanilla_options_path() {
+            Some(path) => path,
+            None => {
+                self.status = "§cMinecraft folder not found".to_owned();
+                return;
+            },
+        };
+
+        let report = match import_options_file(&path, canvas.settings_mut()) {
+            Ok(report) => report,
+            Err(why) => {
+                warn!(
+                    "Failed to import vanilla options from {}: {}",
+                    path.display(),
+                    why
+                );
+                self.status = "§cCan't read options.txt".to_owned();
+                return;
+            },
+        };
+
+        report.log();
+
+        self.status = format!(
+            "Imported {} options, {} skipped",
+            report.imported.len(),
+            report.unmapped.len()
+        );
+
+        for &category in SoundCategory::all() {
+            let volume = canvas.settings().volume(category);
+            canvas.sounds_mut().set_volume(category, volume);
+        }
+
+        let language = canvas.settings().language().to_owned();
+
+        if let Err(why) = canvas.resources_mut().set_language(&language) {
+            warn!("Failed to load language '{}': {}", language, why);
+        }
+
+        let fullscreen = canvas.settings().fullscreen();
+        canvas.set_fullscreen(fullscreen);
+    }
+}
+
+impl Scene for OptionsMenu {
+    fn load(&mut self, _canvas: &mut Canvas) {}
+
+    fn camera(&self) -> Option<&Camera> { Some(self.screen.camera()) }
+
+    fn draw(
+        &mut self,
+        canvas: &mut Canvas,
+        device: &mut RenderDevice,
+        _partial_tick: f32,
+    ) -> SceneAction {
+        let title = translate(canvas, "options.title", "Options");
+
+        let entries = [
+            Entry::Button(translate(canvas, "options.video", "Video Settings...")),
+            Entry::Button(translate(canvas, "options.sounds", "Music & Sounds...")),
+            Entry::Button(translate(canvas, "options.controls", "Controls...")),
+            Entry::Button(translate(canvas, "options.language", "Language...")),
+            Entry::Button("Import options.txt".to_owned()),
+            Entry::Label(self.status.clone()),
+        ];
+
+        let interaction = self.screen.draw(canvas, device, &title, &entries);
+
+        if interaction.done {
+            canvas.save_settings();
+            return SceneAction::Pop;
+        }
+
+        match interaction.clicked {
+            Some(0) => SceneAction::Push(box VideoOptions::new()),
+            Some(1) => SceneAction::Push(box AudioOptions::new()),
+            Some(2) => SceneAction::Push(box ControlsOptions::new()),
+            Some(3) => SceneAction::Push(box LanguageOptions::new()),
+            Some(4) => {
+                self.import(canvas);
+                SceneAction::None
+            },
+            _ => SceneAction::None,
+        }
+    }
+}
@@ -0,0 +1,160 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use gfx::canvas::Canvas;
+use gfx::device::RenderDevice;
+use gfx::scene::{Scene, SceneAction};
+
+use scenes::options::{caption, toggle, translate, Entry, OptionsScreen};
+
+use core::camera::Camera;
+use core::settings::{FOV_RANGE, GUI_SCALE_RANGE, MAX_MULTISAMPLING, RENDER_DISTANCE_RANGE};
+
+/// GUI scale slider snaps to this step
+const GUI_SCALE_STEP: f64 = 0.25;
+
+/// Window, rendering and GUI options
+pub struct VideoOptions {
+    screen: OptionsScreen,
+}
+
+impl VideoOptions {
+    pub fn new() -> VideoOptions {
+        VideoOptions {
+            screen: OptionsScreen::new(),
+        }
+    }
+
+    /// Next MSAA sample count: off, 2x, 4x... up to the maximum and back to off
+    fn next_multisampling(samples: u16) -> u16 {
+        match samples {
+            0 => 2,
+            samples if samples >= MAX_MULTISAMPLING => 0,
+            samples => (samples * 2).min(MAX_MULTISAMPLING),
+        }
+    }
+}
+
+impl Scene for VideoOptions {
+    fn load(&mut self, _canvas: &mut Canvas) {}
+
+    fn camera(&self) -> Option<&Camera> { Some(self.screen.camera()) }
+
+    fn draw(
+        &mut self,
+        canvas: &mut Canvas,
+        device: &mut RenderDevice,
+        _partial_tick: f32,
+    ) -> SceneAction {
+        let title = translate(canvas, "options.videoTitle", "Video Settings");
+
+        let (fov, distance, scale, fullscreen, vsync, samples, reversed_depth) = {
+            let settings = canvas.settings();
+
+            (
+                settings.fov(),
+                settings.render_distance(),
+                settings.scale(),
+                settings.fullscreen(),
+                settings.vsync(),
+                settings.multisampling(),
+                settings.reversed_depth(),
+            )
+        };
+
+        let fov_value = match fov {
+            70 => translate(canvas, "options.fov.min", "Normal"),
+            110 => translate(canvas, "options.fov.max", "Quake Pro"),
+            fov => fov.to_string(),
+        };
+
+        let samples_value = if samples == 0 {
+            translate(canvas, "options.off", "OFF")
+        } else {
+            format!("{}x", samples)
+        };
+
+        let entries = [
+            Entry::Slider(
+                caption(canvas, "options.fov", "FOV", &fov_value),
+                f64::from(fov),
+                f64::from(FOV_RANGE.0),
+                f64::from(FOV_RANGE.1),
+            ),
+            Entry::Slider(
+                caption(
+                    canvas,
+                    "options.renderDistance",
+                    "Render Distance",
+                    &format!("{} chunks", distance),
+                ),
+                f64::from(distance),
+                f64::from(RENDER_DISTANCE_RANGE.0),
+                f64::from(RENDER_DISTANCE_RANGE.1),
+            ),
+            Entry::Slider(
+                caption(canvas, "options.guiScale", "GUI Scale", &format!("{:.2}", scale)),
+                scale,
+                GUI_SCALE_RANGE.0,
+                GUI_SCALE_RANGE.1,
+            ),
+            Entry::Button(toggle(canvas, "options.fullscreen", "Fullscreen", fullscreen)),
+            Entry::Button(toggle(canvas, "options.vsync", "Use VSync", vsync)),
+            Entry::Button(caption(
+                canvas,
+                "options.multisampling",
+                "Multisampling",
+                &samples_value,
+            )),
+            Entry::Button(toggle(
+                canvas,
+                "options.reversedDepth",
+                "Reversed Depth",
+                reversed_depth,
+            )),
+            Entry::Label("§7VSync and multisampling apply on restart".to_owned()),
+        ];
+
+        let interaction = self.screen.draw(canvas, device, &title, &entries);
+
+        if let Some((i, value)) = interaction.changed {
+            let settings = canvas.settings_mut();
+
+            match i {
+                0 => settings.set_fov(value.round() as u8),
+                1 => settings.set_render_distance(value.round() as u8),
+                2 => settings.set_scale((value / GUI_SCALE_STEP).round() * GUI_SCALE_STEP),
+                _ => (),
+            }
+        }
+
+        match interaction.clicked {
+            Some(3) => canvas.set_fullscreen(!fullscreen),
+            Some(4) => canvas.settings_mut().set_vsync(!vsync),
+            Some(5) => canvas
+                .settings_mut()
+                .set_multisampling(VideoOptions::next_multisampling(samples)),
+            Some(6) => canvas.settings_mut().set_reversed_depth(!reversed_depth),
+            _ => (),
+        }
+
+        if interaction.done {
+            canvas.save_settings();
+            return SceneAction::Pop;
+        }
+
+        SceneAction::None
+    }
+}
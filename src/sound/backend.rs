@@ -0,0 +1,179 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::path::Path;
+
+type Result<T> = std::result::Result<T, Box<Error>>;
+
+/// Sample rate used by the mixer
+pub const OUTPUT_SAMPLE_RATE: u32 = 44_100;
+
+/// Output channels, always stereo
+pub const OUTPUT_CHANNELS: u16 = 2;
+
+/// Where mixed audio goes
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioOutput {
+    Null,
+    Wav,
+}
+
+impl Default for AudioOutput {
+    fn default() -> AudioOutput { AudioOutput::Null }
+}
+
+/// Audio device receiving mixed samples
+pub trait AudioBackend {
+    /// Get output sample rate
+    fn sample_rate(&self) -> u32;
+
+    /// Queue interleaved stereo samples, from -1.0 to 1.0
+    fn write(&mut self, samples: &[f32]) -> Result<()>;
+}
+
+/// Create an output backend, null if it can't be opened
+pub fn create(output: AudioOutput) -> Box<AudioBackend> {
+    use core::constants::SOUND_CAPTURE_FILE;
+
+    match output {
+        AudioOutput::Null => Box::new(NullBackend::new()),
+        AudioOutput::Wav => match WavBackend::new(SOUND_CAPTURE_FILE) {
+            Ok(backend) => Box::new(backend),
+            Err(why) => {
+                warn!("Can't create {}: {}. Sound is disabled", SOUND_CAPTURE_FILE, why);
+                Box::new(NullBackend::new())
+            },
+        },
+    }
+}
+
+/// Backend that drops every sample
+pub struct NullBackend {
+    frames: u64,
+}
+
+impl NullBackend {
+    pub fn new() -> NullBackend { NullBackend { frames: 0 } }
+
+    /// Get written stereo frames
+    pub fn frames(&self) -> u64 { self.frames }
+}
+
+impl AudioBackend for NullBackend {
+    fn sample_rate(&self) -> u32 { OUTPUT_SAMPLE_RATE }
+
+    fn write(&mut self, samples: &[f32]) -> Result<()> {
+        self.frames += samples.len() as u64 / u64::from(OUTPUT_CHANNELS);
+        Ok(())
+    }
+}
+
+/// Backend that records everything to a 16 bit PCM WAV file
+pub struct WavBackend {
+    file: BufWriter<File>,
+    frames: u32,
+}
+
+impl WavBackend {
+    /// Create WAV file and write its header
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<WavBackend> {
+        let mut backend = WavBackend {
+            file: BufWriter::new(File::create(path)?),
+            frames: 0,
+        };
+
+        backend.write_header()?;
+
+        Ok(backend)
+    }
+
+    /// Get written stereo frames
+    pub fn frames(&self) -> u32 { self.frames }
+
+    /// Update chunk sizes and flush to disk
+    pub fn finish(&mut self) -> Result<()> {
+        self.file.seek(SeekFrom::Start(0))?;
+        self.write_header()?;
+        self.file.seek(SeekFrom::End(0))?;
+        self.file.flush()?;
+
+        Ok(())
+    }
+
+    /// Write RIFF header using current length
+    fn write_header(&mut self) -> Result<()> {
+        let block_align = OUTPUT_CHANNELS * 2;
+        let data_size = self.frames * u32::from(block_align);
+
+        let file = &mut self.file;
+
+        file.write_all(b"RIFF")?;
+        write_u32(file, 36 + data_size)?;
+        file.write_all(b"WAVE")?;
+
+        file.write_all(b"fmt ")?;
+        write_u32(file, 16)?;
+        write_u16(file, 1)?; // PCM
+        write_u16(file, OUTPUT_CHANNELS)?;
+        write_u32(file, OUTPUT_SAMPLE_RATE)?;
+        write_u32(file, OUTPUT_SAMPLE_RATE * u32::from(block_align))?;
+        write_u16(file, block_align)?;
+        write_u16(file, 16)?; // Bits per sample
+
+        file.write_all(b"data")?;
+        write_u32(file, data_size)?;
+
+        Ok(())
+    }
+}
+
+impl AudioBackend for WavBackend {
+    fn sample_rate(&self) -> u32 { OUTPUT_SAMPLE_RATE }
+
+    fn write(&mut self, samples: &[f32]) -> Result<()> {
+        for sample in samples {
+            let sample = (sample.max(-1.0).min(1.0) * f32::from(i16::max_value())) as i16;
+            write_u16(&mut self.file, sample as u16)?;
+        }
+
+        self.frames += samples.len() as u32 / u32::from(OUTPUT_CHANNELS);
+
+        Ok(())
+    }
+}
+
+/// Write a little endian u16
+fn write_u16<W: Write>(writer: &mut W, value: u16) -> Result<()> {
+    writer.write_all(&[value as u8, (value >> 8) as u8])?;
+    Ok(())
+}
+
+/// Write a little endian u32
+fn write_u32<W: Write>(writer: &mut W, value: u32) -> Result<()> {
+    write_u16(writer, value as u16)?;
+    write_u16(writer, (value >> 16) as u16)
+}
+
+impl Drop for WavBackend {
+    fn drop(&mut self) {
+        if let Err(why) = self.finish() {
+            warn!("Failed to finish WAV file: {}", why);
+        }
+    }
+}
@@ -0,0 +1,78 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use lewton::inside_ogg::OggStreamReader;
+
+use std::error::Error;
+use std::io::Cursor;
+
+type Result<T> = std::result::Result<T, Box<Error>>;
+
+/// Decoded PCM audio
+pub struct SoundBuffer {
+    sample_rate: u32,
+    channels: usize,
+    samples: Vec<f32>,
+}
+
+impl SoundBuffer {
+    /// Create buffer from interleaved samples
+    pub fn new(sample_rate: u32, channels: usize, samples: Vec<f32>) -> SoundBuffer {
+        SoundBuffer {
+            sample_rate,
+            channels: channels.max(1),
+            samples,
+        }
+    }
+
+    /// Decode an Ogg Vorbis file
+    pub fn decode(data: Vec<u8>) -> Result<SoundBuffer> {
+        let mut reader = OggStreamReader::new(Cursor::new(data))?;
+
+        let sample_rate = reader.ident_hdr.audio_sample_rate;
+        let channels = usize::from(reader.ident_hdr.audio_channels);
+
+        let mut samples = Vec::new();
+
+        while let Some(packet) = reader.read_dec_packet_itl()? {
+            samples.extend(packet.into_iter().map(|sample| f32::from(sample) / 32_768.0));
+        }
+
+        Ok(SoundBuffer::new(sample_rate, channels, samples))
+    }
+
+    /// Get sample rate
+    pub fn sample_rate(&self) -> u32 { self.sample_rate }
+
+    /// Get channel count
+    pub fn channels(&self) -> usize { self.channels }
+
+    /// Get frame count
+    pub fn frames(&self) -> usize { self.samples.len() / self.channels }
+
+    /// Get length in seconds
+    pub fn duration(&self) -> f32 { self.frames() as f32 / self.sample_rate as f32 }
+
+    /// Get a stereo frame, mono is copied to both sides and extra channels are ignored
+    pub fn frame(&self, index: usize) -> (f32, f32) {
+        let start = index * self.channels;
+
+        match self.samples.get(start..start + self.channels.min(2)) {
+            Some(&[mono]) => (mono, mono),
+            Some(&[left, right]) => (left, right),
+            _ => (0.0, 0.0),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::camera::Camera;
+
+use cgmath::prelude::*;
+use cgmath::{Point3, Vector3};
+
+/// Hearing range of a sound at full volume, in blocks
+pub const ATTENUATION_DISTANCE: f32 = 16.0;
+
+/// Where sounds are heard from, usually the camera
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Listener {
+    pub position: Point3<f32>,
+    pub right: Vector3<f32>,
+}
+
+impl Listener {
+    /// Create a listener at world origin looking at -Z
+    pub fn new() -> Listener {
+        Listener {
+            position: Point3::new(0.0, 0.0, 0.0),
+            right: Vector3::unit_x(),
+        }
+    }
+
+    /// Create a listener matching camera position and yaw
+    pub fn from_camera(camera: &Camera) -> Listener {
+        Listener {
+            position: camera.position(),
+            right: camera.right(),
+        }
+    }
+
+    /// Check if a sound can be heard at all
+    pub fn hears(&self, source: Point3<f32>, volume: f32) -> bool {
+        self.position.distance(source) < range(volume)
+    }
+
+    /// Get left and right gain of a sound, using vanilla's linear falloff
+    pub fn gains(&self, source: Point3<f32>, volume: f32) -> (f32, f32) {
+        let offset = source - self.position;
+        let distance = offset.magnitude();
+
+        let attenuation = (1.0 - distance / range(volume)).max(0.0);
+
+        if attenuation == 0.0 {
+            return (0.0, 0.0);
+        }
+
+        // Sounds right on the listener play centered
+        let pan = if distance > 0.0001 {
+            (offset / distance).dot(self.right).max(-1.0).min(1.0)
+        } else {
+            0.0
+        };
+
+        let (left, right) = pan_gains(pan);
+
+        (left * attenuation, right * attenuation)
+    }
+}
+
+impl Default for Listener {
+    fn default() -> Listener { Listener::new() }
+}
+
+/// Get hearing range, louder than full volume sounds reach further
+pub fn range(volume: f32) -> f32 { ATTENUATION_DISTANCE * volume.max(1.0) }
+
+/// Equal power panning from -1.0 (left) to 1.0 (right), centered sounds play at full volume on both sides
+pub fn pan_gains(pan: f32) -> (f32, f32) {
+    use std::f32::consts::SQRT_2;
+
+    let left = ((1.0 - pan) / 2.0).sqrt() * SQRT_2;
+    let right = ((1.0 + pan) / 2.0).sqrt() * SQRT_2;
+
+    (left.min(1.0), right.min(1.0))
+}
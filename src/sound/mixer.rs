@@ -0,0 +1,266 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use sound::backend::AudioBackend;
+use sound::decoder::SoundBuffer;
+use sound::listener::Listener;
+use sound::{Playback, SoundCategory, SoundHandle};
+
+use cgmath::Point3;
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::Arc;
+use std::time::Duration;
+
+type Result<T> = std::result::Result<T, Box<Error>>;
+
+/// Maximum sounds playing at once
+pub const MAX_VOICES: usize = 255;
+
+/// Longest time mixed in one update, so a frame hitch doesn't produce a huge burst
+const MAX_UPDATE: f64 = 0.25;
+
+/// A playing sound
+struct Voice {
+    handle: SoundHandle,
+    buffer: Arc<SoundBuffer>,
+    playback: Playback,
+    cursor: f64,
+    fade: f32,
+    fade_step: f32,
+}
+
+impl Voice {
+    /// Check if the sound ended or faded out completely
+    fn finished(&self) -> bool {
+        self.cursor as usize >= self.buffer.frames() || (self.fade <= 0.0 && self.fade_step < 0.0)
+    }
+}
+
+/// Software mixer, adds every playing sound into a stereo stream
+pub struct Mixer {
+    backend: Box<AudioBackend>,
+    voices: Vec<Voice>,
+    volumes: HashMap<SoundCategory, f32>,
+    listener: Listener,
+    next_handle: u64,
+    remainder: f64,
+}
+
+impl Mixer {
+    /// Create a mixer writing to a backend
+    pub fn new(backend: Box<AudioBackend>) -> Mixer {
+        Mixer {
+            backend,
+            voices: Vec::new(),
+            volumes: HashMap::new(),
+            listener: Listener::new(),
+            next_handle: 0,
+            remainder: 0.0,
+        }
+    }
+
+    /// Get output sample rate
+    pub fn sample_rate(&self) -> u32 { self.backend.sample_rate() }
+
+    /// Get category volume, from 0.0 to 1.0
+    pub fn volume(&self, category: SoundCategory) -> f32 {
+        self.volumes.get(&category).cloned().unwrap_or(1.0)
+    }
+
+    /// Set category volume, from 0.0 to 1.0
+    pub fn set_volume(&mut self, category: SoundCategory, volume: f32) {
+        self.volumes.insert(category, volume.max(0.0).min(1.0));
+    }
+
+    /// Reserve a handle for a sound that will start later
+    pub fn allocate(&mut self) -> SoundHandle {
+        self.next_handle += 1;
+        SoundHandle(self.next_handle)
+    }
+
+    /// Get listener used by positional sounds
+    pub fn listener(&self) -> &Listener { &self.listener }
+
+    /// Move listener, usually once per frame
+    pub fn set_listener(&mut self, listener: Listener) { self.listener = listener; }
+
+    /// Start playing a sound
+    pub fn play(&mut self, buffer: Arc<SoundBuffer>, playback: Playback) -> SoundHandle {
+        let handle = self.allocate();
+        self.play_as(handle, buffer, playback);
+        handle
+    }
+
+    /// Start playing a sound using a reserved handle
+    pub fn play_as(&mut self, handle: SoundHandle, buffer: Arc<SoundBuffer>, playback: Playback) {
+        if self.voices.len() >= MAX_VOICES {
+            warn!("Too many sounds playing, skipping one");
+            return;
+        }
+
+        let rate = self.sample_rate() as f32;
+
+        let (fade, fade_step) = if playback.fade_in > 0.0 {
+            (0.0, 1.0 / (playback.fade_in * rate))
+        } else {
+            (1.0, 0.0)
+        };
+
+        self.voices.push(Voice {
+            handle,
+            buffer,
+            playback,
+            cursor: 0.0,
+            fade,
+            fade_step,
+        });
+    }
+
+    /// Move a positional sound, like one following an entity
+    pub fn set_position(&mut self, handle: SoundHandle, position: Point3<f32>) {
+        for voice in self.voices.iter_mut().filter(|voice| voice.handle == handle) {
+            voice.playback.position = Some(position);
+        }
+    }
+
+    /// Stop a sound
+    pub fn stop(&mut self, handle: SoundHandle) { self.voices.retain(|voice| voice.handle != handle); }
+
+    /// Lower a sound volume to silence over some seconds, then stop it
+    pub fn fade_out(&mut self, handle: SoundHandle, seconds: f32) {
+        if seconds <= 0.0 {
+            return self.stop(handle);
+        }
+
+        let step = -1.0 / (seconds * self.sample_rate() as f32);
+
+        for voice in self.voices.iter_mut().filter(|voice| voice.handle == handle) {
+            voice.fade_step = step;
+        }
+    }
+
+    /// Stop every sound
+    pub fn stop_all(&mut self) { self.voices.clear(); }
+
+    /// Check if a sound is still playing
+    pub fn playing(&self, handle: SoundHandle) -> bool {
+        self.voices.iter().any(|voice| voice.handle == handle)
+    }
+
+    /// Get playing sound count
+    pub fn voices(&self) -> usize { self.voices.len() }
+
+    /// Mix some frames into interleaved stereo samples, finished sounds are removed
+    pub fn mix(&mut self, frames: usize) -> Vec<f32> {
+        let mut output = vec![0.0; frames * 2];
+        let rate = f64::from(self.sample_rate());
+        let master = self.volume(SoundCategory::Master);
+
+        // Positional sounds that left hearing range are stopped, freeing their voice
+        let listener = self.listener;
+
+        self.voices.retain(|voice| match voice.playback.position {
+            Some(position) => listener.hears(position, voice.playback.volume),
+            None => true,
+        });
+
+        for voice in &mut self.voices {
+            let playback = &voice.playback;
+
+            let category = if playback.category == SoundCategory::Master {
+                1.0
+            } else {
+                self.volumes.get(&playback.category).cloned().unwrap_or(1.0)
+            };
+
+            // Positional sounds are mixed down to mono and panned around the listener
+            let (gain_left, gain_right) = match playback.position {
+                Some(position) => {
+                    let (left, right) = self.listener.gains(position, playback.volume);
+                    let volume = playback.volume.min(1.0);
+
+                    (left * volume, right * volume)
+                },
+                None => (playback.volume, playback.volume),
+            };
+
+            let gain_left = master * category * gain_left;
+            let gain_right = master * category * gain_right;
+
+            // Resample to output rate, pitch just changes playback speed
+            let step = f64::from(voice.buffer.sample_rate()) / rate * f64::from(playback.pitch);
+            let length = voice.buffer.frames();
+
+            // Silent sounds keep playing so they resume at the right point
+            if gain_left == 0.0 && gain_right == 0.0 {
+                voice.cursor += step * frames as f64;
+                voice.fade = (voice.fade + voice.fade_step * frames as f32).max(0.0).min(1.0);
+                continue;
+            }
+
+            for frame in output.chunks_mut(2) {
+                let index = voice.cursor as usize;
+
+                if index >= length || voice.finished() {
+                    break;
+                }
+
+                // Linear interpolation between source frames
+                let fraction = (voice.cursor - index as f64) as f32;
+                let (left, right) = voice.buffer.frame(index);
+                let (next_left, next_right) = if index + 1 < length {
+                    voice.buffer.frame(index + 1)
+                } else {
+                    (left, right)
+                };
+
+                let mut left = left + (next_left - left) * fraction;
+                let mut right = right + (next_right - right) * fraction;
+
+                if playback.position.is_some() {
+                    left = (left + right) / 2.0;
+                    right = left;
+                }
+
+                frame[0] += left * gain_left * voice.fade;
+                frame[1] += right * gain_right * voice.fade;
+
+                voice.cursor += step;
+                voice.fade = (voice.fade + voice.fade_step).max(0.0).min(1.0);
+            }
+        }
+
+        self.voices.retain(|voice| !voice.finished());
+
+        for sample in &mut output {
+            *sample = sample.max(-1.0).min(1.0);
+        }
+
+        output
+    }
+
+    /// Mix elapsed time and send it to the backend
+    pub fn update(&mut self, elapsed: Duration) -> Result<()> {
+        let elapsed = elapsed.as_secs() as f64 + f64::from(elapsed.subsec_nanos()) / 1_000_000_000.0;
+        let frames = elapsed.min(MAX_UPDATE) * f64::from(self.sample_rate()) + self.remainder;
+
+        self.remainder = frames.fract();
+
+        let samples = self.mix(frames as usize);
+        self.backend.write(&samples)
+    }
+}
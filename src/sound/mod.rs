@@ -0,0 +1,405 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::camera::Camera;
+use core::resource_manager::resource::Resource;
+use core::settings::Settings;
+
+use sound::decoder::SoundBuffer;
+use sound::listener::Listener;
+use sound::mixer::Mixer;
+use sound::music::{MusicAction, MusicManager, MusicType, MUSIC_FADE};
+use sound::registry::SoundRegistry;
+
+use cgmath::Point3;
+use rand::rngs::ThreadRng;
+use rand::thread_rng;
+use threadpool::ThreadPool;
+
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
+use std::time::Instant;
+
+pub mod backend;
+pub mod decoder;
+pub mod listener;
+pub mod mixer;
+pub mod music;
+pub mod registry;
+
+/// Volume groups configurable by the user
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum SoundCategory {
+    Master,
+    Music,
+    Record,
+    Weather,
+    Block,
+    Hostile,
+    Neutral,
+    Player,
+    Ambient,
+    Voice,
+}
+
+impl SoundCategory {
+    /// Get every category, sorted like vanilla's options screen
+    pub fn all() -> &'static [SoundCategory] {
+        const CATEGORIES: [SoundCategory; 10] = [
+            SoundCategory::Master,
+            SoundCategory::Music,
+            SoundCategory::Record,
+            SoundCategory::Weather,
+            SoundCategory::Block,
+            SoundCategory::Hostile,
+            SoundCategory::Neutral,
+            SoundCategory::Player,
+            SoundCategory::Ambient,
+            SoundCategory::Voice,
+        ];
+
+        &CATEGORIES
+    }
+
+    /// Get name used by vanilla, like `music`
+    pub fn name(self) -> &'static str {
+        match self {
+            SoundCategory::Master => "master",
+            SoundCategory::Music => "music",
+            SoundCategory::Record => "record",
+            SoundCategory::Weather => "weather",
+            SoundCategory::Block => "block",
+            SoundCategory::Hostile => "hostile",
+            SoundCategory::Neutral => "neutral",
+            SoundCategory::Player => "player",
+            SoundCategory::Ambient => "ambient",
+            SoundCategory::Voice => "voice",
+        }
+    }
+
+    /// Get category by its vanilla name
+    pub fn from_name(name: &str) -> Option<SoundCategory> {
+        SoundCategory::all()
+            .iter()
+            .cloned()
+            .find(|category| category.name() == name)
+    }
+}
+
+/// Identifies a playing sound
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SoundHandle(u64);
+
+/// How a sound is played
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Playback {
+    pub category: SoundCategory,
+    pub volume: f32,
+    pub pitch: f32,
+    pub position: Option<Point3<f32>>,
+    pub fade_in: f32,
+}
+
+impl Playback {
+    /// Play at full volume and normal pitch, not positional
+    pub fn new(category: SoundCategory) -> Playback {
+        Playback {
+            category,
+            volume: 1.0,
+            pitch: 1.0,
+            position: None,
+            fade_in: 0.0,
+        }
+    }
+
+    /// Set volume, values over 1.0 make positional sounds reach further
+    pub fn volume(mut self, volume: f32) -> Playback {
+        self.volume = volume;
+        self
+    }
+
+    /// Set pitch
+    pub fn pitch(mut self, pitch: f32) -> Playback {
+        self.pitch = pitch;
+        self
+    }
+
+    /// Raise volume from silence over some seconds
+    pub fn fade_in(mut self, seconds: f32) -> Playback {
+        self.fade_in = seconds;
+        self
+    }
+
+    /// Play from a world position
+    pub fn at(mut self, position: Point3<f32>) -> Playback {
+        self.position = Some(position);
+        self
+    }
+}
+
+/// Sound file decoded async
+struct DecodedSound {
+    resource: Resource,
+    buffer: Option<SoundBuffer>,
+}
+
+/// Sound waiting for its file to be decoded
+struct QueuedSound {
+    handle: SoundHandle,
+    resource: Resource,
+    playback: Playback,
+    cache: bool,
+}
+
+/// Sound events, decoded sound cache and mixer
+pub struct SoundManager {
+    registry: SoundRegistry,
+    mixer: Mixer,
+    music: MusicManager,
+    buffers: HashMap<Resource, Arc<SoundBuffer>>,
+    loading: HashSet<Resource>,
+    queue: Vec<QueuedSound>,
+    sender: Sender<DecodedSound>,
+    receiver: Receiver<DecodedSound>,
+    pool: ThreadPool,
+    rng: ThreadRng,
+    last_update: Instant,
+}
+
+impl SoundManager {
+    /// Start sound manager using user volumes and output
+    pub fn new(settings: &Settings) -> SoundManager {
+        info!("Starting sound manager...");
+
+        let (sender, receiver) = channel();
+
+        let mut mixer = Mixer::new(backend::create(settings.audio_output()));
+
+        for &category in SoundCategory::all() {
+            mixer.set_volume(category, settings.volume(category));
+        }
+
+        SoundManager {
+            registry: SoundRegistry::new(),
+            mixer,
+            music: MusicManager::new(),
+            buffers: HashMap::new(),
+            loading: HashSet::new(),
+            queue: Vec::new(),
+            sender,
+            receiver,
+            pool: ThreadPool::new(2),
+            rng: thread_rng(),
+            last_update: Instant::now(),
+        }
+    }
+
+    /// Load sound events from every `sounds.json`
+    pub fn load(&mut self) -> Result<(), Box<Error>> {
+        self.buffers.clear();
+        self.registry.load()
+    }
+
+    /// Get sound event registry
+    pub fn registry(&self) -> &SoundRegistry { &self.registry }
+
+    /// Get mixer
+    pub fn mixer(&self) -> &Mixer { &self.mixer }
+
+    /// Get mixer
+    pub fn mixer_mut(&mut self) -> &mut Mixer { &mut self.mixer }
+
+    /// Set category volume, from 0.0 to 1.0
+    pub fn set_volume(&mut self, category: SoundCategory, volume: f32) {
+        self.mixer.set_volume(category, volume);
+    }
+
+    /// Move listener to the camera, should be called every frame while in game
+    pub fn set_listener(&mut self, camera: &Camera) {
+        self.mixer.set_listener(Listener::from_camera(camera));
+    }
+
+    /// Play a sound event like `ui.button.click`, none if it doesn't exist or is out of hearing range
+    pub fn play(&mut self, event: &str, playback: Playback) -> Option<SoundHandle> {
+        let sound = match self.registry.pick(event, &mut self.rng) {
+            Some(sound) => sound,
+            None => {
+                debug!("Unknown sound event {}", event);
+                return None;
+            },
+        };
+
+        let playback = Playback {
+            volume: playback.volume * sound.volume,
+            pitch: playback.pitch * sound.pitch,
+            ..playback
+        };
+
+        // Don't bother decoding sounds nobody can hear
+        if let Some(position) = playback.position {
+            if !self.mixer.listener().hears(position, playback.volume) {
+                return None;
+            }
+        }
+
+        let handle = self.mixer.allocate();
+
+        self.queue.push(QueuedSound {
+            handle,
+            resource: sound.resource(),
+            playback,
+            // Streamed sounds are long, so don't keep them in memory
+            cache: !sound.stream,
+        });
+
+        self.start_queued();
+
+        Some(handle)
+    }
+
+    /// Set music for current scene, none for silence
+    pub fn set_music(&mut self, music: Option<MusicType>) { self.music.set(music); }
+
+    /// Get music manager
+    pub fn music(&self) -> &MusicManager { &self.music }
+
+    /// Lower a sound volume to silence over some seconds, then stop it
+    pub fn fade_out(&mut self, handle: SoundHandle, seconds: f32) {
+        self.queue.retain(|sound| sound.handle != handle);
+        self.mixer.fade_out(handle, seconds);
+    }
+
+    /// Move a positional sound, like one following an entity
+    pub fn set_position(&mut self, handle: SoundHandle, position: Point3<f32>) {
+        for sound in self.queue.iter_mut().filter(|sound| sound.handle == handle) {
+            sound.playback.position = Some(position);
+        }
+
+        self.mixer.set_position(handle, position);
+    }
+
+    /// Stop a sound
+    pub fn stop(&mut self, handle: SoundHandle) {
+        self.queue.retain(|sound| sound.handle != handle);
+        self.mixer.stop(handle);
+    }
+
+    /// Stop every sound
+    pub fn stop_all(&mut self) {
+        self.queue.clear();
+        self.mixer.stop_all();
+    }
+
+    /// Check if a sound is still playing or waiting to play
+    pub fn playing(&self, handle: SoundHandle) -> bool {
+        self.mixer.playing(handle) || self.queue.iter().any(|sound| sound.handle == handle)
+    }
+
+    /// Receive decoded sounds and feed the backend
+    pub fn tick(&mut self) {
+        while let Ok(DecodedSound { resource, buffer }) = self.receiver.try_recv() {
+            self.loading.remove(&resource);
+
+            match buffer {
+                Some(buffer) => {
+                    let buffer = Arc::new(buffer);
+
+                    // Start everything waiting for this sound, cached or not
+                    let (ready, queue) = self
+                        .queue
+                        .drain(..)
+                        .partition::<Vec<_>, _>(|sound| sound.resource == resource);
+
+                    self.queue = queue;
+
+                    for sound in &ready {
+                        self.mixer.play_as(sound.handle, buffer.clone(), sound.playback);
+                    }
+
+                    if ready.iter().any(|sound| sound.cache) {
+                        self.buffers.insert(resource, buffer);
+                    }
+                },
+                None => self.queue.retain(|sound| sound.resource != resource),
+            }
+        }
+
+        let now = Instant::now();
+        let elapsed = now - self.last_update;
+
+        self.update_music(elapsed.as_secs() as f32 + elapsed.subsec_nanos() as f32 / 1_000_000_000.0);
+
+        if let Err(why) = self.mixer.update(elapsed) {
+            warn!("Failed to write sound output: {}", why);
+        }
+
+        self.last_update = now;
+    }
+
+    /// Start, stop and crossfade music tracks
+    fn update_music(&mut self, elapsed: f32) {
+        let playing = self.music.handle().map_or(false, |handle| self.playing(handle));
+
+        match self.music.update(elapsed, playing, &mut self.rng) {
+            Some(MusicAction::Play(music)) => {
+                let playback = Playback::new(SoundCategory::Music).fade_in(MUSIC_FADE);
+                let handle = self.play(music.event(), playback);
+
+                self.music.started(music, handle, &mut self.rng);
+            },
+            Some(MusicAction::FadeOut(handle)) => self.fade_out(handle, MUSIC_FADE),
+            None => (),
+        }
+    }
+
+    /// Play cached sounds and decode missing ones
+    fn start_queued(&mut self) {
+        let mut waiting = Vec::new();
+
+        for sound in self.queue.drain(..) {
+            if let Some(buffer) = self.buffers.get(&sound.resource) {
+                self.mixer.play_as(sound.handle, buffer.clone(), sound.playback);
+                continue;
+            }
+
+            if self.loading.insert(sound.resource.clone()) {
+                let sender = self.sender.clone();
+                let resource = sound.resource.clone();
+
+                self.pool.execute(move || {
+                    debug!("Decoding sound {}", resource);
+
+                    let buffer = resource
+                        .load_binary()
+                        .and_then(SoundBuffer::decode)
+                        .map_err(|why| warn!("Failed to load sound {}: {}", resource, why))
+                        .ok();
+
+                    // Mixer is gone when the game closes while decoding
+                    if sender.send(DecodedSound { resource, buffer }).is_err() {
+                        debug!("Dropped decoded sound, the mixer was closed");
+                    }
+                });
+            }
+
+            waiting.push(sound);
+        }
+
+        self.queue = waiting;
+    }
+}
@@ -0,0 +1,179 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use sound::SoundHandle;
+
+use rand::Rng;
+
+/// Seconds used to fade music in and out
+pub const MUSIC_FADE: f32 = 2.0;
+
+/// Seconds before the first track
+const INITIAL_DELAY: f32 = 5.0;
+
+/// Music tracks, each one with vanilla's delay range between songs
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MusicType {
+    Menu,
+    Game,
+    Creative,
+    Credits,
+    Nether,
+    EndBoss,
+    End,
+    UnderWater,
+}
+
+impl MusicType {
+    /// Music used in the overworld
+    pub fn game(creative: bool) -> MusicType {
+        if creative {
+            MusicType::Creative
+        } else {
+            MusicType::Game
+        }
+    }
+
+    /// Get sound event
+    pub fn event(self) -> &'static str {
+        match self {
+            MusicType::Menu => "music.menu",
+            MusicType::Game => "music.game",
+            MusicType::Creative => "music.creative",
+            MusicType::Credits => "music.credits",
+            MusicType::Nether => "music.nether",
+            MusicType::EndBoss => "music.dragon",
+            MusicType::End => "music.end",
+            MusicType::UnderWater => "music.under_water",
+        }
+    }
+
+    /// Get minimum and maximum seconds between tracks
+    pub fn delay(self) -> (f32, f32) {
+        let (min, max) = match self {
+            MusicType::Menu => (20, 600),
+            MusicType::Game => (12_000, 24_000),
+            MusicType::Creative => (1_200, 3_600),
+            MusicType::Credits => (0, 0),
+            MusicType::Nether => (1_200, 3_600),
+            MusicType::EndBoss => (0, 0),
+            MusicType::End => (6_000, 24_000),
+            MusicType::UnderWater => (12_000, 24_000),
+        };
+
+        // Vanilla counts in ticks
+        (min as f32 / 20.0, max as f32 / 20.0)
+    }
+}
+
+/// What the sound manager should do with music
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MusicAction {
+    Play(MusicType),
+    FadeOut(SoundHandle),
+}
+
+/// Picks when music tracks play, like vanilla's music ticker
+pub struct MusicManager {
+    wanted: Option<MusicType>,
+    current: Option<(MusicType, SoundHandle)>,
+    delay: f32,
+}
+
+impl MusicManager {
+    pub fn new() -> MusicManager {
+        MusicManager {
+            wanted: None,
+            current: None,
+            delay: INITIAL_DELAY,
+        }
+    }
+
+    /// Set music that should be playing, none for silence
+    pub fn set(&mut self, music: Option<MusicType>) { self.wanted = music; }
+
+    /// Get music that should be playing
+    pub fn wanted(&self) -> Option<MusicType> { self.wanted }
+
+    /// Get playing track handle
+    pub fn handle(&self) -> Option<SoundHandle> { self.current.map(|(_, handle)| handle) }
+
+    /// Get seconds until next track
+    pub fn delay(&self) -> f32 { self.delay }
+
+    /// Tell which track started, none if it couldn't be played
+    pub fn started<R: Rng>(&mut self, music: MusicType, handle: Option<SoundHandle>, rng: &mut R) {
+        match handle {
+            Some(handle) => self.current = Some((music, handle)),
+            None => self.delay = random_delay(music.delay(), rng),
+        }
+    }
+
+    /// Advance timers, `playing` tells if the current track is still playing
+    pub fn update<R: Rng>(&mut self, elapsed: f32, playing: bool, rng: &mut R) -> Option<MusicAction> {
+        let wanted = match self.wanted {
+            Some(wanted) => wanted,
+            None => {
+                return self
+                    .current
+                    .take()
+                    .map(|(_, handle)| MusicAction::FadeOut(handle))
+            },
+        };
+
+        if let Some((music, handle)) = self.current {
+            // Switching scenes fades out the old track and starts the new one soon
+            if music != wanted {
+                self.current = None;
+                self.delay = random_delay((0.0, wanted.delay().0 / 2.0), rng);
+
+                return Some(MusicAction::FadeOut(handle));
+            }
+
+            if !playing {
+                self.current = None;
+                self.delay = self.delay.min(random_delay(wanted.delay(), rng));
+            }
+        }
+
+        self.delay = self.delay.min(wanted.delay().1);
+
+        if self.current.is_none() {
+            self.delay -= elapsed;
+
+            if self.delay <= 0.0 {
+                // Wait until the track ends before counting again
+                self.delay = ::std::f32::MAX;
+
+                return Some(MusicAction::Play(wanted));
+            }
+        }
+
+        None
+    }
+}
+
+impl Default for MusicManager {
+    fn default() -> MusicManager { MusicManager::new() }
+}
+
+/// Pick a delay in a range
+fn random_delay<R: Rng>((min, max): (f32, f32), rng: &mut R) -> f32 {
+    if max > min {
+        rng.gen_range(min, max)
+    } else {
+        min
+    }
+}
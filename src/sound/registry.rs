@@ -0,0 +1,243 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::resource_manager::resource::Resource;
+use core::resource_manager::resource_type::ResourceType;
+
+use rand::Rng;
+use serde_json;
+
+use std::collections::HashMap;
+use std::error::Error;
+
+type Result<T> = std::result::Result<T, Box<Error>>;
+
+/// Namespaces scanned for `sounds.json`
+const NAMESPACES: [&str; 2] = ["minecraft", "litecraft"];
+
+/// Limit of nested event references
+const MAX_DEPTH: u8 = 8;
+
+/// What a sound entry name points to
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SoundKind {
+    File,
+    Event,
+}
+
+/// Full form of a sound entry
+#[derive(Deserialize)]
+struct SoundDefinition {
+    name: String,
+    #[serde(default = "one")]
+    volume: f32,
+    #[serde(default = "one")]
+    pitch: f32,
+    #[serde(default = "weight")]
+    weight: u32,
+    #[serde(default)]
+    stream: bool,
+    #[serde(default = "file", rename = "type")]
+    kind: SoundKind,
+}
+
+fn one() -> f32 { 1.0 }
+
+fn weight() -> u32 { 1 }
+
+fn file() -> SoundKind { SoundKind::File }
+
+/// Sound entry, either a file name or a full definition
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SoundDefinitionEntry {
+    Name(String),
+    Full(SoundDefinition),
+}
+
+/// Event as written in `sounds.json`
+#[derive(Deserialize)]
+struct EventDefinition {
+    #[serde(default)]
+    replace: bool,
+    subtitle: Option<String>,
+    #[serde(default)]
+    sounds: Vec<SoundDefinitionEntry>,
+}
+
+/// A sound that can be picked by an event
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sound {
+    pub name: String,
+    pub volume: f32,
+    pub pitch: f32,
+    pub weight: u32,
+    pub stream: bool,
+    pub kind: SoundKind,
+}
+
+impl Sound {
+    /// Get sound file resource, names without namespace use the namespace of their `sounds.json`
+    pub fn resource(&self) -> Resource {
+        let (namespace, path) = split_name(&self.name, "minecraft");
+        Resource::new(namespace.to_owned(), path.to_owned(), ResourceType::Sound)
+    }
+}
+
+/// Named group of sounds, one is picked at random each time it plays
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SoundEvent {
+    pub subtitle: Option<String>,
+    pub sounds: Vec<Sound>,
+}
+
+/// Sound events loaded from every `sounds.json`
+pub struct SoundRegistry {
+    events: HashMap<String, SoundEvent>,
+}
+
+impl SoundRegistry {
+    /// Create an empty registry
+    pub fn new() -> SoundRegistry {
+        SoundRegistry {
+            events: HashMap::new(),
+        }
+    }
+
+    /// Load `sounds.json` from resources and every resource pack
+    pub fn load(&mut self) -> Result<()> {
+        self.events.clear();
+
+        for namespace in &NAMESPACES {
+            let resource = Resource::new(*namespace, "sounds", ResourceType::SoundDefinition);
+
+            for data in resource.load_all()? {
+                if let Err(why) = self.merge(namespace, &data) {
+                    warn!("Failed to parse {}: {}", resource, why);
+                }
+            }
+        }
+
+        info!("Loaded {} sound events", self.events.len());
+
+        Ok(())
+    }
+
+    /// Add events from a `sounds.json`, later definitions extend earlier ones unless `replace` is set
+    pub fn merge(&mut self, namespace: &str, data: &str) -> Result<()> {
+        let definitions: HashMap<String, EventDefinition> = serde_json::from_str(data)?;
+
+        for (name, definition) in definitions {
+            let event = self.events.entry(format!("{}:{}", namespace, name)).or_default();
+
+            if definition.replace {
+                *event = SoundEvent::default();
+            }
+
+            if definition.subtitle.is_some() {
+                event.subtitle = definition.subtitle;
+            }
+
+            for sound in definition.sounds {
+                let sound = match sound {
+                    SoundDefinitionEntry::Name(name) => Sound {
+                        name,
+                        volume: 1.0,
+                        pitch: 1.0,
+                        weight: 1,
+                        stream: false,
+                        kind: SoundKind::File,
+                    },
+                    SoundDefinitionEntry::Full(definition) => Sound {
+                        name: definition.name,
+                        volume: definition.volume,
+                        pitch: definition.pitch,
+                        weight: definition.weight,
+                        stream: definition.stream,
+                        kind: definition.kind,
+                    },
+                };
+
+                // Make names absolute so they don't depend on where they were defined
+                let (sound_namespace, path) = split_name(&sound.name, namespace);
+                let name = format!("{}:{}", sound_namespace, path);
+
+                event.sounds.push(Sound { name, ..sound });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get an event, names without namespace are looked up in `minecraft`
+    pub fn get(&self, name: &str) -> Option<&SoundEvent> {
+        let (namespace, path) = split_name(name, "minecraft");
+        self.events.get(&format!("{}:{}", namespace, path))
+    }
+
+    /// Get event count
+    pub fn len(&self) -> usize { self.events.len() }
+
+    /// Check if there are no events
+    pub fn is_empty(&self) -> bool { self.events.is_empty() }
+
+    /// Pick a random sound file by weight, following event references
+    pub fn pick<R: Rng>(&self, name: &str, rng: &mut R) -> Option<Sound> {
+        self.pick_nested(name, rng, 0)
+    }
+
+    fn pick_nested<R: Rng>(&self, name: &str, rng: &mut R, depth: u8) -> Option<Sound> {
+        if depth > MAX_DEPTH {
+            warn!("Sound event {} references too many events", name);
+            return None;
+        }
+
+        let event = self.get(name)?;
+        let total: u32 = event.sounds.iter().map(|sound| sound.weight).sum();
+
+        if total == 0 {
+            return None;
+        }
+
+        let mut roll = rng.gen_range(0, total);
+
+        let sound = event.sounds.iter().find(|sound| {
+            if roll < sound.weight {
+                true
+            } else {
+                roll -= sound.weight;
+                false
+            }
+        })?;
+
+        match sound.kind {
+            SoundKind::File => Some(sound.clone()),
+            SoundKind::Event => self.pick_nested(&sound.name, rng, depth + 1).map(|picked| Sound {
+                volume: picked.volume * sound.volume,
+                pitch: picked.pitch * sound.pitch,
+                ..picked
+            }),
+        }
+    }
+}
+
+/// Split `namespace:path`, using a default namespace if missing
+fn split_name<'a>(name: &'a str, namespace: &'a str) -> (&'a str, &'a str) {
+    match name.find(':') {
+        Some(index) => (&name[..index], &name[index + 1..]),
+        None => (namespace, name),
+    }
+}
@@ -0,0 +1,125 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::arguments::{Arguments, ServerAddress, DEFAULT_PORT};
+use core::settings::{Overrides, Settings};
+
+use log::Level;
+
+use std::path::PathBuf;
+
+fn parse(args: &[&str]) -> Arguments {
+    Arguments::parse(args.iter().map(|arg| arg.to_string())).unwrap()
+}
+
+#[test]
+fn arguments_parsed() {
+    let arguments = parse(&[
+        "--config=test.yml",
+        "--width",
+        "1280",
+        "--fullscreen",
+        "--username",
+        "Steve",
+        "--server",
+        "localhost:25566",
+        "--log-level",
+        "debug",
+    ]);
+
+    assert_eq!(arguments.config, PathBuf::from("test.yml"));
+    assert_eq!(arguments.overrides.width, Some(1280));
+    assert_eq!(arguments.overrides.height, None);
+    assert_eq!(arguments.overrides.fullscreen, Some(true));
+    assert_eq!(arguments.username, "Steve");
+    assert_eq!(arguments.log_level, Level::Debug);
+    assert_eq!(
+        arguments.server,
+        Some(ServerAddress {
+            host: "localhost".to_owned(),
+            port: 25566,
+        })
+    );
+}
+
+#[test]
+fn arguments_invalid() {
+    let invalid = [
+        vec!["--width", "wide"],
+        vec!["--height"],
+        vec!["--server", "localhost:port"],
+        vec!["--server", "[::1]:port"],
+        vec!["--log-level", "loud"],
+        vec!["--unknown"],
+    ];
+
+    for args in invalid.iter() {
+        assert!(Arguments::parse(args.iter().map(|arg| arg.to_string())).is_err());
+    }
+}
+
+#[test]
+fn arguments_server_default_port() {
+    let server: ServerAddress = "mc.example.com".parse().unwrap();
+
+    assert_eq!(server.port, DEFAULT_PORT);
+}
+
+#[test]
+fn arguments_server_ipv6() {
+    let server: ServerAddress = "[::1]".parse().unwrap();
+
+    assert_eq!(server.host, "::1");
+    assert_eq!(server.port, DEFAULT_PORT);
+
+    let server: ServerAddress = "[::1]:25566".parse().unwrap();
+
+    assert_eq!(server.host, "::1");
+    assert_eq!(server.port, 25566);
+
+    let server: ServerAddress = "fe80::1".parse().unwrap();
+
+    assert_eq!(server.host, "fe80::1");
+    assert_eq!(server.port, DEFAULT_PORT);
+
+    assert!("[::1".parse::<ServerAddress>().is_err());
+    assert!("[::1]25566".parse::<ServerAddress>().is_err());
+    assert!("[]:25566".parse::<ServerAddress>().is_err());
+}
+
+#[test]
+fn overrides_not_saved() {
+    let mut settings = Settings::new_with_size(1024, 768);
+
+    let overrides = Overrides {
+        width: Some(1920),
+        fullscreen: Some(true),
+        ..Overrides::default()
+    };
+
+    let replaced = overrides.apply(&mut settings);
+
+    assert_eq!(settings.width(), 1920);
+    assert!(settings.fullscreen());
+
+    // Height changed in game, so it is kept
+    settings.set_height(900);
+
+    let saved = overrides.restore(&settings, &replaced);
+
+    assert_eq!(saved.width(), 1024);
+    assert_eq!(saved.height(), 900);
+    assert!(!saved.fullscreen());
+}
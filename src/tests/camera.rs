@@ -0,0 +1,104 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::camera::controller::CameraController;
+use core::camera::perspective::{Perspective, THIRD_PERSON_DISTANCE};
+use core::camera::{Camera, Movement};
+use core::controls::Action;
+use core::shape::VoxelShape;
+
+use tests::raycast::TestWorld;
+
+use cgmath::prelude::*;
+use cgmath::Point3;
+
+use std::time::Duration;
+
+#[test]
+fn camera_pitch_clamped() {
+    let mut camera = Camera::new();
+
+    camera.rotate(0.0, 120.0);
+    assert!(camera.pitch() < 90.0);
+
+    camera.rotate(0.0, -360.0);
+    assert!(camera.pitch() > -90.0);
+}
+
+#[test]
+fn camera_movement_frame_rate_independent() {
+    let mut controller = CameraController::new(0.5, false);
+    controller.set_moving(Movement::FORWARD, true);
+    controller.set_moving(Movement::LEFT, true);
+
+    let mut slow = Camera::with_position(Point3::new(0.0, 64.0, 0.0));
+    let mut fast = Camera::with_position(Point3::new(0.0, 64.0, 0.0));
+
+    controller.update(&mut slow, Duration::from_millis(100));
+
+    for _ in 0..10 {
+        controller.update(&mut fast, Duration::from_millis(10));
+    }
+
+    assert!((slow.position() - fast.position()).magnitude() < 1e-4);
+
+    // Diagonal movement isn't faster
+    let moved = (slow.position() - Point3::new(0.0, 64.0, 0.0)).magnitude();
+    assert!((moved - 0.4317).abs() < 1e-4);
+}
+
+#[test]
+fn camera_opposite_movement_cancels() {
+    let mut controller = CameraController::new(0.5, false);
+    let mut camera = Camera::new();
+
+    assert!(controller.action(Action::Forward, true));
+    assert!(controller.action(Action::Back, true));
+    assert!(!controller.action(Action::Inventory, true));
+
+    controller.update(&mut camera, Duration::from_millis(50));
+
+    assert_eq!(camera.position(), Camera::new().position());
+}
+
+#[test]
+fn camera_third_person() {
+    let eyes = Camera::with_position(Point3::new(0.5, 1.5, 0.5));
+    let world = TestWorld::new(Vec::new());
+
+    // Default camera looks towards negative Z
+    let back = Perspective::ThirdPersonBack.place(&eyes, &world);
+    assert!((back.position().z - (0.5 + THIRD_PERSON_DISTANCE)).abs() < 1e-4);
+    assert_eq!(back.yaw(), eyes.yaw());
+
+    let front = Perspective::ThirdPersonFront.place(&eyes, &world);
+    assert!((front.position().z - (0.5 - THIRD_PERSON_DISTANCE)).abs() < 1e-4);
+    assert!((front.front() + eyes.front()).magnitude() < 1e-4);
+
+    assert!(!Perspective::FirstPerson.shows_player());
+    assert_eq!(Perspective::ThirdPersonFront.next(), Perspective::FirstPerson);
+}
+
+#[test]
+fn camera_third_person_pulled_in() {
+    let eyes = Camera::with_position(Point3::new(0.5, 1.5, 0.5));
+    let world = TestWorld::new(vec![(Point3::new(0, 1, 2), VoxelShape::cube())]);
+
+    let back = Perspective::ThirdPersonBack.place(&eyes, &world);
+
+    // Wall starts at Z 2, camera box corners stop there
+    assert!(back.position().z <= 1.9 + 1e-4);
+    assert!(back.position().z > 0.5);
+}
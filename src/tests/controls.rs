@@ -0,0 +1,104 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::controls::{Action, Binding, Controls, Key, Modifiers};
+use core::settings_manager::parse_config;
+
+use glium::glutin::MouseButton;
+
+use serde_yaml;
+
+#[test]
+fn binding_parse() {
+    let binding: Binding = "Ctrl+Shift+F3".parse().unwrap();
+
+    assert!(binding.modifiers.ctrl && binding.modifiers.shift && !binding.modifiers.alt);
+    assert!(binding.matches_key(Key::F3, binding.modifiers));
+    assert!(!binding.matches_key(Key::F3, Modifiers::default()));
+
+    assert_eq!(
+        "mouse.middle".parse::<Binding>().unwrap(),
+        Binding::mouse(MouseButton::Middle)
+    );
+    assert_eq!(
+        "mouse.4".parse::<Binding>().unwrap(),
+        Binding::mouse(MouseButton::Other(4))
+    );
+    assert!(!"none".parse::<Binding>().unwrap().is_bound());
+    assert!("Hyper+W".parse::<Binding>().is_err());
+}
+
+#[test]
+fn controls_defaults() {
+    let controls = Controls::default();
+
+    assert_eq!(controls.get(Action::Forward), Binding::key(Key::W));
+    assert_eq!(controls.get(Action::Attack), Binding::mouse(MouseButton::Left));
+    assert_eq!(controls.get(Action::hotbar(8).unwrap()), Binding::key(Key::Key9));
+    assert_eq!(Action::from_vanilla_name("key.sneak"), Some(Action::Sneak));
+    assert!(controls.conflicts().is_empty());
+}
+
+#[test]
+fn controls_yaml_round_trip() {
+    let mut controls = Controls::default();
+    controls.set(
+        Action::DebugOverlay,
+        Binding::key(Key::F3).with_modifiers(Modifiers {
+            alt: true,
+            ..Modifiers::default()
+        }),
+    );
+
+    let yaml = serde_yaml::to_string(&controls).unwrap();
+    assert!(yaml.contains("debug: Alt+F3"));
+
+    assert_eq!(serde_yaml::from_str::<Controls>(&yaml).unwrap(), controls);
+}
+
+#[test]
+fn controls_conflicts() {
+    let settings = parse_config("controls:\n  jump: W\n  chat: Typo\n").unwrap();
+    let controls = settings.controls();
+
+    assert_eq!(controls.conflicts(), vec![(Action::Forward, Action::Jump)]);
+    assert!(controls.conflicting(Action::Jump));
+    assert!(!controls.get(Action::Chat).is_bound());
+    assert_eq!(controls.key_actions(Key::W, Modifiers::default()).len(), 2);
+}
+
+#[test]
+fn controls_extra_modifiers() {
+    let ctrl = Modifiers {
+        ctrl: true,
+        ..Modifiers::default()
+    };
+    let mut controls = Controls::default();
+
+    // Sprinting with Ctrl held must not stop W from moving forward
+    assert!(controls.get(Action::Forward).matches_key(Key::W, ctrl));
+    assert_eq!(controls.key_actions(Key::W, ctrl), vec![Action::Forward]);
+    assert!(!Binding::key(Key::W)
+        .with_modifiers(ctrl)
+        .matches_key(Key::W, Modifiers::default()));
+
+    // A binding that declares the held modifier wins over the plain one
+    controls.set(Action::Chat, Binding::key(Key::W).with_modifiers(ctrl));
+    assert_eq!(controls.key_actions(Key::W, ctrl), vec![Action::Chat]);
+    assert_eq!(
+        controls.key_actions(Key::W, Modifiers::default()),
+        vec![Action::Forward]
+    );
+}
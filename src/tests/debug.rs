@@ -0,0 +1,51 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use gfx::debug::FrameTimes;
+
+use std::time::Duration;
+
+#[test]
+fn frame_times_fps() {
+    let mut frames = FrameTimes::new();
+    assert_eq!(frames.fps(), 0);
+    assert_eq!(frames.stats(), None);
+
+    for _ in 0..100 {
+        frames.push(Duration::from_millis(20));
+    }
+
+    // Only the last second counts
+    assert_eq!(frames.fps(), 50);
+
+    frames.push(Duration::from_millis(50));
+
+    let (min, average, max) = frames.stats().unwrap();
+    assert!((min - 20.0).abs() < 1e-3);
+    assert!((max - 50.0).abs() < 1e-3);
+    assert!(average > 20.0 && average < 21.0);
+}
+
+#[test]
+fn frame_times_history_limited() {
+    let mut frames = FrameTimes::new();
+
+    for _ in 0..1000 {
+        frames.push(Duration::from_millis(1));
+    }
+
+    assert!(frames.times().len() < 1000);
+    assert_eq!(frames.fps(), frames.times().len() as u32);
+}
@@ -0,0 +1,208 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::arguments::Arguments;
+use core::camera::Camera;
+use core::resource_manager::resource::Resource;
+use core::resource_manager::resource_type::ResourceType;
+use core::resource_manager::texture_manager::TextureManager;
+use core::settings::Settings;
+
+use gfx::canvas::Canvas;
+use gfx::device::{
+    DrawCall, DrawMode, Geometry, RecordedGeometry, RecordingDevice, RenderDevice, Uniform,
+};
+use gfx::scene::{Scene, SceneAction, SceneStack};
+use gfx::ui_renderer::{white_texture, UiRenderer};
+
+use scenes::loading::LoadingScene;
+use scenes::main_menu::{draw_wallpaper, MainMenu};
+
+use conrod::{color, widget, Colorable, Positionable, UiBuilder, Widget};
+
+fn logo() -> Resource { Resource::litecraft("logo", ResourceType::Texture) }
+
+fn panorama(i: u32) -> Resource {
+    Resource::minecraft_path(
+        format!("panorama_{}", i),
+        "gui/title/background",
+        ResourceType::Texture,
+    )
+}
+
+#[test]
+fn loading_scene_waits_for_logo() {
+    let mut device = RecordingDevice::new();
+    let mut scene = LoadingScene::new();
+
+    scene.draw_background(&mut device);
+    assert_eq!(device.programs(), vec!["noise"]);
+
+    device.clear();
+    device.add_texture(logo());
+
+    scene.draw_background(&mut device);
+    assert_eq!(device.programs(), vec!["noise", "logo"]);
+
+    let call = &device.calls()[1];
+
+    assert_eq!(call.geometry, RecordedGeometry::Quad);
+    assert_eq!(call.texture, Some(logo()));
+    assert!(call.linear);
+}
+
+#[test]
+fn loading_scene_reversed_depth() {
+    let mut device = RecordingDevice::new();
+    device.add_texture(logo());
+    device.set_reversed_depth(true);
+
+    LoadingScene::new().draw_background(&mut device);
+
+    let projection: [[f32; 4]; 4] = Camera::new().reversed_perspective().into();
+    assert_eq!(
+        device.calls()[1].uniform("persp_matrix"),
+        Some(Uniform::Mat4(projection))
+    );
+}
+
+#[test]
+fn main_menu_wallpaper() {
+    let mut device = RecordingDevice::new();
+    draw_wallpaper(&mut device, &Camera::new());
+    assert!(device.calls().is_empty());
+
+    for i in 0..5 {
+        device.add_texture(panorama(i));
+    }
+
+    draw_wallpaper(&mut device, &Camera::new());
+
+    assert_eq!(device.programs(), vec!["wallpaper"]);
+    assert!(device.calls()[0].texture.is_some());
+}
+
+#[test]
+fn recording_device_missing_texture() {
+    let mut device = RecordingDevice::new();
+    let logo = logo();
+
+    let call = DrawCall {
+        program: "logo",
+        geometry: Geometry::Quad,
+        uniforms: &[],
+        texture: Some(&logo),
+        linear: false,
+        mode: DrawMode::World,
+        scissor: None,
+    };
+
+    assert!(device.draw(&call).is_err());
+    assert!(device.calls().is_empty());
+}
+
+#[test]
+fn loading_scene_draw() {
+    let mut canvas = Canvas::headless(Arguments::default(), Settings::default());
+    let mut device = canvas.recording_device();
+
+    // Nothing is loading, so main menu opens right away
+    let action = LoadingScene::new().draw(&mut canvas, &mut device, 0.0);
+
+    assert_eq!(device.programs(), vec!["noise"]);
+
+    match action {
+        SceneAction::Transition(action, _) => match *action {
+            SceneAction::Replace(_) => (),
+            _ => panic!("Loading scene should be replaced"),
+        },
+        _ => panic!("Main menu should open with a transition"),
+    }
+}
+
+#[test]
+fn loading_scene_load() {
+    let mut canvas = Canvas::headless(Arguments::default(), Settings::default());
+
+    // Shaders need a display, so only resource loads are started
+    let mut scenes = SceneStack::new(&mut canvas, box LoadingScene::new());
+    assert_eq!(canvas.resources().shaders().count(), 0);
+
+    let mut device = canvas.recording_device();
+    scenes.draw(&mut canvas, &mut device, 0.0);
+
+    assert_eq!(device.programs()[0], "noise");
+}
+
+#[test]
+fn main_menu_draw() {
+    let mut canvas = Canvas::headless(Arguments::default(), Settings::default());
+    let mut device = canvas.recording_device();
+
+    for i in 0..5 {
+        device.add_texture(panorama(i));
+    }
+
+    device.add_texture(white_texture());
+
+    let mut menu = MainMenu::new(&mut canvas);
+
+    // Buttons need the widgets texture, so nothing can be clicked
+    match menu.draw(&mut canvas, &mut device, 0.0) {
+        SceneAction::None => (),
+        _ => panic!("Main menu should stay open"),
+    }
+
+    UiRenderer::new().draw(&mut device, canvas.ui(), canvas.resources().textures(), 854, 480);
+
+    assert_eq!(device.programs()[0], "wallpaper");
+    assert!(device.calls()[1..]
+        .iter()
+        .all(|call| call.program == "sprite" && call.mode == DrawMode::Overlay));
+}
+
+#[test]
+fn ui_renderer_rectangle() {
+    let mut ui = UiBuilder::new([100.0, 100.0]).build();
+    let id = ui.widget_id_generator().next();
+
+    widget::Rectangle::fill([20.0, 10.0])
+        .x_y(0.0, 0.0)
+        .color(color::WHITE)
+        .set(id, &mut ui.set_widgets());
+
+    let mut device = RecordingDevice::new();
+    UiRenderer::new().draw(&mut device, &ui, &TextureManager::new(), 200, 200);
+    assert!(device.calls().is_empty());
+
+    device.add_texture(white_texture());
+    UiRenderer::new().draw(&mut device, &ui, &TextureManager::new(), 200, 200);
+
+    assert_eq!(device.programs(), vec!["sprite"]);
+
+    let call = &device.calls()[0];
+
+    assert_eq!(call.texture, Some(white_texture()));
+    assert_eq!(call.scissor, Some([0, 0, 200, 200]));
+
+    match call.geometry {
+        RecordedGeometry::Sprites(ref vertices, ref indices) => {
+            assert_eq!(indices.len(), 6);
+            assert_eq!(vertices[0].position(), [80.0, 90.0]);
+            assert_eq!(vertices[2].position(), [120.0, 110.0]);
+        },
+        ref other => panic!("Rectangle drawn as {:?}", other),
+    }
+}
@@ -0,0 +1,144 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::camera::frustum::Frustum;
+use core::camera::Camera;
+use core::settings::Settings;
+
+use cgmath::{Matrix4, Point3, Vector4};
+
+/// Camera at origin looking towards negative Z
+fn camera(width: u32, height: u32) -> Camera {
+    let mut camera = Camera::with_position(Point3::new(0.0, 0.0, 0.0));
+    camera.aspect_ratio(width, height);
+    camera.set_fov(90.0);
+    camera
+}
+
+fn aabb(frustum: &Frustum, min: (f32, f32, f32), max: (f32, f32, f32)) -> bool {
+    frustum.intersects_aabb(Point3::new(min.0, min.1, min.2), Point3::new(max.0, max.1, max.2))
+}
+
+#[test]
+fn frustum_front_and_behind() {
+    let frustum = camera(800, 600).frustum();
+
+    assert!(frustum.contains_point(Point3::new(0.0, 0.0, -10.0)));
+    assert!(!frustum.contains_point(Point3::new(0.0, 0.0, 10.0)));
+
+    assert!(frustum.intersects_block(Point3::new(-0.5, -0.5, -10.0)));
+    assert!(!frustum.intersects_block(Point3::new(-0.5, -0.5, 10.0)));
+
+    assert!(frustum.intersects_sphere(Point3::new(0.0, 0.0, -10.0), 1.0));
+    assert!(!frustum.intersects_sphere(Point3::new(0.0, 0.0, 10.0), 1.0));
+}
+
+#[test]
+fn frustum_near_and_far_planes() {
+    let camera = camera(800, 600);
+    let frustum = camera.frustum();
+    let (near, far) = camera.planes();
+
+    // Closer than near plane
+    assert!(!frustum.contains_point(Point3::new(0.0, 0.0, -near * 0.5)));
+    assert!(!aabb(
+        &frustum,
+        (-0.1, -0.1, -near * 0.9),
+        (0.1, 0.1, -near * 0.5)
+    ));
+
+    // Crossing near plane
+    assert!(aabb(&frustum, (-0.1, -0.1, -near * 2.0), (0.1, 0.1, 0.0)));
+
+    // Crossing and beyond far plane
+    assert!(aabb(&frustum, (-1.0, -1.0, -far - 1.0), (1.0, 1.0, -far + 1.0)));
+    assert!(!aabb(&frustum, (-1.0, -1.0, -far - 3.0), (1.0, 1.0, -far - 1.0)));
+
+    assert!(frustum.intersects_sphere(Point3::new(0.0, 0.0, -far - 1.0), 2.0));
+    assert!(!frustum.intersects_sphere(Point3::new(0.0, 0.0, -far - 3.0), 2.0));
+}
+
+#[test]
+fn frustum_follows_render_distance() {
+    let mut camera = camera(800, 600);
+    let point = Point3::new(0.0, 0.0, -200.0);
+
+    camera.set_render_distance(2);
+    assert!(!camera.frustum().contains_point(point));
+
+    camera.set_render_distance(16);
+    assert!(camera.frustum().contains_point(point));
+}
+
+#[test]
+fn frustum_render_distance_from_settings() {
+    let mut settings = Settings::new();
+    settings.set_render_distance(4);
+
+    let mut camera = camera(800, 600);
+    camera.apply_settings(&settings);
+
+    assert_eq!(camera.render_distance(), 4);
+    assert!(!camera.frustum().contains_point(Point3::new(0.0, 0.0, -100.0)));
+}
+
+#[test]
+fn frustum_reversed_depth() {
+    let camera = camera(800, 600);
+    let (near, _) = camera.planes();
+    let matrix = camera.reversed_perspective();
+
+    let depth = |distance: f32| {
+        let clip = matrix * Vector4::new(0.0, 0.0, -distance, 1.0);
+        clip.z / clip.w
+    };
+
+    // Near plane is the closest depth, far away points approach the other end but never reach it
+    assert!((depth(near) - 1.0).abs() < 1e-5);
+    assert!(depth(10.0) < depth(1.0));
+    assert!(depth(1.0e6) > 0.0);
+
+    // Only near plane is left for culling
+    let frustum = Frustum::from_matrix(matrix);
+    assert!(frustum.contains_point(Point3::new(0.0, 0.0, -1.0e6)));
+    assert!(!frustum.contains_point(Point3::new(0.0, 0.0, -near * 0.5)));
+}
+
+#[test]
+fn frustum_aspect_ratio() {
+    // Horizontal field of view grows with aspect ratio, vertical one stays
+    let wide = camera(1600, 600).frustum();
+    let narrow = camera(600, 600).frustum();
+
+    let side = Point3::new(15.0, 0.0, -10.0);
+    let top = Point3::new(0.0, 11.0, -10.0);
+
+    assert!(wide.contains_point(side));
+    assert!(!narrow.contains_point(side));
+
+    assert!(!wide.contains_point(top));
+    assert!(!narrow.contains_point(top));
+}
+
+#[test]
+fn frustum_identity() {
+    // Identity matrix keeps the clip cube from -1 to 1
+    let frustum = Frustum::from_matrix(Matrix4::from_scale(1.0));
+
+    assert!(frustum.contains_point(Point3::new(0.99, -0.99, 0.99)));
+    assert!(!frustum.contains_point(Point3::new(1.01, 0.0, 0.0)));
+    assert!(aabb(&frustum, (1.0, 1.0, 1.0), (2.0, 2.0, 2.0)));
+    assert!(!aabb(&frustum, (1.1, 1.0, 1.0), (2.0, 2.0, 2.0)));
+}
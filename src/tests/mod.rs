@@ -15,3 +15,48 @@
 
 #[cfg(test)]
 pub mod resource_manager;
+
+#[cfg(test)]
+pub mod text_component;
+
+#[cfg(test)]
+pub mod sound;
+
+#[cfg(test)]
+pub mod settings;
+
+#[cfg(test)]
+pub mod controls;
+
+#[cfg(test)]
+pub mod arguments;
+
+#[cfg(test)]
+pub mod vanilla_options;
+
+#[cfg(test)]
+pub mod camera;
+
+#[cfg(test)]
+pub mod frustum;
+
+#[cfg(test)]
+pub mod raycast;
+
+#[cfg(test)]
+pub mod transition;
+
+#[cfg(test)]
+pub mod tick;
+
+#[cfg(test)]
+pub mod debug;
+
+#[cfg(test)]
+pub mod screenshot;
+
+#[cfg(test)]
+pub mod device;
+
+#[cfg(test)]
+pub mod sprites;
@@ -0,0 +1,165 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::raycast::{raycast, BlockView, ShapeKind, SURVIVAL_REACH};
+use core::shape::{BoundingBox, Face, VoxelShape};
+
+use cgmath::{Point3, Vector3};
+
+use std::collections::HashMap;
+
+/// Blocks placed by hand, everything else is air
+pub struct TestWorld {
+    blocks: HashMap<Point3<i32>, VoxelShape>,
+    air: VoxelShape,
+}
+
+impl TestWorld {
+    pub fn new(blocks: Vec<(Point3<i32>, VoxelShape)>) -> TestWorld {
+        TestWorld {
+            blocks: blocks.into_iter().collect(),
+            air: VoxelShape::empty(),
+        }
+    }
+}
+
+impl BlockView for TestWorld {
+    fn shape(&self, position: Point3<i32>, _kind: ShapeKind) -> &VoxelShape {
+        self.blocks.get(&position).unwrap_or(&self.air)
+    }
+}
+
+#[test]
+fn raycast_hits_face() {
+    let world = TestWorld::new(vec![(Point3::new(3, 0, 0), VoxelShape::cube())]);
+
+    let hit = raycast(
+        &world,
+        Point3::new(0.5, 0.5, 0.5),
+        Vector3::new(1.0, 0.0, 0.0),
+        SURVIVAL_REACH,
+        ShapeKind::Outline,
+    )
+    .unwrap();
+
+    assert_eq!(hit.block, Point3::new(3, 0, 0));
+    assert_eq!(hit.face, Face::West);
+    assert_eq!(hit.adjacent(), Point3::new(2, 0, 0));
+    assert!((hit.distance - 2.5).abs() < 1e-5);
+    assert!((hit.point.x - 3.0).abs() < 1e-5);
+}
+
+#[test]
+fn raycast_out_of_reach() {
+    let world = TestWorld::new(vec![(Point3::new(0, -6, 0), VoxelShape::cube())]);
+
+    let hit = raycast(
+        &world,
+        Point3::new(0.5, 0.5, 0.5),
+        Vector3::new(0.0, -1.0, 0.0),
+        SURVIVAL_REACH,
+        ShapeKind::Outline,
+    );
+
+    assert_eq!(hit, None);
+}
+
+#[test]
+fn raycast_partial_shapes() {
+    // Bottom slab, a ray passing over it hits the block behind
+    let slab = VoxelShape::new(vec![BoundingBox::pixels([0.0; 3], [16.0, 8.0, 16.0])]);
+
+    let world = TestWorld::new(vec![
+        (Point3::new(0, 0, -2), slab),
+        (Point3::new(0, 0, -3), VoxelShape::cube()),
+    ]);
+
+    let over = raycast(
+        &world,
+        Point3::new(0.5, 0.75, 0.5),
+        Vector3::new(0.0, 0.0, -1.0),
+        SURVIVAL_REACH,
+        ShapeKind::Outline,
+    )
+    .unwrap();
+
+    assert_eq!(over.block, Point3::new(0, 0, -3));
+    assert_eq!(over.face, Face::South);
+
+    let down = raycast(
+        &world,
+        Point3::new(0.5, 1.5, -1.5),
+        Vector3::new(0.0, -1.0, 0.0),
+        SURVIVAL_REACH,
+        ShapeKind::Outline,
+    )
+    .unwrap();
+
+    assert_eq!(down.block, Point3::new(0, 0, -2));
+    assert_eq!(down.face, Face::Up);
+    assert!((down.point.y - 0.5).abs() < 1e-5);
+}
+
+#[test]
+fn raycast_tall_shapes() {
+    // Fence collision, a ray over the fence post still hits it
+    let fence = VoxelShape::new(vec![BoundingBox::pixels([6.0, 0.0, 6.0], [10.0, 24.0, 10.0])]);
+
+    let world = TestWorld::new(vec![
+        (Point3::new(0, 0, -2), fence),
+        (Point3::new(0, 1, -4), VoxelShape::cube()),
+    ]);
+
+    let hit = raycast(
+        &world,
+        Point3::new(0.5, 1.25, 0.5),
+        Vector3::new(0.0, 0.0, -1.0),
+        SURVIVAL_REACH,
+        ShapeKind::Collision,
+    )
+    .unwrap();
+
+    assert_eq!(hit.block, Point3::new(0, 0, -2));
+    assert_eq!(hit.face, Face::South);
+    assert!((hit.distance - 1.875).abs() < 1e-5);
+
+    // Above the fence post the ray reaches the block behind
+    let over = raycast(
+        &world,
+        Point3::new(0.5, 1.75, 0.5),
+        Vector3::new(0.0, 0.0, -1.0),
+        SURVIVAL_REACH,
+        ShapeKind::Collision,
+    )
+    .unwrap();
+
+    assert_eq!(over.block, Point3::new(0, 1, -4));
+}
+
+#[test]
+fn raycast_negative_diagonal() {
+    let world = TestWorld::new(vec![(Point3::new(-2, -2, -2), VoxelShape::cube())]);
+
+    let hit = raycast(
+        &world,
+        Point3::new(0.1, 0.1, 0.1),
+        Vector3::new(-1.0, -1.0, -1.0),
+        SURVIVAL_REACH,
+        ShapeKind::Outline,
+    )
+    .unwrap();
+
+    assert_eq!(hit.block, Point3::new(-2, -2, -2));
+}
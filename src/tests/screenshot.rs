@@ -0,0 +1,49 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use gfx::screenshot::{flip_rows, reserve_path, timestamp};
+
+use std::fs;
+
+#[test]
+fn screenshot_timestamp() {
+    assert_eq!(timestamp(0), "1970-01-01_00.00.00");
+    assert_eq!(timestamp(951_782_400), "2000-02-29_00.00.00");
+    assert_eq!(timestamp(1_537_551_005), "2018-09-21_17.30.05");
+}
+
+#[test]
+fn screenshot_flip_rows() {
+    // 2x2 image, bottom row first as OpenGL reads it
+    let rgba = [1, 1, 1, 255, 2, 2, 2, 255, 3, 3, 3, 255, 4, 4, 4, 255];
+
+    assert_eq!(flip_rows(&rgba, 2, 2), vec![3, 3, 3, 4, 4, 4, 1, 1, 1, 2, 2, 2]);
+}
+
+#[test]
+fn screenshot_name_collisions() {
+    let directory = ::std::env::temp_dir().join("litecraft_screenshot_test");
+    let _ = fs::remove_dir_all(&directory);
+
+    let first = reserve_path(&directory, "2018-09-21_17.30.05").unwrap();
+    let second = reserve_path(&directory, "2018-09-21_17.30.05").unwrap();
+    let third = reserve_path(&directory, "2018-09-21_17.30.05").unwrap();
+
+    assert_eq!(first, directory.join("2018-09-21_17.30.05.png"));
+    assert_eq!(second, directory.join("2018-09-21_17.30.05_1.png"));
+    assert_eq!(third, directory.join("2018-09-21_17.30.05_2.png"));
+
+    fs::remove_dir_all(&directory).unwrap();
+}
@@ -0,0 +1,160 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::settings::{Settings, SETTINGS_VERSION};
+use core::settings_manager::{parse_config, save_config_to, ConfigSaver};
+
+use sound::backend::AudioOutput;
+use sound::SoundCategory;
+
+use std::fs;
+use std::time::{Duration, Instant};
+
+#[test]
+fn settings_partial_file() {
+    let settings = parse_config("window:\n  width: 1280\n").unwrap();
+
+    assert_eq!(settings.width(), 1280);
+    assert_eq!(settings.height(), 600);
+    assert_eq!(settings.fov(), 90);
+    assert_eq!(settings.volume(SoundCategory::Music), 1.0);
+}
+
+#[test]
+fn settings_invalid_values() {
+    let settings =
+        parse_config("window:\n  width: big\n  colour: blue\ngameplay:\n  vsync: false\nsomething: 1\n")
+            .unwrap();
+
+    assert_eq!(settings.width(), 800);
+    assert!(!settings.vsync());
+}
+
+#[test]
+fn settings_invalid_field_types() {
+    let data = "window:\n  width: 1280\n\
+                gameplay:\n  fov: 300\n  render_distance: 8\n\
+                audio:\n  output: speakers\n\
+                resourcepacks: [a, 5]\n";
+
+    let settings = parse_config(data).unwrap();
+
+    // Values their fields can't take fall back alone, the rest of the file is kept
+    assert_eq!(settings.fov(), 90);
+    assert_eq!(settings.audio_output(), AudioOutput::Null);
+    assert!(settings.resourcepacks().is_empty());
+    assert_eq!(settings.width(), 1280);
+    assert_eq!(settings.render_distance(), 8);
+}
+
+#[test]
+fn settings_clamped() {
+    let settings = parse_config(
+        "window:\n  gui_scale: -2.0\ngameplay:\n  fov: 0\naudio:\n  volumes:\n    music: 3\n",
+    )
+    .unwrap();
+
+    assert_eq!(settings.fov(), 30);
+    assert_eq!(settings.scale(), 0.5);
+    assert_eq!(settings.volume(SoundCategory::Music), 1.0);
+}
+
+#[test]
+fn settings_not_a_number() {
+    let settings = parse_config(
+        "window:\n  gui_scale: .nan\ngameplay:\n  mouse_sensitivity: .nan\n\
+         audio:\n  volumes:\n    music: .nan\n    master: .NaN\n",
+    )
+    .unwrap();
+
+    assert_eq!(settings.scale(), 0.5);
+    assert_eq!(settings.mouse_sensitivity(), 0.0);
+    assert_eq!(settings.volume(SoundCategory::Music), 0.0);
+    assert_eq!(settings.volume(SoundCategory::Master), 0.0);
+}
+
+#[test]
+fn settings_render_distance_and_language() {
+    let settings = parse_config("gameplay:\n  render_distance: 64\nlanguage: ''\n").unwrap();
+
+    assert_eq!(settings.render_distance(), 32);
+    assert_eq!(settings.language(), "en_us");
+
+    let mut settings = Settings::new();
+    settings.set_render_distance(1);
+    settings.set_language("es_es");
+
+    assert_eq!(settings.render_distance(), 2);
+    assert_eq!(settings.language(), "es_es");
+}
+
+#[test]
+fn settings_migrated() {
+    let settings = parse_config("gameplay:\n  fov: 70\n  vsync: true\nresourcepacks: []\n").unwrap();
+
+    assert_eq!(settings.version(), SETTINGS_VERSION);
+    assert_eq!(settings.fov(), 70);
+}
+
+#[test]
+#[should_panic]
+fn settings_not_a_mapping() { parse_config("- 1\n- 2\n").unwrap(); }
+
+#[test]
+fn settings_save_atomic() {
+    let path = ::std::env::temp_dir().join("litecraft_settings_test.yml");
+
+    let mut settings = Settings::new_with_size(1024, 768);
+    settings.set_volume(SoundCategory::Music, 0.25);
+
+    save_config_to(&settings, &path).unwrap();
+
+    let data = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    let loaded = parse_config(&data).unwrap();
+
+    assert_eq!(loaded.width(), 1024);
+    assert_eq!(loaded.volume(SoundCategory::Music), 0.25);
+    assert!(!path.with_extension("yml.tmp").exists());
+}
+
+#[test]
+fn settings_save_debounced() {
+    let mut saver = ConfigSaver::new("unused.yml");
+    assert!(!saver.pending());
+
+    saver.changed();
+
+    let now = Instant::now();
+
+    assert!(saver.pending());
+    assert!(!saver.due(now));
+    assert!(saver.due(now + Duration::from_secs(5)));
+}
+
+#[test]
+fn settings_save_only_changed() {
+    let mut saver = ConfigSaver::new("unused.yml");
+    let mut settings = Settings::default();
+
+    saver.tick(&settings);
+    saver.tick(&settings);
+    assert!(!saver.pending());
+
+    settings.set_width(1024);
+    saver.tick(&settings);
+    assert!(saver.pending());
+}
@@ -0,0 +1,277 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use sound::backend::{AudioBackend, NullBackend, WavBackend, OUTPUT_SAMPLE_RATE};
+use sound::decoder::SoundBuffer;
+use sound::listener::{pan_gains, Listener};
+use sound::mixer::Mixer;
+use sound::music::{MusicAction, MusicManager, MusicType};
+use sound::registry::SoundRegistry;
+use sound::{Playback, SoundCategory};
+
+use core::arguments::Arguments;
+use core::camera::Camera;
+use core::settings::Settings;
+
+use gfx::canvas::Canvas;
+use gfx::device::RenderDevice;
+use gfx::scene::{Scene, SceneAction, SceneStack};
+
+use cgmath::Point3;
+use rand::thread_rng;
+
+use std::fs;
+use std::sync::Arc;
+
+const SOUNDS: &str = r#"{
+    "ui.button.click": {"sounds": ["random/click"]},
+    "block.stone.break": {"subtitle": "subtitles.block.generic.break", "sounds": [
+        "dig/stone1",
+        {"name": "dig/stone2", "volume": 0.5, "pitch": 2.0, "weight": 3}
+    ]},
+    "music.menu": {"sounds": [{"name": "music/menu/menu1", "stream": true}]},
+    "ambient.alias": {"sounds": [{"name": "ui.button.click", "type": "event", "volume": 0.5}]}
+}"#;
+
+#[test]
+fn sound_registry_merge() {
+    let mut registry = SoundRegistry::new();
+    registry.merge("minecraft", SOUNDS).unwrap();
+
+    let event = registry.get("block.stone.break").unwrap();
+
+    assert_eq!(event.sounds.len(), 2);
+    assert_eq!(event.sounds[1].weight, 3);
+    assert_eq!(event.sounds[1].name, "minecraft:dig/stone2");
+    assert!(registry.get("minecraft:music.menu").unwrap().sounds[0].stream);
+
+    // Packs extend events unless they replace them
+    registry
+        .merge(
+            "minecraft",
+            r#"{"ui.button.click": {"sounds": ["random/click2"]}}"#,
+        )
+        .unwrap();
+    assert_eq!(registry.get("ui.button.click").unwrap().sounds.len(), 2);
+
+    registry
+        .merge(
+            "minecraft",
+            r#"{"ui.button.click": {"replace": true, "sounds": ["custom:click"]}}"#,
+        )
+        .unwrap();
+    assert_eq!(registry.get("ui.button.click").unwrap().sounds.len(), 1);
+    assert_eq!(
+        registry.get("ui.button.click").unwrap().sounds[0].name,
+        "custom:click"
+    );
+}
+
+#[test]
+fn sound_registry_pick() {
+    let mut registry = SoundRegistry::new();
+    registry.merge("minecraft", SOUNDS).unwrap();
+
+    let mut rng = thread_rng();
+
+    let sound = registry.pick("ambient.alias", &mut rng).unwrap();
+    assert_eq!(sound.name, "minecraft:random/click");
+    assert_eq!(sound.volume, 0.5);
+
+    assert!(registry.pick("missing.event", &mut rng).is_none());
+}
+
+#[test]
+fn mixer_volumes() {
+    let buffer = Arc::new(SoundBuffer::new(OUTPUT_SAMPLE_RATE, 1, vec![0.5; 100]));
+
+    let mut mixer = Mixer::new(Box::new(NullBackend::new()));
+    mixer.set_volume(SoundCategory::Master, 0.5);
+    mixer.set_volume(SoundCategory::Block, 0.5);
+
+    let handle = mixer.play(buffer.clone(), Playback::new(SoundCategory::Block));
+    mixer.play(buffer, Playback::new(SoundCategory::Music));
+
+    let samples = mixer.mix(50);
+
+    assert_eq!(samples.len(), 100);
+    assert!((samples[0] - (0.125 + 0.25)).abs() < 0.0001);
+    assert!(mixer.playing(handle));
+
+    mixer.mix(50);
+    assert_eq!(mixer.voices(), 0);
+}
+
+#[test]
+fn mixer_pitch() {
+    let buffer = Arc::new(SoundBuffer::new(OUTPUT_SAMPLE_RATE, 2, [1.0, -1.0].repeat(100)));
+
+    let mut mixer = Mixer::new(Box::new(NullBackend::new()));
+    let handle = mixer.play(buffer, Playback::new(SoundCategory::Player).pitch(2.0));
+
+    let samples = mixer.mix(50);
+
+    assert_eq!(samples[0], 1.0);
+    assert_eq!(samples[1], -1.0);
+    assert!(!mixer.playing(handle));
+}
+
+#[test]
+fn listener_attenuation() {
+    let listener = Listener::from_camera(&Camera::with_position([0.0, 0.0, 0.0]));
+
+    // Straight ahead plays centered
+    let (left, right) = listener.gains(Point3::new(0.0, 0.0, -8.0), 1.0);
+    assert!((left - 0.5).abs() < 0.0001);
+    assert!((right - 0.5).abs() < 0.0001);
+
+    // Louder sounds reach further
+    assert_eq!(listener.gains(Point3::new(0.0, 0.0, -20.0), 1.0), (0.0, 0.0));
+    assert!(listener.gains(Point3::new(0.0, 0.0, -20.0), 2.0).0 > 0.0);
+    assert!(!listener.hears(Point3::new(16.0, 0.0, 0.0), 1.0));
+}
+
+#[test]
+fn listener_panning() {
+    let mut camera = Camera::with_position([0.0, 0.0, 0.0]);
+    let listener = Listener::from_camera(&camera);
+
+    let (left, right) = listener.gains(Point3::new(4.0, 0.0, 0.0), 1.0);
+    assert!(right > 0.7 && left < 0.0001);
+
+    // Turning around swaps sides
+    camera.set_yaw(90.0);
+    let (left, right) = Listener::from_camera(&camera).gains(Point3::new(4.0, 0.0, 0.0), 1.0);
+    assert!(left > 0.7 && right < 0.0001);
+
+    let (left, right) = pan_gains(0.0);
+    assert!((left - 1.0).abs() < 0.0001 && (right - 1.0).abs() < 0.0001);
+}
+
+#[test]
+fn mixer_positional() {
+    let buffer = Arc::new(SoundBuffer::new(OUTPUT_SAMPLE_RATE, 1, vec![1.0; 100]));
+
+    let mut mixer = Mixer::new(Box::new(NullBackend::new()));
+    let handle = mixer.play(
+        buffer,
+        Playback::new(SoundCategory::Hostile).at(Point3::new(-4.0, 0.0, 0.0)),
+    );
+
+    let samples = mixer.mix(10);
+    assert!(samples[0] > 0.7 && samples[1] < 0.0001);
+
+    // Sounds moving out of range are stopped
+    mixer.set_position(handle, Point3::new(100.0, 0.0, 0.0));
+    assert_eq!(mixer.mix(10)[0], 0.0);
+    assert!(!mixer.playing(handle));
+    assert_eq!(mixer.voices(), 0);
+}
+
+/// Scene looking from a camera
+struct Viewpoint(Camera);
+
+impl Scene for Viewpoint {
+    fn load(&mut self, _canvas: &mut Canvas) {}
+
+    fn draw(
+        &mut self,
+        _canvas: &mut Canvas,
+        _device: &mut RenderDevice,
+        _partial_tick: f32,
+    ) -> SceneAction {
+        SceneAction::None
+    }
+
+    fn camera(&self) -> Option<&Camera> { Some(&self.0) }
+}
+
+#[test]
+fn scene_moves_listener() {
+    let mut canvas = Canvas::headless(Arguments::default(), Settings::default());
+    let camera = Camera::with_position([10.0, 64.0, -3.0]);
+
+    let mut scenes = SceneStack::new(&mut canvas, Box::new(Viewpoint(camera)));
+    scenes.tick(&mut canvas);
+
+    let listener = canvas.sounds().mixer().listener();
+    assert_eq!(listener.position, Point3::new(10.0, 64.0, -3.0));
+}
+
+#[test]
+fn mixer_fade_out() {
+    let buffer = Arc::new(SoundBuffer::new(OUTPUT_SAMPLE_RATE, 1, vec![1.0; 1000]));
+
+    let mut mixer = Mixer::new(Box::new(NullBackend::new()));
+    let handle = mixer.play(buffer, Playback::new(SoundCategory::Music).fade_in(0.001));
+
+    let samples = mixer.mix(10);
+    assert!(samples[0] < samples[18]);
+
+    mixer.fade_out(handle, 100.0 / OUTPUT_SAMPLE_RATE as f32);
+    mixer.mix(200);
+    assert!(!mixer.playing(handle));
+}
+
+#[test]
+fn music_manager_delays() {
+    let mut rng = thread_rng();
+    let mut music = MusicManager::new();
+
+    // Nothing plays without music
+    assert_eq!(music.update(100.0, false, &mut rng), None);
+
+    music.set(Some(MusicType::Menu));
+    assert_eq!(music.update(1.0, false, &mut rng), None);
+    assert_eq!(
+        music.update(30.0, false, &mut rng),
+        Some(MusicAction::Play(MusicType::Menu))
+    );
+
+    let mut mixer = Mixer::new(Box::new(NullBackend::new()));
+    let handle = mixer.allocate();
+    music.started(MusicType::Menu, Some(handle), &mut rng);
+    assert_eq!(music.update(1.0, true, &mut rng), None);
+
+    // Track ended, next one waits vanilla's delay
+    assert_eq!(music.update(0.0, false, &mut rng), None);
+    assert!(music.delay() >= 1.0 && music.delay() <= 30.0);
+
+    music.started(MusicType::Menu, Some(handle), &mut rng);
+    music.set(Some(MusicType::Game));
+    assert_eq!(
+        music.update(0.0, true, &mut rng),
+        Some(MusicAction::FadeOut(handle))
+    );
+    assert!(music.delay() <= MusicType::Game.delay().0 / 2.0);
+}
+
+#[test]
+fn wav_backend_header() {
+    let path = ::std::env::temp_dir().join("litecraft_sound_test.wav");
+
+    {
+        let mut backend = WavBackend::new(&path).unwrap();
+        backend.write(&[0.0, 0.0, 1.0, -1.0]).unwrap();
+    }
+
+    let data = fs::read(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(data.len(), 44 + 8);
+    assert_eq!(&data[0..4], b"RIFF");
+    assert_eq!(&data[8..12], b"WAVE");
+    assert_eq!(&data[40..44], &[8, 0, 0, 0]);
+}
@@ -0,0 +1,156 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::resource_manager::resource::Resource;
+use core::resource_manager::resource_type::ResourceType;
+
+use gfx::device::{DrawMode, RecordedGeometry, RecordingDevice};
+use gfx::sprites::{image_source, SpriteBatch, Units};
+
+const WHITE: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+const FULL: [f32; 4] = [0.0, 1.0, 1.0, 0.0];
+
+fn texture(name: &'static str) -> Resource { Resource::litecraft(name, ResourceType::Texture) }
+
+#[test]
+fn sprites_grouped_by_texture() {
+    let mut sprites = SpriteBatch::new(1.0);
+
+    sprites.sprite(
+        &texture("hearts"),
+        [0.0, 0.0, 9.0, 9.0],
+        Units::Pixels,
+        FULL,
+        WHITE,
+    );
+    sprites.sprite(
+        &texture("hunger"),
+        [0.0, 10.0, 9.0, 9.0],
+        Units::Pixels,
+        FULL,
+        WHITE,
+    );
+    sprites.sprite(
+        &texture("hearts"),
+        [10.0, 0.0, 9.0, 9.0],
+        Units::Pixels,
+        FULL,
+        WHITE,
+    );
+
+    let batches = sprites.batches();
+
+    assert_eq!(batches.len(), 2);
+    assert_eq!(batches[0].texture, texture("hearts"));
+    assert_eq!(batches[0].vertices.len(), 8);
+    assert_eq!(batches[0].indices, vec![0, 1, 2, 0, 2, 3, 4, 5, 6, 4, 6, 7]);
+    assert_eq!(batches[1].texture, texture("hunger"));
+    assert!(sprites.is_empty());
+}
+
+#[test]
+fn sprites_layers() {
+    let mut sprites = SpriteBatch::new(1.0);
+
+    sprites.set_layer(1);
+    sprites.sprite(
+        &texture("item"),
+        [0.0, 0.0, 16.0, 16.0],
+        Units::Pixels,
+        FULL,
+        WHITE,
+    );
+    sprites.set_layer(0);
+    sprites.sprite(
+        &texture("hotbar"),
+        [0.0, 0.0, 182.0, 22.0],
+        Units::Pixels,
+        FULL,
+        WHITE,
+    );
+
+    let batches = sprites.batches();
+
+    assert_eq!(batches[0].texture, texture("hotbar"));
+    assert_eq!(batches[1].texture, texture("item"));
+}
+
+#[test]
+fn sprites_clipping() {
+    let mut sprites = SpriteBatch::new(2.0);
+
+    sprites.push_clip([0.0, 0.0, 10.0, 10.0], Units::Gui);
+    sprites.push_clip([10.0, 0.0, 100.0, 100.0], Units::Pixels);
+
+    // Half inside both clipping rectangles, in GUI units
+    sprites.sprite(&texture("slot"), [0.0, 0.0, 10.0, 10.0], Units::Gui, FULL, WHITE);
+    sprites.pop_clip();
+
+    // Outside remaining clipping rectangle
+    sprites.sprite(&texture("slot"), [20.0, 0.0, 10.0, 10.0], Units::Gui, FULL, WHITE);
+
+    let batches = sprites.batches();
+    let vertices = &batches[0].vertices;
+
+    assert_eq!(vertices.len(), 4);
+    assert_eq!(vertices[0].position(), [10.0, 0.0]);
+    assert_eq!(vertices[2].position(), [20.0, 20.0]);
+    assert_eq!(vertices[0].tex_coords(), [0.5, 1.0]);
+    assert_eq!(vertices[2].tex_coords(), [1.0, 0.0]);
+}
+
+#[test]
+fn sprites_image_source() {
+    assert_eq!(
+        image_source([16.0, 0.0, 16.0, 32.0], [64.0, 64.0]),
+        [0.25, 1.0, 0.5, 0.5]
+    );
+}
+
+#[test]
+fn sprites_flush() {
+    let mut device = RecordingDevice::new();
+    device.add_texture(texture("hearts"));
+
+    let mut sprites = SpriteBatch::new(1.0);
+
+    for i in 0..10 {
+        let x = i as f32 * 8.0;
+        sprites.sprite(&texture("hearts"), [x, 0.0, 9.0, 9.0], Units::Pixels, FULL, WHITE);
+    }
+
+    // Not loaded yet, skipped
+    sprites.sprite(
+        &texture("hunger"),
+        [0.0, 10.0, 9.0, 9.0],
+        Units::Pixels,
+        FULL,
+        WHITE,
+    );
+    sprites.flush(&mut device, 800, 600);
+
+    assert_eq!(device.programs(), vec!["sprite"]);
+
+    let call = &device.calls()[0];
+
+    match call.geometry {
+        RecordedGeometry::Sprites(ref vertices, ref indices) => {
+            assert_eq!((vertices.len(), indices.len()), (40, 60))
+        },
+        ref other => panic!("Sprites drawn as {:?}", other),
+    }
+    assert_eq!(call.mode, DrawMode::Overlay);
+    assert_eq!(call.texture, Some(texture("hearts")));
+}
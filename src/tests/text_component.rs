@@ -0,0 +1,132 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::controls::{Action, Binding, Controls, Key};
+use core::text::component::TextComponent;
+use core::text::language::{Language, TranslationPiece};
+use core::text::legacy;
+use core::text::style::{ClickAction, TextColor};
+
+#[test]
+fn component_plain_string() {
+    let component = TextComponent::parse("\"Hello\"").unwrap();
+
+    assert_eq!(component.plain(&Language::empty(), &Controls::default()), "Hello");
+}
+
+#[test]
+fn component_inherits_style() {
+    let component = TextComponent::parse(
+        r#"{"text":"A","color":"red","bold":true,"extra":["B",{"text":"C","color":"blue"}]}"#,
+    )
+    .unwrap();
+
+    let runs = component.runs(&Language::empty(), &Controls::default());
+
+    assert_eq!(runs.len(), 3);
+    assert_eq!(runs[1].style.color(), TextColor::Red);
+    assert_eq!(runs[2].style.color(), TextColor::Blue);
+    assert!(runs.iter().all(|run| run.style.bold()));
+}
+
+#[test]
+fn component_click_event() {
+    let component = TextComponent::parse(
+        r#"{"text":"Link","clickEvent":{"action":"open_url","value":"https://litecraft.org"}}"#,
+    )
+    .unwrap();
+
+    let event = component.style().click_event.clone().unwrap();
+
+    assert_eq!(event.action, ClickAction::OpenUrl);
+    assert_eq!(event.value, "https://litecraft.org");
+}
+
+#[test]
+fn component_json_round_trip() {
+    let json = r#"{"translate":"chat.type.text","with":[{"text":"Steve","color":"gold"},"Hi"],"extra":[{"text":"!","italic":true}]}"#;
+    let component = TextComponent::parse(json).unwrap();
+
+    assert_eq!(TextComponent::from_json(&component.to_json()).unwrap(), component);
+}
+
+#[test]
+#[should_panic]
+fn component_invalid() { TextComponent::parse(r#"{"color":"red"}"#).unwrap(); }
+
+#[test]
+fn translation_arguments() {
+    let language = Language::empty();
+
+    assert_eq!(
+        language.format("%2$s loves %1$s 100%%"),
+        vec![
+            TranslationPiece::Argument(1),
+            TranslationPiece::Literal(" loves ".to_owned()),
+            TranslationPiece::Argument(0),
+            TranslationPiece::Literal(" 100%".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn legacy_codes() {
+    let runs = legacy::parse("§aGreen §lbold§r plain");
+
+    assert_eq!(runs.len(), 3);
+    assert_eq!(runs[0].text, "Green ");
+    assert_eq!(runs[0].style.color(), TextColor::Green);
+    assert!(!runs[0].style.bold());
+    assert!(runs[1].style.bold());
+    assert_eq!(runs[1].style.color(), TextColor::Green);
+    assert!(runs[2].style.is_empty());
+}
+
+#[test]
+fn legacy_strip() {
+    assert_eq!(legacy::strip("§4§lRed§z bold§"), "Red bold");
+}
+
+#[test]
+fn legacy_round_trip() {
+    let text = "§6Gold §l§nstyled§7 gray";
+
+    assert_eq!(legacy::format(&legacy::parse(text)), text);
+}
+
+#[test]
+fn legacy_to_component() {
+    let component = legacy::to_component("§cA§9B");
+    let runs = component.runs(&Language::empty(), &Controls::default());
+
+    assert_eq!(component.plain(&Language::empty(), &Controls::default()), "AB");
+    assert_eq!(runs[0].style.color(), TextColor::Red);
+    assert_eq!(runs[1].style.color(), TextColor::Blue);
+}
+
+#[test]
+fn component_keybind_shows_bound_key() {
+    let component = TextComponent::parse(r#"{"keybind":"key.jump"}"#).unwrap();
+    let mut controls = Controls::default();
+
+    assert_eq!(component.plain(&Language::empty(), &controls), "Space");
+
+    controls.set(Action::Jump, Binding::key(Key::J));
+    assert_eq!(component.plain(&Language::empty(), &controls), "J");
+
+    // Unknown actions fall back to their translation
+    let component = TextComponent::parse(r#"{"keybind":"key.unknown"}"#).unwrap();
+    assert_eq!(component.plain(&Language::empty(), &controls), "key.unknown");
+}
@@ -0,0 +1,57 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::tick::{TickTimer, MAX_TICKS_PER_FRAME, TICKS_PER_SECOND};
+
+use std::time::Duration;
+
+#[test]
+fn tick_rate_independent_of_frames() {
+    let mut fast = TickTimer::new(TICKS_PER_SECOND);
+    let mut slow = TickTimer::new(TICKS_PER_SECOND);
+
+    // One second at 144 and 30 frames per second
+    let fast_ticks: u32 = (0..144)
+        .map(|_| fast.advance(Duration::new(0, 1_000_000_000 / 144 + 1)))
+        .sum();
+    let slow_ticks: u32 = (0..30)
+        .map(|_| slow.advance(Duration::new(0, 1_000_000_000 / 30 + 1)))
+        .sum();
+
+    assert_eq!(fast_ticks, TICKS_PER_SECOND);
+    assert_eq!(slow_ticks, TICKS_PER_SECOND);
+    assert_eq!(fast.ticks(), u64::from(TICKS_PER_SECOND));
+}
+
+#[test]
+fn tick_partial() {
+    let mut timer = TickTimer::new(20);
+
+    assert_eq!(timer.advance(Duration::from_millis(25)), 0);
+    assert!((timer.partial_tick() - 0.5).abs() < 1e-6);
+
+    assert_eq!(timer.advance(Duration::from_millis(40)), 1);
+    assert!((timer.partial_tick() - 0.3).abs() < 1e-6);
+}
+
+#[test]
+fn tick_catch_up_capped() {
+    let mut timer = TickTimer::new(20);
+
+    // Short hitch is caught up, a long one is dropped
+    assert_eq!(timer.advance(Duration::from_millis(200)), 4);
+    assert_eq!(timer.advance(Duration::from_secs(5)), MAX_TICKS_PER_FRAME);
+    assert_eq!(timer.advance(Duration::from_millis(50)), 1);
+}
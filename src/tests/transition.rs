@@ -0,0 +1,153 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::arguments::Arguments;
+use core::settings::Settings;
+
+use gfx::canvas::Canvas;
+use gfx::device::{RecordingDevice, RenderDevice};
+use gfx::pencil::Pencil;
+use gfx::scene::{Scene, SceneAction, SceneStack};
+use gfx::transition::{Side, Transition, TransitionKind};
+
+use std::time::Duration;
+
+/// Scene drawing a quad with its own shader program
+struct Backdrop {
+    program: &'static str,
+    overlay: bool,
+}
+
+impl Backdrop {
+    fn new(program: &'static str, overlay: bool) -> Box<Scene> {
+        Box::new(Backdrop { program, overlay })
+    }
+}
+
+impl Scene for Backdrop {
+    fn load(&mut self, _canvas: &mut Canvas) {}
+
+    fn draw(
+        &mut self,
+        canvas: &mut Canvas,
+        device: &mut RenderDevice,
+        partial_tick: f32,
+    ) -> SceneAction {
+        self.draw_offscreen(canvas, device, partial_tick);
+        SceneAction::None
+    }
+
+    fn draw_offscreen(
+        &mut self,
+        _canvas: &mut Canvas,
+        device: &mut RenderDevice,
+        _partial_tick: f32,
+    ) -> bool {
+        Pencil::new(device, self.program).draw();
+        true
+    }
+
+    fn is_overlay(&self) -> bool { self.overlay }
+}
+
+/// Draw both sides of the playing transition, returning their programs
+fn draw_transition(canvas: &mut Canvas, scenes: &mut SceneStack) -> Option<(Vec<String>, Vec<String>)> {
+    let mut from = RecordingDevice::new();
+    let mut to = RecordingDevice::new();
+
+    if !scenes.draw_transition(canvas, &mut from, &mut to, 0.0) {
+        return None;
+    }
+
+    let programs = |device: &RecordingDevice| -> Vec<String> {
+        device
+            .programs()
+            .iter()
+            .map(|program| program.to_string())
+            .collect()
+    };
+
+    Some((programs(&from), programs(&to)))
+}
+
+#[test]
+fn transition_progress() {
+    let fade = Transition::fade(Duration::from_millis(500));
+
+    assert_eq!(fade.progress(Duration::from_millis(0)), 0.0);
+    assert!((fade.progress(Duration::from_millis(250)) - 0.5).abs() < 1e-6);
+    assert_eq!(fade.progress(Duration::from_secs(2)), 1.0);
+
+    // Cuts and empty transitions are over right away
+    assert_eq!(Transition::cut().progress(Duration::from_millis(0)), 1.0);
+    assert_eq!(
+        Transition::slide(Side::Left, Duration::from_secs(0)).progress(Duration::from_millis(0)),
+        1.0
+    );
+}
+
+#[test]
+fn transition_kinds() {
+    let slide = Transition::slide(Side::Top, Duration::from_secs(1));
+
+    assert_eq!(slide.kind(), TransitionKind::Slide(Side::Top));
+    assert_eq!(slide.duration(), Duration::from_secs(1));
+    assert_eq!(Side::Right.direction(), [1.0, 0.0]);
+
+    let custom = Transition::shader("wipe", Duration::from_millis(300));
+    assert_eq!(custom.kind(), TransitionKind::Shader("wipe"));
+}
+
+#[test]
+fn transition_draws_both_scenes() {
+    let mut canvas = Canvas::headless(Arguments::default(), Settings::default());
+    let mut scenes = SceneStack::new(&mut canvas, Backdrop::new("menu", false));
+
+    assert_eq!(draw_transition(&mut canvas, &mut scenes), None);
+
+    // Replaced scene keeps drawing on every frame of the transition
+    let fade = Transition::fade(Duration::from_secs(60));
+    scenes.apply(
+        &mut canvas,
+        SceneAction::Replace(Backdrop::new("world", false)).with_transition(fade),
+    );
+
+    for _ in 0..2 {
+        assert_eq!(
+            draw_transition(&mut canvas, &mut scenes),
+            Some((vec!["menu".to_owned()], vec!["world".to_owned()]))
+        );
+    }
+
+    // Pushed overlays are drawn over the scenes below them
+    scenes.apply(
+        &mut canvas,
+        SceneAction::Push(Backdrop::new("pause", true)).with_transition(fade),
+    );
+
+    assert_eq!(
+        draw_transition(&mut canvas, &mut scenes),
+        Some((
+            vec!["world".to_owned()],
+            vec!["world".to_owned(), "pause".to_owned()]
+        ))
+    );
+
+    // Cuts stop the playing transition
+    scenes.apply(&mut canvas, SceneAction::Pop.with_transition(Transition::cut()));
+
+    assert_eq!(draw_transition(&mut canvas, &mut scenes), None);
+    assert!(!canvas.transitions().active());
+}
@@ -0,0 +1,96 @@
+// The MIT License (MIT)
+// Copyright © 2014-2018 Miguel Peláez <kernelfreeze@outlook.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation
+// files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy,
+// modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES
+// OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use core::controls::{Action, Binding, Key};
+use core::settings::Settings;
+use core::vanilla_options::import_options;
+
+use glium::glutin::MouseButton;
+
+use sound::SoundCategory;
+
+#[test]
+fn vanilla_options_imported() {
+    let data = "version:1631\nfov:1.0\nguiScale:0\nrenderDistance:8\nlang:es_ES\nresourcePacks:[\"\
+                vanilla\",\"file/Low.zip\",\"file/High.zip\"]\nsoundCategory_music:0.5\nkey_key.\
+                forward:key.keyboard.up\nkey_key.jump:key.keyboard.left.shift\nkey_key.attack:key.\
+                mouse.right\nkey_key.smoothCamera:key.keyboard.unknown\nmaxFps:120\nkey_key.unknown:\
+                key.keyboard.a\n";
+
+    let mut settings = Settings::new();
+    let report = import_options(data, &mut settings);
+
+    assert_eq!(settings.fov(), 110);
+    assert_eq!(settings.scale(), 1.0);
+    assert_eq!(settings.render_distance(), 8);
+    assert_eq!(settings.language(), "es_es");
+    assert_eq!(
+        settings.resourcepacks(),
+        &vec!["High".to_owned(), "Low".to_owned()]
+    );
+    assert_eq!(settings.volume(SoundCategory::Music), 0.5);
+
+    let controls = settings.controls();
+
+    assert_eq!(controls.get(Action::Forward), Binding::key(Key::Up));
+    assert_eq!(controls.get(Action::Jump), Binding::key(Key::LShift));
+    assert_eq!(controls.get(Action::Attack), Binding::mouse(MouseButton::Right));
+    assert!(!controls.get(Action::SmoothCamera).is_bound());
+
+    assert_eq!(report.imported.len(), 10);
+    assert_eq!(
+        report.unmapped,
+        vec!["maxFps".to_owned(), "key_key.unknown".to_owned()]
+    );
+}
+
+#[test]
+fn vanilla_key_names() {
+    let names = [
+        ("key.keyboard.a", Key::A),
+        ("key.keyboard.7", Key::Key7),
+        ("key.keyboard.f11", Key::F11),
+        ("key.keyboard.keypad.3", Key::Numpad3),
+        ("key.keyboard.space", Key::Space),
+        ("key.keyboard.grave.accent", Key::Grave),
+    ];
+
+    for &(name, key) in names.iter() {
+        assert_eq!(Binding::from_vanilla_name(name), Some(Binding::key(key)));
+    }
+
+    assert_eq!(Binding::from_vanilla_name("key.keyboard.world.1"), None);
+    assert_eq!(Binding::from_vanilla_name("17"), None);
+}
+
+#[test]
+fn vanilla_gui_scale() {
+    let scales = [(0, 1.0), (1, 0.5), (2, 1.0), (3, 1.5), (4, 2.0)];
+
+    for &(vanilla, scale) in scales.iter() {
+        let mut settings = Settings::new();
+        import_options(&format!("guiScale:{}\n", vanilla), &mut settings);
+
+        assert_eq!(settings.scale(), scale);
+    }
+
+    // Automatic scale grows with the window
+    let mut settings = Settings::new();
+    settings.set_width(1920);
+    settings.set_height(1080);
+    import_options("guiScale:0\n", &mut settings);
+
+    assert_eq!(settings.scale(), 2.0);
+}